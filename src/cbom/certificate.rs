@@ -0,0 +1,99 @@
+//! X.509 parsing for certificate components
+
+use chrono::{DateTime, Duration, Utc};
+use lazy_static::lazy_static;
+use std::path::Path;
+use x509_parser::certificate::X509Certificate;
+use x509_parser::der_parser::oid::Oid;
+use x509_parser::oid_registry::OidRegistry;
+use x509_parser::pem::parse_x509_pem;
+use x509_parser::time::ASN1Time;
+
+use super::CertificateProperties;
+
+/// Certificates expiring within this many days are reported as a risk
+pub const EXPIRY_WARNING_DAYS: i64 = 30;
+
+lazy_static! {
+    static ref OID_NAMES: OidRegistry<'static> = OidRegistry::default().with_crypto().with_x509();
+}
+
+/// Short name of an algorithm OID (`sha256WithRSAEncryption`), or its dotted form when unknown
+fn algorithm_name(oid: &Oid) -> String {
+    OID_NAMES.get(oid).map(|entry| entry.sn().to_string()).unwrap_or_else(|| oid.to_id_string())
+}
+
+fn to_utc(time: ASN1Time) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(time.timestamp(), 0)
+}
+
+fn properties(cert: &X509Certificate) -> CertificateProperties {
+    CertificateProperties {
+        subject_name: Some(cert.subject().to_string()),
+        issuer_name: Some(cert.issuer().to_string()),
+        not_valid_before: to_utc(cert.validity().not_before),
+        not_valid_after: to_utc(cert.validity().not_after),
+        signature_algorithm_ref: Some(algorithm_name(&cert.signature_algorithm.algorithm)),
+        subject_public_key_algorithm_ref: Some(algorithm_name(&cert.public_key().algorithm.algorithm)),
+        certificate_format: Some("X.509".to_string()),
+        certificate_extension: None,
+    }
+}
+
+/// Parse the first certificate in a PEM or DER file; `None` if it isn't a readable certificate
+pub fn parse_certificate_file(path: &Path) -> Option<CertificateProperties> {
+    let bytes = std::fs::read(path).ok()?;
+
+    if bytes.windows(11).any(|w| w == b"-----BEGIN ") {
+        let (_, pem) = parse_x509_pem(&bytes).ok()?;
+        let cert = pem.parse_x509().ok()?;
+        Some(properties(&cert))
+    } else {
+        let (_, cert) = x509_parser::parse_x509_certificate(&bytes).ok()?;
+        Some(properties(&cert))
+    }
+}
+
+/// Parse a DER-encoded certificate, such as one presented in a TLS handshake
+pub fn parse_certificate_der(der: &[u8]) -> Option<CertificateProperties> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    Some(properties(&cert))
+}
+
+/// Whether a certificate has expired (`Some(true)`) or expires within [`EXPIRY_WARNING_DAYS`] (`Some(false)`) as of `now`
+pub fn expiry_risk(not_valid_after: DateTime<Utc>, now: DateTime<Utc>) -> Option<bool> {
+    if not_valid_after <= now {
+        Some(true)
+    } else if not_valid_after <= now + Duration::days(EXPIRY_WARNING_DAYS) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_expiry_risk() {
+        let now = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(expiry_risk(now - Duration::days(1), now), Some(true));
+        assert_eq!(expiry_risk(now + Duration::days(10), now), Some(false));
+        assert_eq!(expiry_risk(now + Duration::days(365), now), None);
+    }
+
+    #[test]
+    fn test_malformed_certificate_ignored() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let pem = dir.path().join("broken.pem");
+        std::fs::write(&pem, "-----BEGIN CERTIFICATE-----\nbm90IGEgY2VydA==\n-----END CERTIFICATE-----\n").unwrap();
+        let der = dir.path().join("random.cer");
+        std::fs::write(&der, [0x30, 0x82, 0xff, 0x00, 0x01]).unwrap();
+
+        assert!(parse_certificate_file(&pem).is_none());
+        assert!(parse_certificate_file(&der).is_none());
+        assert!(parse_certificate_file(&dir.path().join("missing.crt")).is_none());
+    }
+}
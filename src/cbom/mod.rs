@@ -1,12 +1,20 @@
+//! CycloneDX CBOM (Cryptography Bill of Materials) generator
+//! Implements CycloneDX 1.6 specification for cryptographic asset inventory
+
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
+use base64::Engine;
 
-use crate::utils::report::Finding;
+use crate::scanner::code;
+use crate::utils::file_utils::sha256_file;
+use crate::utils::report::{Finding, Severity};
 
-/// CycloneDX CBOM (Cryptography Bill of Materials) generator
-/// Implements CycloneDX 1.6 specification for cryptographic asset inventory
+pub(crate) mod certificate;
+pub mod spdx;
+mod xml;
 
 /// Main CBOM document structure
 #[derive(Serialize, Deserialize, Debug)]
@@ -69,10 +77,46 @@ pub struct CbomComponent {
     pub version: Option<String>,
     /// Component description
     pub description: Option<String>,
+    /// Every place the component was seen
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub evidence: Option<ComponentEvidence>,
     /// Cryptographic properties
     pub crypto_properties: Option<CryptoProperties>,
 }
 
+/// Evidence of where a component was found
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentEvidence {
+    pub occurrences: Vec<Occurrence>,
+}
+
+/// One file (and line, when known) a component was seen in
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Occurrence {
+    pub location: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl ComponentEvidence {
+    /// Occurrences of `findings`, each file and line listed once
+    fn from_findings(findings: &[&Finding]) -> Option<Self> {
+        let mut occurrences: Vec<Occurrence> = Vec::new();
+        for finding in findings {
+            let occurrence = Occurrence {
+                location: finding.file.clone(),
+                line: (finding.line_number > 0).then_some(finding.line_number),
+            };
+            if !occurrences.contains(&occurrence) {
+                occurrences.push(occurrence);
+            }
+        }
+        (!occurrences.is_empty()).then_some(ComponentEvidence { occurrences })
+    }
+}
+
 /// Cryptographic properties of a component
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -241,15 +285,220 @@ pub struct RiskAssessment {
     pub mitigation: Option<String>,
 }
 
+/// Component types allowed by the CycloneDX 1.6 schema
+pub const CYCLONEDX_COMPONENT_TYPES: &[&str] = &[
+    "application", "framework", "library", "container", "platform", "operating-system",
+    "device", "device-driver", "firmware", "file", "machine-learning-model", "data",
+    "cryptographic-asset",
+];
+
+/// Bill of materials standard written by `--cbom`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum SbomFormat {
+    /// CycloneDX 1.6 CBOM, as JSON or XML per `--cbom-format`
+    #[default]
+    Cyclonedx,
+    /// SPDX 2.3 JSON document with one package per detected library
+    Spdx,
+}
+
+/// Kinds of component CryptoScanner emits
+pub const COMPONENT_ROLES: &[&str] = &["application", "library", "keystore", "tls-endpoint"];
+
+/// Built-in role-to-type mapping, in the same `role=type` form accepted as an override
+const DEFAULT_COMPONENT_TYPES: &str =
+    "application=application,library=library,keystore=cryptographic-asset,tls-endpoint=cryptographic-asset";
+
+/// Maps each kind of emitted component onto a CycloneDX component type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentTypeMap {
+    types: HashMap<String, String>,
+}
+
+impl Default for ComponentTypeMap {
+    fn default() -> Self {
+        ComponentTypeMap { types: HashMap::new() }
+            .with_overrides(DEFAULT_COMPONENT_TYPES)
+            .expect("built-in component type mapping is valid")
+    }
+}
+
+impl ComponentTypeMap {
+    /// Apply comma-separated `role=type` overrides, e.g. `keystore=data`.
+    /// Roles must be one of [`COMPONENT_ROLES`] and types one of [`CYCLONEDX_COMPONENT_TYPES`].
+    pub fn with_overrides(mut self, overrides: &str) -> Result<Self, String> {
+        for entry in overrides.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (role, component_type) = entry
+                .split_once('=')
+                .map(|(role, t)| (role.trim().to_lowercase(), t.trim().to_lowercase()))
+                .ok_or_else(|| format!("Expected role=type, got '{}'", entry))?;
+
+            if !COMPONENT_ROLES.contains(&role.as_str()) {
+                return Err(format!("Unknown component role '{}' (expected one of: {})", role, COMPONENT_ROLES.join(", ")));
+            }
+            if !CYCLONEDX_COMPONENT_TYPES.contains(&component_type.as_str()) {
+                return Err(format!(
+                    "'{}' is not a CycloneDX 1.6 component type (expected one of: {})",
+                    component_type,
+                    CYCLONEDX_COMPONENT_TYPES.join(", ")
+                ));
+            }
+            self.types.insert(role, component_type);
+        }
+        Ok(self)
+    }
+
+    /// The CycloneDX component type for a role
+    pub fn type_for(&self, role: &str) -> String {
+        self.types.get(role).cloned().unwrap_or_else(|| "library".to_string())
+    }
+}
+
+/// Parse a `--cbom-component-types` value on top of the built-in mapping
+pub fn parse_component_types(overrides: &str) -> Result<ComponentTypeMap, String> {
+    ComponentTypeMap::default().with_overrides(overrides)
+}
+
+/// Public-key algorithm families that Shor's algorithm breaks on a large quantum computer
+const QUANTUM_VULNERABLE_FAMILIES: &[&str] = &["RSA", "DSA", "ECDSA", "ECDH", "DH", "EdDSA", "Ed25519", "Ed448", "X25519", "X448"];
+
+/// An algorithm the CBOM knows the parameters of, referenced by id from [`code::LIBRARY_ALGORITHMS`]
+struct AlgorithmSpec {
+    id: &'static str,
+    primitive: &'static str,
+    name: &'static str,
+    key_length: Option<u32>,
+    parameter_set: Option<&'static str>,
+    /// Classical security in bits
+    classical: u32,
+    /// NIST post-quantum security category; 0 when a quantum computer breaks it
+    nist_quantum: u32,
+}
+
+const fn spec(
+    id: &'static str,
+    primitive: &'static str,
+    name: &'static str,
+    key_length: Option<u32>,
+    parameter_set: Option<&'static str>,
+    classical: u32,
+    nist_quantum: u32,
+) -> AlgorithmSpec {
+    AlgorithmSpec { id, primitive, name, key_length, parameter_set, classical, nist_quantum }
+}
+
+/// Typical parameters of the algorithms libraries provide; hashes are rated by collision resistance
+const ALGORITHMS: &[AlgorithmSpec] = &[
+    spec("AES-128", "symmetric-encryption", "AES", Some(128), None, 128, 1),
+    spec("AES-256", "symmetric-encryption", "AES", Some(256), None, 256, 5),
+    spec("AES-256-GCM", "authenticated-encryption", "AES-GCM", Some(256), None, 256, 5),
+    spec("ChaCha20-Poly1305", "authenticated-encryption", "ChaCha20-Poly1305", Some(256), None, 256, 5),
+    spec("XSalsa20-Poly1305", "authenticated-encryption", "XSalsa20-Poly1305", Some(256), None, 256, 5),
+    spec("3DES", "symmetric-encryption", "3DES", Some(168), None, 112, 0),
+    spec("DES", "symmetric-encryption", "DES", Some(56), None, 56, 0),
+    spec("RC4", "stream-cipher", "RC4", Some(128), None, 0, 0),
+    spec("SHA-1", "hash", "SHA-1", None, None, 63, 0),
+    spec("MD5", "hash", "MD5", None, None, 18, 0),
+    spec("SHA-256", "hash", "SHA-256", None, None, 128, 2),
+    spec("SHA-512", "hash", "SHA-512", None, None, 256, 5),
+    spec("SHA3-256", "hash", "SHA3-256", None, None, 128, 2),
+    spec("BLAKE2b", "hash", "BLAKE2b", None, Some("512"), 256, 5),
+    spec("HMAC-SHA256", "mac", "HMAC-SHA256", Some(256), None, 256, 5),
+    spec("HKDF-SHA256", "kdf", "HKDF-SHA256", None, None, 256, 5),
+    spec("PBKDF2-SHA256", "kdf", "PBKDF2", None, Some("SHA-256"), 128, 2),
+    spec("bcrypt", "kdf", "bcrypt", None, None, 128, 1),
+    spec("scrypt", "kdf", "scrypt", None, None, 128, 1),
+    spec("Argon2id", "kdf", "Argon2id", None, None, 128, 1),
+    spec("RSA-2048", "digital-signature", "RSA", Some(2048), None, 112, 0),
+    spec("DSA-2048", "digital-signature", "DSA", Some(2048), None, 112, 0),
+    spec("ECDSA-P256", "digital-signature", "ECDSA", None, Some("P-256"), 128, 0),
+    spec("ECDH-P256", "key-agreement", "ECDH", None, Some("P-256"), 128, 0),
+    spec("Ed25519", "digital-signature", "Ed25519", None, None, 128, 0),
+    spec("X25519", "key-agreement", "X25519", None, None, 128, 0),
+    spec("ML-KEM-768", "kem", "ML-KEM", None, Some("768"), 192, 3),
+    spec("ML-DSA-65", "digital-signature", "ML-DSA", None, Some("65"), 192, 3),
+    spec("SLH-DSA-SHA2-128s", "digital-signature", "SLH-DSA", None, Some("SHA2-128s"), 128, 1),
+];
+
+/// Post-quantum schemes by the name fragments of libraries implementing just that scheme
+const PQC_SCHEMES: &[(&[&str], &str)] = &[
+    (&["kyber", "mlkem", "ml-kem"], "ML-KEM-768"),
+    (&["dilithium", "mldsa", "ml-dsa"], "ML-DSA-65"),
+    (&["sphincs", "slhdsa", "slh-dsa"], "SLH-DSA-SHA2-128s"),
+];
+
+/// Name fragments of libraries bundling all of [`PQC_SCHEMES`] (liboqs, pqcrypto, ...)
+const PQC_LIBRARIES: &[&str] = &["oqs", "pqcrypto", "post-quantum", "pqc"];
+
+/// Name fragments of wrappers and forks (`pyopenssl`, `boringssl`) and the library whose algorithms they share
+const LIBRARY_FAMILIES: &[(&str, &str)] = &[
+    ("openssl", "openssl"),
+    ("boringssl", "openssl"),
+    ("bouncycastle", "bouncycastle"),
+    ("sodium", "libsodium"),
+    ("mbedtls", "mbedtls"),
+    ("wolfssl", "wolfssl"),
+];
+
+/// Whether an algorithm belongs to a classical public-key family, e.g. `RSA` or `ECDH-P256`
+pub fn is_quantum_vulnerable(algorithm_name: &str) -> bool {
+    let family = algorithm_name.split(['-', '_', ' ']).next().unwrap_or_default();
+    QUANTUM_VULNERABLE_FAMILIES.iter().any(|f| f.eq_ignore_ascii_case(family))
+}
+
+/// Library findings grouped by library name and version, in name order.
+/// Shared by the CycloneDX and SPDX generators so both list the same libraries.
+pub(crate) fn library_groups(findings: &[Finding]) -> BTreeMap<(String, Option<String>), Vec<&Finding>> {
+    let mut groups: BTreeMap<(String, Option<String>), Vec<&Finding>> = BTreeMap::new();
+    for finding in findings.iter().filter(|finding| finding.category == "library") {
+        groups.entry((finding.keyword.clone(), finding.version.clone())).or_default().push(finding);
+    }
+    groups
+}
+
+/// Keystore findings grouped by file content (their `file_hash`, or the file hashed now), so
+/// copies and links of one keystore become a single component; unreadable files are grouped
+/// by path. Groups keep the order found.
+fn keystore_groups(findings: &[Finding]) -> Vec<Vec<&Finding>> {
+    let mut groups: Vec<Vec<&Finding>> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for finding in findings.iter().filter(|finding| finding.category == "keystore") {
+        let key = match &finding.file_hash {
+            Some(digest) => digest.clone(),
+            None => match sha256_file(Path::new(&finding.file)) {
+                Ok((_, digest)) => digest,
+                Err(_) => format!("path:{}", finding.file),
+            },
+        };
+        match index.get(&key) {
+            Some(&i) => groups[i].push(finding),
+            None => {
+                index.insert(key, groups.len());
+                groups.push(vec![finding]);
+            }
+        }
+    }
+    groups
+}
+
 /// CBOM Generator implementation
 pub struct CbomGenerator;
 
 impl CbomGenerator {
     /// Generate CBOM from CryptoScanner findings
     pub fn generate_cbom(findings: &[Finding], target_component: Option<String>) -> Result<CbomDocument, Box<dyn std::error::Error>> {
+        Self::generate_cbom_with_types(findings, target_component, &ComponentTypeMap::default())
+    }
+
+    /// Generate CBOM from CryptoScanner findings using a custom component type mapping
+    pub fn generate_cbom_with_types(
+        findings: &[Finding],
+        target_component: Option<String>,
+        component_types: &ComponentTypeMap,
+    ) -> Result<CbomDocument, Box<dyn std::error::Error>> {
         let timestamp = Utc::now();
         // Format serial number per RFC 4122 URN format as required by CycloneDX 1.6
-        let serial_number = format!("urn:uuid:{}", Uuid::new_v4().to_string());
+        let serial_number = format!("urn:uuid:{}", Uuid::new_v4());
         
         // Create tool metadata
         let tool = CbomTool {
@@ -261,11 +510,12 @@ impl CbomGenerator {
 
         // Create target component
         let target = CbomComponent {
-            component_type: "application".to_string(),
+            component_type: component_types.type_for("application"),
             bom_ref: "target-component".to_string(),
             name: target_component.unwrap_or_else(|| "scanned-application".to_string()),
             version: Some("unknown".to_string()),
             description: Some("Application analyzed by CryptoScanner".to_string()),
+            evidence: None,
             crypto_properties: None,
         };
 
@@ -276,10 +526,10 @@ impl CbomGenerator {
         };
 
         // Generate components from findings
-        let components = Self::generate_components(findings)?;
+        let components = Self::generate_components(findings, component_types)?;
         
         // Generate declarations
-        let declarations = Self::generate_declarations(findings)?;
+        let declarations = Self::generate_declarations(findings, &components)?;
 
         Ok(CbomDocument {
             bom_format: "CycloneDX".to_string(),
@@ -293,22 +543,11 @@ impl CbomGenerator {
     }
 
     /// Generate CBOM components from scan findings
-    fn generate_components(findings: &[Finding]) -> Result<Vec<CbomComponent>, Box<dyn std::error::Error>> {
+    fn generate_components(findings: &[Finding], component_types: &ComponentTypeMap) -> Result<Vec<CbomComponent>, Box<dyn std::error::Error>> {
         let mut components = Vec::new();
-        let mut processed_libraries: HashSet<String> = HashSet::new();
-
-        // Group findings by library/component
-        let mut library_findings: HashMap<String, Vec<&Finding>> = HashMap::new();
-        
-        for finding in findings {
-            if finding.category == "library" {
-                let key = format!("{}_{}", finding.keyword, finding.version.as_deref().unwrap_or("unknown"));
-                library_findings.entry(key).or_default().push(finding);
-            }
-        }
 
         // Generate components for each library
-        for (library_key, lib_findings) in library_findings {
+        for lib_findings in library_groups(findings).into_values() {
             if let Some(first_finding) = lib_findings.first() {
                 let component_id = format!("crypto-lib-{}", Uuid::new_v4().to_string()[..8].to_lowercase());
                 
@@ -323,11 +562,12 @@ impl CbomGenerator {
                 };
 
                 let component = CbomComponent {
-                    component_type: "library".to_string(),
+                    component_type: component_types.type_for("library"),
                     bom_ref: component_id,
                     name: first_finding.keyword.clone(),
                     version: first_finding.version.clone(),
                     description: Some(format!("Cryptographic library detected in {}", first_finding.file)),
+                    evidence: ComponentEvidence::from_findings(&lib_findings),
                     crypto_properties: Some(crypto_properties),
                 };
 
@@ -335,85 +575,275 @@ impl CbomGenerator {
             }
         }
 
-        // Generate components for keystore files
+        // Generate one component per distinct keystore; copies and links of it are its occurrences
+        for group in keystore_groups(findings) {
+            let finding = group[0];
+            let component_id = format!("keystore-{}", Uuid::new_v4().to_string()[..8].to_lowercase());
+            
+            let crypto_properties = match finding.file.split('.').next_back() {
+                Some("pem") | Some("crt") | Some("cer") => {
+                    // Unreadable or malformed certificates keep the bare X.509 entry
+                    let certificate_properties = certificate::parse_certificate_file(Path::new(&finding.file))
+                        .unwrap_or(CertificateProperties {
+                            subject_name: None,
+                            issuer_name: None,
+                            not_valid_before: None,
+                            not_valid_after: None,
+                            signature_algorithm_ref: None,
+                            subject_public_key_algorithm_ref: None,
+                            certificate_format: Some("X.509".to_string()),
+                            certificate_extension: None,
+                        });
+                    Some(CryptoProperties {
+                        asset_type: CryptoAssetType::Certificate,
+                        algorithm_properties: None,
+                        certificate_properties: Some(certificate_properties),
+                        related_crypto_material_properties: None,
+                        protocol_properties: None,
+                    })
+                },
+                Some("key") | Some("p12") | Some("jks") | Some("pfx") => {
+                    Some(CryptoProperties {
+                        asset_type: CryptoAssetType::Key,
+                        algorithm_properties: None,
+                        certificate_properties: None,
+                        related_crypto_material_properties: Some(vec![RelatedCryptoMaterial {
+                            material_type: "private-key".to_string(),
+                            id: component_id.clone(),
+                            state: Some("unknown".to_string()),
+                            algorithm_ref: None,
+                            creation_time: None,
+                            activation_time: None,
+                            update_time: None,
+                            expiration_time: None,
+                        }]),
+                        protocol_properties: None,
+                    })
+                },
+                _ => None,
+            };
+
+            let component = CbomComponent {
+                component_type: component_types.type_for("keystore"),
+                bom_ref: component_id,
+                name: finding.file.split('/').next_back().unwrap_or(&finding.file).to_string(),
+                version: None,
+                description: Some(match group.len() {
+                    1 => format!("Cryptographic keystore file: {}", finding.file),
+                    n => format!("Cryptographic keystore file: {} and {} identical copies", finding.file, n - 1),
+                }),
+                evidence: ComponentEvidence::from_findings(&group),
+                crypto_properties,
+            };
+
+            components.push(component);
+        }
+
+        // Generate components for authorized_keys/known_hosts entries
         for finding in findings {
-            if finding.category == "keystore" {
-                let component_id = format!("keystore-{}", Uuid::new_v4().to_string()[..8].to_lowercase());
-                
-                let crypto_properties = match finding.file.split('.').last() {
-                    Some("pem") | Some("crt") | Some("cer") => {
-                        Some(CryptoProperties {
-                            asset_type: CryptoAssetType::Certificate,
-                            algorithm_properties: None,
-                            certificate_properties: Some(CertificateProperties {
-                                subject_name: None,
-                                issuer_name: None,
-                                not_valid_before: None,
-                                not_valid_after: None,
-                                signature_algorithm_ref: None,
-                                subject_public_key_algorithm_ref: None,
-                                certificate_format: Some("X.509".to_string()),
-                                certificate_extension: None,
-                            }),
-                            related_crypto_material_properties: None,
-                            protocol_properties: None,
-                        })
-                    },
-                    Some("key") | Some("p12") | Some("jks") | Some("pfx") => {
-                        Some(CryptoProperties {
-                            asset_type: CryptoAssetType::Key,
-                            algorithm_properties: None,
-                            certificate_properties: None,
-                            related_crypto_material_properties: Some(vec![RelatedCryptoMaterial {
-                                material_type: "private-key".to_string(),
-                                id: component_id.clone(),
-                                state: Some("unknown".to_string()),
-                                algorithm_ref: None,
-                                creation_time: None,
-                                activation_time: None,
-                                update_time: None,
-                                expiration_time: None,
-                            }]),
-                            protocol_properties: None,
-                        })
-                    },
-                    _ => None,
+            if finding.category == "ssh-key-material" {
+                let component_id = format!("ssh-key-{}", Uuid::new_v4().to_string()[..8].to_lowercase());
+                // Revoked known_hosts entries are kept around only to be rejected
+                let state = if finding.context.contains("@revoked") { "deactivated" } else { "active" };
+
+                let crypto_properties = CryptoProperties {
+                    asset_type: CryptoAssetType::RelatedCryptoMaterial,
+                    algorithm_properties: None,
+                    certificate_properties: None,
+                    related_crypto_material_properties: Some(vec![RelatedCryptoMaterial {
+                        material_type: "public-key".to_string(),
+                        id: component_id.clone(),
+                        state: Some(state.to_string()),
+                        algorithm_ref: Some(finding.keyword.clone()),
+                        creation_time: None,
+                        activation_time: None,
+                        update_time: None,
+                        expiration_time: None,
+                    }]),
+                    protocol_properties: None,
                 };
 
+                let file_name = finding.file.split('/').next_back().unwrap_or(&finding.file);
                 let component = CbomComponent {
-                    component_type: "file".to_string(),
+                    component_type: component_types.type_for("keystore"),
                     bom_ref: component_id,
-                    name: finding.file.split('/').last().unwrap_or(&finding.file).to_string(),
+                    name: format!("{} ({}:{})", finding.keyword, file_name, finding.line_number),
                     version: None,
-                    description: Some(format!("Cryptographic keystore file: {}", finding.file)),
-                    crypto_properties,
+                    description: Some(format!("SSH public key in {}: {}", finding.file, finding.context)),
+                    evidence: None,
+                    crypto_properties: Some(crypto_properties),
                 };
 
                 components.push(component);
             }
         }
 
+        // Generate components for live TLS endpoints (--tls-scan) and the certificates they present
+        for finding in findings {
+            match finding.category.as_str() {
+                "tls-endpoint" => {
+                    let version = finding.version.as_deref().map(|v| v.trim_start_matches("TLSv").to_string());
+                    components.push(CbomComponent {
+                        component_type: component_types.type_for("tls-endpoint"),
+                        bom_ref: format!("tls-{}", Uuid::new_v4().to_string()[..8].to_lowercase()),
+                        name: finding.file.trim_start_matches("tls://").to_string(),
+                        version: version.clone(),
+                        description: Some(finding.context.clone()),
+                        evidence: None,
+                        crypto_properties: Some(CryptoProperties {
+                            asset_type: CryptoAssetType::Protocol,
+                            algorithm_properties: None,
+                            certificate_properties: None,
+                            related_crypto_material_properties: None,
+                            protocol_properties: Some(ProtocolProperties {
+                                protocol_type: "tls".to_string(),
+                                version,
+                                cipher_suites: Some(vec![CipherSuite {
+                                    name: finding.keyword.clone(),
+                                    algorithms: Vec::new(),
+                                    identifiers: None,
+                                }]),
+                                ikev2_transform_types: None,
+                                cryptographic_functions: None,
+                            }),
+                        }),
+                    });
+                }
+                "tls-certificate" => {
+                    let certificate_properties = base64::engine::general_purpose::STANDARD
+                        .decode(&finding.line_content)
+                        .ok()
+                        .and_then(|der| certificate::parse_certificate_der(&der));
+                    components.push(CbomComponent {
+                        component_type: component_types.type_for("tls-endpoint"),
+                        bom_ref: format!("tls-cert-{}", Uuid::new_v4().to_string()[..8].to_lowercase()),
+                        name: format!("{} ({} #{})", finding.keyword, finding.file.trim_start_matches("tls://"), finding.line_number),
+                        version: None,
+                        description: Some(finding.context.clone()),
+                        evidence: None,
+                        crypto_properties: Some(CryptoProperties {
+                            asset_type: CryptoAssetType::Certificate,
+                            algorithm_properties: None,
+                            certificate_properties,
+                            related_crypto_material_properties: None,
+                            protocol_properties: None,
+                        }),
+                    });
+                }
+                _ => {}
+            }
+        }
+
         Ok(components)
     }
 
     /// Generate cryptographic declarations
-    fn generate_declarations(findings: &[Finding]) -> Result<CbomDeclarations, Box<dyn std::error::Error>> {
+    fn generate_declarations(findings: &[Finding], components: &[CbomComponent]) -> Result<CbomDeclarations, Box<dyn std::error::Error>> {
         let mut risk_assessments = Vec::new();
         
-        // Assess hardcoded secrets risk
-        let secret_count = findings.iter().filter(|f| f.category == "secret").count();
+        // Assess hardcoded secrets risk from the most severe secret, falling back to the count when none are rated
+        let secrets: Vec<&Finding> = findings.iter().filter(|f| f.category == "secret").collect();
+        let secret_count = secrets.len();
         if secret_count > 0 {
-            let risk_level = match secret_count {
-                1..=2 => "medium",
-                3..=5 => "high", 
-                _ => "critical",
+            let risk_level = match secrets.iter().filter_map(|f| f.severity).max() {
+                Some(severity) => severity.to_string(),
+                None => match secret_count {
+                    1..=2 => "medium",
+                    3..=5 => "high",
+                    _ => "critical",
+                }
+                .to_string(),
             };
-            
+
+            // The weaknesses and remediation guides named by the matching patterns
+            let cwes: BTreeSet<&str> = secrets.iter().filter_map(|f| f.cwe.as_deref()).collect();
+            let guides: BTreeSet<&str> = secrets.iter().filter_map(|f| f.remediation.as_deref()).collect();
+            let mut description = format!("Found {} hardcoded secrets in codebase", secret_count);
+            if !cwes.is_empty() {
+                description.push_str(&format!(" ({})", cwes.into_iter().collect::<Vec<_>>().join(", ")));
+            }
+            let mut mitigation = "Rotate exposed secrets and implement secure secret management".to_string();
+            if !guides.is_empty() {
+                mitigation.push_str(&format!("; see {}", guides.into_iter().collect::<Vec<_>>().join(", ")));
+            }
+
             risk_assessments.push(RiskAssessment {
                 category: "hardcoded-secrets".to_string(),
-                level: risk_level.to_string(),
-                description: format!("Found {} hardcoded secrets in codebase", secret_count),
-                mitigation: Some("Rotate exposed secrets and implement secure secret management".to_string()),
+                level: risk_level,
+                description,
+                mitigation: Some(mitigation),
+            });
+        }
+
+        // Dependencies pinned to versions with known advisories
+        for finding in findings.iter().filter(|f| f.category == "vulnerable-crypto-dependency") {
+            risk_assessments.push(RiskAssessment {
+                category: "vulnerable-crypto-dependency".to_string(),
+                level: finding.severity.unwrap_or(Severity::High).to_string(),
+                description: format!("{} in {}", finding.context, finding.file),
+                mitigation: Some(format!("Upgrade {} to a version without the advisory", finding.keyword)),
+            });
+        }
+
+        // Weak primitives called from source; informational uses (checksums, cache keys) don't count
+        let weak: Vec<&Finding> = findings
+            .iter()
+            .filter(|f| f.category == "weak-crypto" && f.severity.is_some_and(|s| s > Severity::Info))
+            .collect();
+        if let Some(level) = weak.iter().filter_map(|f| f.severity).max() {
+            let algorithms: BTreeSet<&str> = weak.iter().map(|f| f.keyword.as_str()).collect();
+            risk_assessments.push(RiskAssessment {
+                category: "weak-crypto".to_string(),
+                level: level.to_string(),
+                description: format!(
+                    "{} use(s) of weak or deprecated cryptography: {}",
+                    weak.len(),
+                    algorithms.into_iter().collect::<Vec<_>>().join(", ")
+                ),
+                mitigation: Some(
+                    "Replace MD5/SHA-1 with SHA-256 or better and DES/3DES/RC4 with AES-GCM or ChaCha20-Poly1305; \
+                     avoid ECB mode and generate a random IV for every message"
+                        .to_string(),
+                ),
+            });
+        }
+
+        // Post-quantum readiness: classical public-key algorithms the detected libraries provide
+        let mut vulnerable = BTreeSet::new();
+        let mut quantum_safe = BTreeSet::new();
+        for component in components {
+            let Some(algorithms) = component.crypto_properties.as_ref().and_then(|p| p.algorithm_properties.as_ref()) else {
+                continue;
+            };
+            for algorithm in algorithms {
+                let entry = format!("{} ({})", algorithm.algorithm_name, component.name);
+                if is_quantum_vulnerable(&algorithm.algorithm_name) {
+                    vulnerable.insert(entry);
+                } else if algorithm.quantum_safe == Some(true)
+                    && matches!(algorithm.primitive.as_str(), "kem" | "digital-signature" | "key-agreement")
+                {
+                    quantum_safe.insert(entry);
+                }
+            }
+        }
+        if !vulnerable.is_empty() {
+            let mut description = format!(
+                "{} quantum-vulnerable algorithm(s) in use: {}",
+                vulnerable.len(),
+                vulnerable.into_iter().collect::<Vec<_>>().join(", ")
+            );
+            if !quantum_safe.is_empty() {
+                description.push_str(&format!("; post-quantum already in use: {}", quantum_safe.into_iter().collect::<Vec<_>>().join(", ")));
+            }
+            risk_assessments.push(RiskAssessment {
+                category: "quantum-vulnerable".to_string(),
+                level: "high".to_string(),
+                description,
+                mitigation: Some(
+                    "Migrate key establishment to ML-KEM (FIPS 203) and signatures to ML-DSA (FIPS 204) or SLH-DSA (FIPS 205), \
+                     using hybrid modes during the transition"
+                        .to_string(),
+                ),
             });
         }
 
@@ -433,6 +863,40 @@ impl CbomGenerator {
             });
         }
 
+        // Assess certificate expiry
+        let now = Utc::now();
+        for component in components {
+            let Some(cert) = component.crypto_properties.as_ref().and_then(|p| p.certificate_properties.as_ref()) else {
+                continue;
+            };
+            let Some(not_after) = cert.not_valid_after else {
+                continue;
+            };
+            let subject = cert.subject_name.as_deref().unwrap_or(&component.name);
+
+            match certificate::expiry_risk(not_after, now) {
+                Some(true) => risk_assessments.push(RiskAssessment {
+                    category: "expired-certificate".to_string(),
+                    level: "high".to_string(),
+                    description: format!("Certificate {} ({}) expired on {}", component.name, subject, not_after.format("%Y-%m-%d")),
+                    mitigation: Some("Renew the certificate or remove it if it is no longer used".to_string()),
+                }),
+                Some(false) => risk_assessments.push(RiskAssessment {
+                    category: "expiring-certificate".to_string(),
+                    level: "medium".to_string(),
+                    description: format!(
+                        "Certificate {} ({}) expires on {}, within {} days",
+                        component.name,
+                        subject,
+                        not_after.format("%Y-%m-%d"),
+                        certificate::EXPIRY_WARNING_DAYS
+                    ),
+                    mitigation: Some("Schedule renewal before the certificate expires".to_string()),
+                }),
+                None => {}
+            }
+        }
+
         Ok(CbomDeclarations {
             assessor: Some("CryptoScanner v0.1.0".to_string()),
             assessment_date: Some(Utc::now()),
@@ -441,75 +905,64 @@ impl CbomGenerator {
         })
     }
 
-    /// Infer algorithm properties from library name
+    /// Infer algorithm properties from library name: the library's entry in the shared
+    /// [`code::LIBRARY_ALGORITHMS`] table, else what its name says it implements
     fn infer_algorithm_properties(library_name: &str) -> Vec<AlgorithmProperties> {
         let library_lower = library_name.to_lowercase();
-        let mut algorithms = Vec::new();
-
-        // Common cryptographic libraries and their algorithms
-        match library_lower.as_str() {
-            name if name.contains("openssl") => {
-                algorithms.push(AlgorithmProperties {
-                    primitive: "symmetric-encryption".to_string(),
-                    algorithm_name: "AES".to_string(),
-                    key_length: Some(256),
-                    cryptographic_strength: Some(256),
-                    quantum_safe: Some(false),
-                    classical_security_level: Some(256),
-                    nist_security_level: Some(5),
-                    parameter_set_identifier: None,
-                });
-                algorithms.push(AlgorithmProperties {
-                    primitive: "digital-signature".to_string(),
-                    algorithm_name: "RSA".to_string(),
-                    key_length: Some(2048),
-                    cryptographic_strength: Some(112),
-                    quantum_safe: Some(false),
-                    classical_security_level: Some(112),
-                    nist_security_level: Some(3),
-                    parameter_set_identifier: None,
-                });
-            },
-            name if name.contains("bouncycastle") => {
-                algorithms.push(AlgorithmProperties {
-                    primitive: "symmetric-encryption".to_string(),
-                    algorithm_name: "AES".to_string(),
-                    key_length: Some(256),
-                    cryptographic_strength: Some(256),
-                    quantum_safe: Some(false),
-                    classical_security_level: Some(256),
-                    nist_security_level: Some(5),
-                    parameter_set_identifier: None,
-                });
-            },
-            name if name.contains("crypto") => {
-                algorithms.push(AlgorithmProperties {
-                    primitive: "hash".to_string(),
-                    algorithm_name: "SHA-256".to_string(),
-                    key_length: None,
-                    cryptographic_strength: Some(256),
-                    quantum_safe: Some(false),
-                    classical_security_level: Some(256),
-                    nist_security_level: Some(5),
-                    parameter_set_identifier: None,
-                });
-            },
-            _ => {
-                // Generic fallback
-                algorithms.push(AlgorithmProperties {
-                    primitive: "unknown".to_string(),
-                    algorithm_name: library_name.to_string(),
-                    key_length: None,
-                    cryptographic_strength: None,
-                    quantum_safe: Some(false),
-                    classical_security_level: None,
-                    nist_security_level: None,
-                    parameter_set_identifier: None,
-                });
+
+        let ids: Vec<&str> = match code::library_algorithms(library_name) {
+            Some(ids) => ids.to_vec(),
+            None => {
+                // Post-quantum libraries: the scheme they are named after, or every NIST standard for general-purpose ones
+                let mut ids: Vec<&str> = PQC_SCHEMES
+                    .iter()
+                    .filter(|(fragments, _)| fragments.iter().any(|fragment| library_lower.contains(fragment)))
+                    .map(|(_, id)| *id)
+                    .collect();
+                if ids.is_empty() && PQC_LIBRARIES.iter().any(|fragment| library_lower.contains(fragment)) {
+                    ids = PQC_SCHEMES.iter().map(|(_, id)| *id).collect();
+                }
+                if ids.is_empty() {
+                    ids = LIBRARY_FAMILIES
+                        .iter()
+                        .find(|(fragment, _)| library_lower.contains(fragment))
+                        .and_then(|(_, library)| code::library_algorithms(library))
+                        .map(|ids| ids.to_vec())
+                        .unwrap_or_default();
+                }
+                ids
             }
+        };
+
+        let algorithms: Vec<AlgorithmProperties> = ids
+            .into_iter()
+            .filter_map(|id| ALGORITHMS.iter().find(|spec| spec.id == id))
+            .map(|spec| AlgorithmProperties {
+                primitive: spec.primitive.to_string(),
+                algorithm_name: spec.name.to_string(),
+                key_length: spec.key_length,
+                cryptographic_strength: Some(spec.classical),
+                quantum_safe: Some(spec.nist_quantum > 0),
+                classical_security_level: Some(spec.classical),
+                nist_security_level: Some(spec.nist_quantum),
+                parameter_set_identifier: spec.parameter_set.map(str::to_string),
+            })
+            .collect();
+        if !algorithms.is_empty() {
+            return algorithms;
         }
 
-        algorithms
+        // Generic fallback
+        vec![AlgorithmProperties {
+            primitive: "unknown".to_string(),
+            algorithm_name: library_name.to_string(),
+            key_length: None,
+            cryptographic_strength: None,
+            quantum_safe: Some(false),
+            classical_security_level: None,
+            nist_security_level: None,
+            parameter_set_identifier: None,
+        }]
     }
 
     /// Export CBOM to JSON format
@@ -518,17 +971,15 @@ impl CbomGenerator {
     }
 
     /// Export CBOM to XML format (basic implementation)
+    /// Export CBOM as a CycloneDX 1.6 XML document
     pub fn export_xml(cbom: &CbomDocument) -> Result<String, Box<dyn std::error::Error>> {
-        // Basic XML serialization - in production you'd use a proper XML library
-        let json = Self::export_json(cbom)?;
-        Ok(format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<cbom>\n<!-- JSON representation: -->\n<!-- {} -->\n</cbom>", json))
+        Ok(xml::to_xml(cbom)?)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::report::Finding;
 
     #[test]
     fn test_cbom_generation() {
@@ -536,6 +987,9 @@ mod tests {
             Finding {
                 file: "/test/crypto.rs".to_string(),
                 line_number: 1,
+                end_line_number: None,
+                column_start: None,
+                column_end: None,
                 line_content: "use openssl::crypto;".to_string(),
                 match_type: "import".to_string(),
                 keyword: "openssl".to_string(),
@@ -544,10 +998,17 @@ mod tests {
                 language: "Rust".to_string(),
                 source: "import".to_string(),
                 category: "library".to_string(),
+                severity: None,
+                file_hash: None,
+                cwe: None,
+                remediation: None,
             },
             Finding {
                 file: "/test/cert.pem".to_string(),
                 line_number: 1,
+                end_line_number: None,
+                column_start: None,
+                column_end: None,
                 line_content: "-----BEGIN CERTIFICATE-----".to_string(),
                 match_type: "file".to_string(),
                 keyword: "certificate".to_string(),
@@ -556,6 +1017,10 @@ mod tests {
                 language: "PEM".to_string(),
                 source: "file".to_string(),
                 category: "keystore".to_string(),
+                severity: None,
+                file_hash: None,
+                cwe: None,
+                remediation: None,
             },
         ];
 
@@ -568,6 +1033,465 @@ mod tests {
         assert!(cbom.declarations.is_some());
     }
 
+    #[test]
+    fn test_component_type_mapping() {
+        let findings = vec![Finding {
+            file: "/test/server.key".to_string(),
+            line_number: 0,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: String::new(),
+            match_type: "file".to_string(),
+            keyword: "private key".to_string(),
+            context: "file".to_string(),
+            version: None,
+            language: "PEM".to_string(),
+            source: "file".to_string(),
+            category: "keystore".to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        }];
+
+        let cbom = CbomGenerator::generate_cbom(&findings, None).unwrap();
+        assert_eq!(cbom.components[0].component_type, "cryptographic-asset");
+        assert_eq!(cbom.metadata.component.component_type, "application");
+
+        let types = parse_component_types("keystore=data, application=platform").unwrap();
+        let cbom = CbomGenerator::generate_cbom_with_types(&findings, None, &types).unwrap();
+        assert_eq!(cbom.components[0].component_type, "data");
+        assert_eq!(cbom.metadata.component.component_type, "platform");
+        assert!(cbom
+            .components
+            .iter()
+            .chain(std::iter::once(&cbom.metadata.component))
+            .all(|c| CYCLONEDX_COMPONENT_TYPES.contains(&c.component_type.as_str())));
+    }
+
+    #[test]
+    fn test_ssh_key_material_components() {
+        let findings = crate::scanner::ssh_keys::scan_content(
+            std::path::Path::new("/home/alice/.ssh/authorized_keys"),
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGq1 alice@laptop\n",
+        );
+
+        let cbom = CbomGenerator::generate_cbom(&findings, None).unwrap();
+        assert_eq!(cbom.components.len(), 1);
+        assert_eq!(cbom.components[0].name, "ssh-ed25519 (authorized_keys:1)");
+
+        let json = serde_json::to_value(&cbom.components[0]).unwrap();
+        let properties = &json["cryptoProperties"];
+        assert_eq!(properties["assetType"], "related-crypto-material");
+        assert_eq!(properties["relatedCryptoMaterialProperties"][0]["type"], "public-key");
+        assert_eq!(properties["relatedCryptoMaterialProperties"][0]["algorithmRef"], "ssh-ed25519");
+    }
+
+    #[test]
+    fn test_component_type_validation() {
+        assert!(parse_component_types("keystore=keystore").is_err());
+        assert!(parse_component_types("certificate=data").is_err());
+        assert!(parse_component_types("keystore").is_err());
+        assert_eq!(parse_component_types("").unwrap(), ComponentTypeMap::default());
+    }
+
+    const EXPIRED_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIBHDCBw6ADAgECAgEBMAoGCCqGSM49BAMCMBIxEDAOBgNVBAMMB1Rlc3QgQ0Ew
+HhcNMjAwMTAxMDAwMDAwWhcNMjEwMTAxMDAwMDAwWjAeMRwwGgYDVQQDDBNleHBp
+cmVkLmV4YW1wbGUuY29tMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEvVzM8m+1
+SwOGxA0dW3bOdYyB1kjo1ncxWMP+QrYEAlObknnJszXqs4FKufWkyApGn/CJGQ/m
+ISebUqkM+5htiTAKBggqhkjOPQQDAgNIADBFAiEA6moobyNje5pBQGSv5GhN91Zb
+QfWBIQBKFf70d/D2HIECIDVQyuTGRChoZJcE1x2QeKka3blahsNGnf/2cMnF+Kkl
+-----END CERTIFICATE-----
+";
+
+    const VALID_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIBHDCBw6ADAgECAgEBMAoGCCqGSM49BAMCMBIxEDAOBgNVBAMMB1Rlc3QgQ0Ew
+IBcNMjAwMTAxMDAwMDAwWhgPMjEyMDAxMDEwMDAwMDBaMBwxGjAYBgNVBAMMEXZh
+bGlkLmV4YW1wbGUuY29tMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEvVzM8m+1
+SwOGxA0dW3bOdYyB1kjo1ncxWMP+QrYEAlObknnJszXqs4FKufWkyApGn/CJGQ/m
+ISebUqkM+5htiTAKBggqhkjOPQQDAgNIADBFAiBNV8C9CJIOU6qgKqHum726MnR7
+5d/DL/yVkAhjDo5YAgIhAP2pcrGtOIUgSWqnCzr96GnHF3RQ3bABlDBuwXOSJdCb
+-----END CERTIFICATE-----
+";
+
+    fn keystore_finding(file: &std::path::Path) -> Finding {
+        Finding {
+            file: file.display().to_string(),
+            line_number: 0,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: String::new(),
+            match_type: "keystore".to_string(),
+            keyword: "pem".to_string(),
+            context: "PEM file".to_string(),
+            version: None,
+            language: "Binary/File".to_string(),
+            source: "file extension".to_string(),
+            category: "keystore".to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        }
+    }
+
+    #[test]
+    fn test_certificate_properties_and_expiry_risk() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let expired = dir.path().join("expired.pem");
+        let valid = dir.path().join("valid.crt");
+        let broken = dir.path().join("broken.cer");
+        std::fs::write(&expired, EXPIRED_CERT).unwrap();
+        std::fs::write(&valid, VALID_CERT).unwrap();
+        std::fs::write(&broken, b"\x30\x03garbage").unwrap();
+
+        let findings: Vec<Finding> = [&expired, &valid, &broken].iter().map(|p| keystore_finding(p)).collect();
+        let cbom = CbomGenerator::generate_cbom(&findings, None).unwrap();
+
+        let certificate = |name: &str| {
+            cbom.components
+                .iter()
+                .find(|c| c.name == name)
+                .and_then(|c| c.crypto_properties.as_ref())
+                .and_then(|p| p.certificate_properties.clone())
+                .unwrap()
+        };
+        let expired_props = certificate("expired.pem");
+        assert_eq!(expired_props.subject_name.as_deref(), Some("CN=expired.example.com"));
+        assert_eq!(expired_props.issuer_name.as_deref(), Some("CN=Test CA"));
+        assert_eq!(expired_props.signature_algorithm_ref.as_deref(), Some("ecdsa-with-SHA256"));
+        assert_eq!(expired_props.not_valid_after.unwrap().to_rfc3339(), "2021-01-01T00:00:00+00:00");
+
+        assert_eq!(certificate("valid.crt").subject_name.as_deref(), Some("CN=valid.example.com"));
+        // A certificate that fails to parse keeps the bare X.509 entry
+        let broken_props = certificate("broken.cer");
+        assert!(broken_props.subject_name.is_none());
+        assert_eq!(broken_props.certificate_format.as_deref(), Some("X.509"));
+
+        let risks = cbom.declarations.unwrap().risk_assessments.unwrap();
+        assert_eq!(risks.len(), 1);
+        assert_eq!(risks[0].category, "expired-certificate");
+        assert!(risks[0].description.contains("CN=expired.example.com"));
+    }
+
+    #[test]
+    fn test_components_deduplicated_with_occurrences() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let paths: Vec<_> = ["a/server.pem", "b/server.pem", "c/other.pem"].iter().map(|p| dir.path().join(p)).collect();
+        for path in &paths {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        }
+        std::fs::write(&paths[0], VALID_CERT).unwrap();
+        std::fs::write(&paths[1], VALID_CERT).unwrap();
+        std::fs::write(&paths[2], EXPIRED_CERT).unwrap();
+        let mut findings: Vec<Finding> = paths.iter().map(|p| keystore_finding(p)).collect();
+        let library = |file: &str, line_number: usize| Finding {
+            file: file.to_string(),
+            line_number,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: "use ring::digest;".to_string(),
+            match_type: "use".to_string(),
+            keyword: "ring".to_string(),
+            context: String::new(),
+            version: Some("0.17.8".to_string()),
+            language: "Rust".to_string(),
+            source: "use".to_string(),
+            category: "library".to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        };
+        findings.extend([library("src/a.rs", 1), library("src/b.rs", 4), library("src/a.rs", 1)]);
+
+        let cbom = CbomGenerator::generate_cbom(&findings, None).unwrap();
+        let occurrences = |name: &str| -> Vec<(String, Option<usize>)> {
+            let matching: Vec<&CbomComponent> = cbom.components.iter().filter(|c| c.name == name).collect();
+            assert_eq!(matching.len(), 1, "{}", name);
+            matching[0].evidence.as_ref().unwrap().occurrences.iter().map(|o| (o.location.clone(), o.line)).collect()
+        };
+
+        assert_eq!(occurrences("ring"), vec![("src/a.rs".to_string(), Some(1)), ("src/b.rs".to_string(), Some(4))]);
+        // Identical content is one keystore, whatever it's called
+        let copies = occurrences("server.pem");
+        assert_eq!(copies.len(), 2);
+        assert_eq!(copies[1], (paths[1].display().to_string(), None));
+        assert_eq!(occurrences("other.pem").len(), 1);
+
+        let xml = CbomGenerator::export_xml(&cbom).unwrap();
+        assert!(xml.contains("<location>src/a.rs</location>"));
+        assert_eq!(xml.matches("<occurrence>").count(), 5);
+    }
+
+    #[test]
+    fn test_tls_endpoint_components() {
+        let tls_finding = |category: &str, line_number: usize, keyword: &str, line_content: String| Finding {
+            file: "tls://api.example.com:443".to_string(),
+            line_number,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content,
+            match_type: "tls-handshake".to_string(),
+            keyword: keyword.to_string(),
+            context: "api.example.com:443 negotiated TLSv1.3".to_string(),
+            version: Some("TLSv1.3".to_string()),
+            language: "TLS".to_string(),
+            source: "tls-scan".to_string(),
+            category: category.to_string(),
+            severity: Some(Severity::Info),
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        };
+        // The PEM body is the base64 DER the TLS scan records
+        let der_base64: String = EXPIRED_CERT.lines().filter(|line| !line.starts_with("-----")).collect();
+        let findings = vec![
+            tls_finding("tls-endpoint", 0, "TLS13_AES_256_GCM_SHA384", String::new()),
+            tls_finding("tls-certificate", 1, "CN=expired.example.com", der_base64),
+        ];
+        let cbom = CbomGenerator::generate_cbom(&findings, None).unwrap();
+
+        let protocol = cbom.components.iter().find(|c| c.name == "api.example.com:443").unwrap();
+        assert_eq!(protocol.component_type, "cryptographic-asset");
+        let properties = protocol.crypto_properties.as_ref().unwrap().protocol_properties.as_ref().unwrap();
+        assert_eq!(properties.protocol_type, "tls");
+        assert_eq!(properties.version.as_deref(), Some("1.3"));
+        assert_eq!(properties.cipher_suites.as_ref().unwrap()[0].name, "TLS13_AES_256_GCM_SHA384");
+
+        let certificate = cbom.components.iter().find(|c| c.name.starts_with("CN=expired.example.com")).unwrap();
+        let cert_props = certificate.crypto_properties.as_ref().unwrap().certificate_properties.as_ref().unwrap();
+        assert_eq!(cert_props.issuer_name.as_deref(), Some("CN=Test CA"));
+        let risks = cbom.declarations.unwrap().risk_assessments.unwrap();
+        assert!(risks.iter().any(|r| r.category == "expired-certificate"));
+    }
+
+    #[test]
+    fn test_secret_risk_uses_finding_severity() {
+        let secret = |severity: Option<Severity>| Finding {
+            file: "/test/settings.py".to_string(),
+            line_number: 3,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: "TOKEN = \"...\"".to_string(),
+            match_type: "secret".to_string(),
+            keyword: "API Key".to_string(),
+            context: "Generic API key pattern".to_string(),
+            version: None,
+            language: "Python".to_string(),
+            source: "hardcoded".to_string(),
+            category: "secret".to_string(),
+            severity,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        };
+        let secrets_risk = |findings: &[Finding]| {
+            let cbom = CbomGenerator::generate_cbom(findings, None).unwrap();
+            let risks = cbom.declarations.unwrap().risk_assessments.unwrap();
+            risks.into_iter().find(|r| r.category == "hardcoded-secrets").unwrap().level
+        };
+
+        // One critical secret outweighs any number of low ones
+        assert_eq!(secrets_risk(&[secret(Some(Severity::Critical))]), "critical");
+        let low: Vec<Finding> = (0..8).map(|_| secret(Some(Severity::Low))).collect();
+        assert_eq!(secrets_risk(&low), "low");
+        // Unrated secrets fall back to counting
+        assert_eq!(secrets_risk(&[secret(None), secret(None), secret(None)]), "high");
+
+        let mut key = secret(Some(Severity::Critical));
+        key.cwe = Some("CWE-321".to_string());
+        key.remediation = Some("https://example.com/keys".to_string());
+        let cbom = CbomGenerator::generate_cbom(&[key, secret(None)], None).unwrap();
+        let risk = cbom.declarations.unwrap().risk_assessments.unwrap().into_iter().find(|r| r.category == "hardcoded-secrets").unwrap();
+        assert_eq!(risk.description, "Found 2 hardcoded secrets in codebase (CWE-321)");
+        assert!(risk.mitigation.unwrap().ends_with("; see https://example.com/keys"));
+    }
+
+    #[test]
+    fn test_weak_crypto_risk() {
+        let weak = |keyword: &str, severity: Severity| Finding {
+            file: "/test/legacy.py".to_string(),
+            line_number: 7,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: "cipher = DES.new(key, DES.MODE_ECB)".to_string(),
+            match_type: "cipher".to_string(),
+            keyword: keyword.to_string(),
+            context: String::new(),
+            version: None,
+            language: "Python".to_string(),
+            source: "call".to_string(),
+            category: "weak-crypto".to_string(),
+            severity: Some(severity),
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        };
+        let weak_risk = |findings: &[Finding]| {
+            let cbom = CbomGenerator::generate_cbom(findings, None).unwrap();
+            let risks = cbom.declarations.unwrap().risk_assessments.unwrap_or_default();
+            risks.into_iter().find(|r| r.category == "weak-crypto")
+        };
+
+        let risk = weak_risk(&[weak("Hardcoded IV", Severity::Medium), weak("DES", Severity::High)]).unwrap();
+        assert_eq!(risk.level, "high");
+        assert_eq!(risk.description, "2 use(s) of weak or deprecated cryptography: DES, Hardcoded IV");
+        // MD5 used as a checksum is informational and raises no risk
+        assert!(weak_risk(&[weak("MD5", Severity::Info)]).is_none());
+    }
+
+    #[test]
+    fn test_library_algorithm_table() {
+        for (library, ids) in code::LIBRARY_ALGORITHMS {
+            for id in *ids {
+                assert!(ALGORITHMS.iter().any(|spec| spec.id == *id), "{} lists uncatalogued algorithm {}", library, id);
+            }
+        }
+
+        let names = |library: &str| -> Vec<String> {
+            CbomGenerator::infer_algorithm_properties(library).into_iter().map(|a| a.algorithm_name).collect()
+        };
+        assert_eq!(names("libsodium"), vec!["XSalsa20-Poly1305", "ChaCha20-Poly1305", "X25519", "Ed25519", "BLAKE2b", "Argon2id"]);
+        assert_eq!(names("crypto/ecdsa"), vec!["ECDSA"]);
+        // Rule labels fall back on the library their name refers to
+        assert_eq!(names("pyopenssl"), names("openssl"));
+        assert_eq!(names("acme-cipher"), vec!["acme-cipher"]);
+
+        let ring = CbomGenerator::infer_algorithm_properties("ring");
+        let x25519 = ring.iter().find(|a| a.algorithm_name == "X25519").unwrap();
+        assert_eq!((x25519.primitive.as_str(), x25519.quantum_safe, x25519.nist_security_level), ("key-agreement", Some(false), Some(0)));
+        let aead = ring.iter().find(|a| a.algorithm_name == "AES-GCM").unwrap();
+        assert_eq!((aead.key_length, aead.quantum_safe), (Some(256), Some(true)));
+    }
+
+    #[test]
+    fn test_quantum_vulnerable_risk() {
+        let library = |keyword: &str| Finding {
+            file: "/test/crypto.rs".to_string(),
+            line_number: 1,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: format!("use {}::kem;", keyword),
+            match_type: "use".to_string(),
+            keyword: keyword.to_string(),
+            context: "use".to_string(),
+            version: None,
+            language: "Rust".to_string(),
+            source: "use".to_string(),
+            category: "library".to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        };
+        let quantum_risk = |findings: &[Finding]| {
+            let cbom = CbomGenerator::generate_cbom(findings, None).unwrap();
+            let risks = cbom.declarations.unwrap().risk_assessments.unwrap_or_default();
+            risks.into_iter().find(|r| r.category == "quantum-vulnerable")
+        };
+
+        let pqc = CbomGenerator::infer_algorithm_properties("pqcrypto-kyber");
+        assert_eq!(pqc.len(), 1);
+        assert_eq!((pqc[0].algorithm_name.as_str(), pqc[0].quantum_safe), ("ML-KEM", Some(true)));
+        let names: Vec<String> = CbomGenerator::infer_algorithm_properties("oqs").into_iter().map(|a| a.algorithm_name).collect();
+        assert_eq!(names, vec!["ML-KEM", "ML-DSA", "SLH-DSA"]);
+
+        // Only post-quantum libraries: nothing to migrate
+        assert!(quantum_risk(&[library("oqs")]).is_none());
+
+        let risk = quantum_risk(&[library("openssl"), library("oqs")]).unwrap();
+        assert_eq!(risk.level, "high");
+        assert!(risk.description.contains("RSA (openssl)"), "{}", risk.description);
+        assert!(risk.description.contains("post-quantum already in use: ML-DSA (oqs)"), "{}", risk.description);
+        assert!(!risk.description.contains("AES"), "{}", risk.description);
+
+        assert!(is_quantum_vulnerable("ECDH-P256") && is_quantum_vulnerable("ed25519"));
+        assert!(!is_quantum_vulnerable("ML-DSA") && !is_quantum_vulnerable("AES"));
+    }
+
+    #[test]
+    fn test_xml_export() {
+        let mut findings = crate::scanner::ssh_keys::scan_content(
+            std::path::Path::new("authorized_keys"),
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGq1 alice@laptop\n",
+        );
+        findings.push(Finding {
+            file: "/test/crypto.rs".to_string(),
+            line_number: 1,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: String::new(),
+            match_type: "import".to_string(),
+            keyword: "openssl".to_string(),
+            context: "import".to_string(),
+            version: None,
+            language: "Rust".to_string(),
+            source: "import".to_string(),
+            category: "library".to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        });
+        findings.push(Finding {
+            file: "/test/server.key".to_string(),
+            line_number: 0,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: String::new(),
+            match_type: "file".to_string(),
+            keyword: "key".to_string(),
+            context: "file".to_string(),
+            version: None,
+            language: "PEM".to_string(),
+            source: "file".to_string(),
+            category: "keystore".to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        });
+
+        let cbom = CbomGenerator::generate_cbom(&findings, Some("R&D <portal>".to_string())).unwrap();
+        let xml = CbomGenerator::export_xml(&cbom).unwrap();
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains("<bom xmlns=\"http://cyclonedx.org/schema/bom/1.6\""));
+        assert!(xml.contains("<name>R&amp;D &lt;portal&gt;</name>"));
+        // Library algorithms become nested algorithm assets with schema primitives
+        assert!(xml.contains("<primitive>block-cipher</primitive>"));
+        assert!(xml.contains("<parameterSetIdentifier>256</parameterSetIdentifier>"));
+        assert!(xml.contains("<assetType>related-crypto-material</assetType>"));
+        assert!(xml.contains("<type>public-key</type>"));
+        // Absent optional values are omitted, and out-of-schema values ("unknown" state) dropped
+        assert!(!xml.contains("<version/>") && !xml.contains("<version></version>"));
+        assert!(!xml.contains("<state>unknown</state>"));
+        assert!(!xml.contains("<!--"));
+
+        let mut reader = quick_xml::Reader::from_str(&xml);
+        let mut depth = 0i32;
+        loop {
+            match reader.read_event().expect("well-formed XML") {
+                quick_xml::events::Event::Start(_) => depth += 1,
+                quick_xml::events::Event::End(_) => depth -= 1,
+                quick_xml::events::Event::Eof => break,
+                _ => {}
+            }
+        }
+        assert_eq!(depth, 0);
+    }
+
     #[test]
     fn test_json_export() {
         let findings = vec![];
@@ -0,0 +1,247 @@
+//! SPDX 2.3 JSON export of the detected crypto libraries, for consumers that take SPDX
+//! rather than CycloneDX. Libraries are grouped as for the CBOM; each becomes a package
+//! with a purl reference, depended on by a package for the scanned application.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::library_groups;
+use crate::utils::report::Finding;
+
+const DOCUMENT_ID: &str = "SPDXRef-DOCUMENT";
+const APPLICATION_ID: &str = "SPDXRef-Package-application";
+const NOASSERTION: &str = "NOASSERTION";
+
+/// SPDX 2.3 document
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxDocument {
+    pub spdx_version: String,
+    pub data_license: String,
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    pub name: String,
+    pub document_namespace: String,
+    pub creation_info: SpdxCreationInfo,
+    pub packages: Vec<SpdxPackage>,
+    pub relationships: Vec<SpdxRelationship>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxCreationInfo {
+    /// UTC, to the second, as SPDX requires
+    pub created: String,
+    pub creators: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxPackage {
+    pub name: String,
+    #[serde(rename = "SPDXID")]
+    pub spdx_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_info: Option<String>,
+    pub download_location: String,
+    pub files_analyzed: bool,
+    pub license_concluded: String,
+    pub license_declared: String,
+    pub copyright_text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub external_refs: Vec<SpdxExternalRef>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxExternalRef {
+    pub reference_category: String,
+    pub reference_type: String,
+    pub reference_locator: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpdxRelationship {
+    pub spdx_element_id: String,
+    pub relationship_type: String,
+    pub related_spdx_element: String,
+}
+
+/// SPDX document generator
+pub struct SpdxGenerator;
+
+impl SpdxGenerator {
+    /// Generate an SPDX document listing one package per unique library (name and version)
+    pub fn generate(findings: &[Finding], target_component: Option<String>) -> SpdxDocument {
+        Self::generate_at(findings, target_component, Utc::now())
+    }
+
+    fn generate_at(findings: &[Finding], target_component: Option<String>, created: DateTime<Utc>) -> SpdxDocument {
+        let application_name = target_component.unwrap_or_else(|| "scanned-application".to_string());
+        let mut packages = vec![SpdxPackage {
+            name: application_name.clone(),
+            spdx_id: APPLICATION_ID.to_string(),
+            version_info: None,
+            download_location: NOASSERTION.to_string(),
+            files_analyzed: false,
+            license_concluded: NOASSERTION.to_string(),
+            license_declared: NOASSERTION.to_string(),
+            copyright_text: NOASSERTION.to_string(),
+            comment: Some("Application analyzed by CryptoScanner".to_string()),
+            external_refs: Vec::new(),
+        }];
+        let mut relationships = vec![SpdxRelationship {
+            spdx_element_id: DOCUMENT_ID.to_string(),
+            relationship_type: "DESCRIBES".to_string(),
+            related_spdx_element: APPLICATION_ID.to_string(),
+        }];
+
+        for (index, ((name, version), lib_findings)) in library_groups(findings).into_iter().enumerate() {
+            let spdx_id = format!("SPDXRef-Package-{}-{}", index + 1, spdx_id_fragment(&name));
+            let language = lib_findings.first().map(|f| f.language.as_str()).unwrap_or_default();
+            let files: Vec<&str> = lib_findings.iter().map(|f| f.file.as_str()).collect();
+            relationships.push(SpdxRelationship {
+                spdx_element_id: APPLICATION_ID.to_string(),
+                relationship_type: "DEPENDS_ON".to_string(),
+                related_spdx_element: spdx_id.clone(),
+            });
+            packages.push(SpdxPackage {
+                external_refs: vec![SpdxExternalRef {
+                    reference_category: "PACKAGE-MANAGER".to_string(),
+                    reference_type: "purl".to_string(),
+                    reference_locator: purl(&name, version.as_deref(), language),
+                }],
+                name,
+                spdx_id,
+                version_info: version,
+                download_location: NOASSERTION.to_string(),
+                files_analyzed: false,
+                license_concluded: NOASSERTION.to_string(),
+                license_declared: NOASSERTION.to_string(),
+                copyright_text: NOASSERTION.to_string(),
+                comment: Some(format!("Cryptographic library detected in {}", files.join(", "))),
+            });
+        }
+
+        SpdxDocument {
+            spdx_version: "SPDX-2.3".to_string(),
+            data_license: "CC0-1.0".to_string(),
+            spdx_id: DOCUMENT_ID.to_string(),
+            name: format!("{}-crypto-libraries", application_name),
+            document_namespace: format!(
+                "https://link2trust.com/spdxdocs/cryptoscan-{}-{}",
+                spdx_id_fragment(&application_name),
+                Uuid::new_v4()
+            ),
+            creation_info: SpdxCreationInfo {
+                created: created.to_rfc3339_opts(SecondsFormat::Secs, true),
+                creators: vec![
+                    format!("Tool: CryptoScanner-{}", env!("CARGO_PKG_VERSION")),
+                    "Organization: Link2Trust".to_string(),
+                ],
+            },
+            packages,
+            relationships,
+        }
+    }
+
+    /// Export an SPDX document as JSON
+    pub fn export_json(document: &SpdxDocument) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(serde_json::to_string_pretty(document)?)
+    }
+}
+
+/// `name` reduced to the letters, digits, `.` and `-` allowed in an SPDX identifier
+fn spdx_id_fragment(name: &str) -> String {
+    let fragment: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '-' })
+        .collect();
+    fragment.trim_matches('-').to_string()
+}
+
+/// Package URL for a library found in `language` code; `generic` when the ecosystem has no
+/// purl type or the label isn't a package name (e.g. `javax.crypto`)
+fn purl(name: &str, version: Option<&str>, language: &str) -> String {
+    let purl_type = match language {
+        "Rust" => "cargo",
+        "Python" => "pypi",
+        "JavaScript" => "npm",
+        "Go" => "golang",
+        _ => "generic",
+    };
+    // Scoped npm packages and Go module paths keep their `/`; everything else is percent-encoded
+    let encoded: String = name
+        .chars()
+        .map(|c| match c {
+            c if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '~' | '/') => c.to_string(),
+            c => c.to_string().bytes().map(|b| format!("%{:02X}", b)).collect(),
+        })
+        .collect();
+    match version {
+        Some(version) => format!("pkg:{}/{}@{}", purl_type, encoded, version),
+        None => format!("pkg:{}/{}", purl_type, encoded),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library(keyword: &str, version: Option<&str>, language: &str, file: &str) -> Finding {
+        Finding {
+            file: file.to_string(),
+            line_number: 1,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: format!("import {}", keyword),
+            match_type: "import".to_string(),
+            keyword: keyword.to_string(),
+            context: String::new(),
+            version: version.map(str::to_string),
+            language: language.to_string(),
+            source: "import".to_string(),
+            category: "library".to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        }
+    }
+
+    #[test]
+    fn test_spdx_document() {
+        let findings = vec![
+            library("ring", Some("0.17.8"), "Rust", "src/main.rs"),
+            library("ring", Some("0.17.8"), "Rust", "src/tls.rs"),
+            library("@noble/post-quantum", None, "JavaScript", "web/app.js"),
+            library("javax.crypto", None, "Java", "App.java"),
+        ];
+        let created = DateTime::parse_from_rfc3339("2024-05-01T12:00:00.250Z").unwrap().with_timezone(&Utc);
+        let document = SpdxGenerator::generate_at(&findings, Some("my app".to_string()), created);
+
+        assert_eq!(document.spdx_version, "SPDX-2.3");
+        assert_eq!(document.creation_info.created, "2024-05-01T12:00:00Z");
+        assert!(document.document_namespace.starts_with("https://link2trust.com/spdxdocs/cryptoscan-my-app-"));
+        let names: Vec<&str> = document.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["my app", "@noble/post-quantum", "javax.crypto", "ring"]);
+        let locators: Vec<&str> = document.packages[1..]
+            .iter()
+            .map(|p| p.external_refs[0].reference_locator.as_str())
+            .collect();
+        assert_eq!(locators, vec!["pkg:npm/%40noble/post-quantum", "pkg:generic/javax.crypto", "pkg:cargo/ring@0.17.8"]);
+        assert_eq!(document.packages[1].spdx_id, "SPDXRef-Package-1-noble-post-quantum");
+        assert_eq!(document.relationships.len(), 4);
+        assert_eq!(document.relationships[3].related_spdx_element, document.packages[3].spdx_id);
+
+        let json: serde_json::Value = serde_json::from_str(&SpdxGenerator::export_json(&document).unwrap()).unwrap();
+        assert_eq!(json["SPDXID"], "SPDXRef-DOCUMENT");
+        assert_eq!(json["packages"][3]["versionInfo"], "0.17.8");
+        assert_eq!(json["packages"][3]["externalRefs"][0]["referenceType"], "purl");
+    }
+}
@@ -0,0 +1,304 @@
+//! CycloneDX 1.6 XML serialization of a [`CbomDocument`]
+//!
+//! Elements follow the sequence order of the 1.6 XSD. Optional values that are
+//! absent are omitted rather than written as empty elements, and values outside
+//! the schema's enumerations are mapped onto the closest allowed value or dropped.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use quick_xml::events::{BytesDecl, BytesText, Event};
+use quick_xml::Writer;
+
+use super::{
+    AlgorithmProperties, CbomComponent, CbomDocument, CertificateProperties, ComponentEvidence, CryptoAssetType,
+    CryptoProperties, ProtocolProperties, RelatedCryptoMaterial,
+};
+
+const BOM_NAMESPACE: &str = "http://cyclonedx.org/schema/bom/1.6";
+
+/// `relatedCryptoMaterialType` values allowed by the schema
+const MATERIAL_TYPES: &[&str] = &[
+    "private-key", "public-key", "secret-key", "key", "ciphertext", "signature", "digest",
+    "initialization-vector", "nonce", "seed", "salt", "shared-secret", "tag", "additional-data",
+    "password", "credential", "token", "other", "unknown",
+];
+
+/// `cryptographicKeyState` values allowed by the schema
+const MATERIAL_STATES: &[&str] = &["pre-activation", "active", "suspended", "deactivated", "compromised", "destroyed"];
+
+/// `protocolType` values allowed by the schema
+const PROTOCOL_TYPES: &[&str] = &["tls", "ssh", "ipsec", "ike", "sstp", "wpa", "other", "unknown"];
+
+type XmlResult = quick_xml::Result<()>;
+
+/// Map a primitive onto the schema's `cryptographicPrimitive` enumeration
+fn primitive(value: &str) -> &'static str {
+    match value.to_lowercase().as_str() {
+        "drbg" => "drbg",
+        "mac" => "mac",
+        "block-cipher" | "symmetric-encryption" => "block-cipher",
+        "stream-cipher" => "stream-cipher",
+        "signature" | "digital-signature" => "signature",
+        "hash" => "hash",
+        "pke" | "asymmetric-encryption" => "pke",
+        "xof" => "xof",
+        "kdf" => "kdf",
+        "key-agree" | "key-agreement" | "key-exchange" => "key-agree",
+        "kem" => "kem",
+        "ae" | "authenticated-encryption" => "ae",
+        "combiner" => "combiner",
+        "other" => "other",
+        _ => "unknown",
+    }
+}
+
+fn asset_type(asset: &CryptoAssetType) -> &'static str {
+    match asset {
+        CryptoAssetType::Algorithm => "algorithm",
+        CryptoAssetType::Certificate => "certificate",
+        CryptoAssetType::Protocol => "protocol",
+        // Keys and tokens are related crypto material in 1.6
+        CryptoAssetType::RelatedCryptoMaterial | CryptoAssetType::Key | CryptoAssetType::Token => "related-crypto-material",
+    }
+}
+
+fn date_time(value: &DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn text_element(w: &mut Writer<Vec<u8>>, name: &str, value: &str) -> XmlResult {
+    w.create_element(name).write_text_content(BytesText::new(value))?;
+    Ok(())
+}
+
+fn optional_element<T: ToString>(w: &mut Writer<Vec<u8>>, name: &str, value: Option<T>) -> XmlResult {
+    match value {
+        Some(value) => text_element(w, name, &value.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Serialize a CBOM as a CycloneDX 1.6 XML document
+pub fn to_xml(cbom: &CbomDocument) -> quick_xml::Result<String> {
+    let mut w = Writer::new_with_indent(Vec::new(), b' ', 2);
+    w.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    let version = cbom.version.to_string();
+    w.create_element("bom")
+        .with_attribute(("xmlns", BOM_NAMESPACE))
+        .with_attribute(("serialNumber", cbom.serial_number.as_str()))
+        .with_attribute(("version", version.as_str()))
+        .write_inner_content(|w| -> XmlResult {
+            w.create_element("metadata").write_inner_content(|w| -> XmlResult {
+                text_element(w, "timestamp", &date_time(&cbom.metadata.timestamp))?;
+                w.create_element("tools").write_inner_content(|w| -> XmlResult {
+                    for tool in &cbom.metadata.tools {
+                        w.create_element("tool").write_inner_content(|w| -> XmlResult {
+                            text_element(w, "vendor", &tool.vendor)?;
+                            text_element(w, "name", &tool.name)?;
+                            text_element(w, "version", &tool.version)
+                        })?;
+                    }
+                    Ok(())
+                })?;
+                write_component(w, &cbom.metadata.component)
+            })?;
+
+            if !cbom.components.is_empty() {
+                w.create_element("components").write_inner_content(|w| -> XmlResult {
+                    cbom.components.iter().try_for_each(|component| write_component(w, component))
+                })?;
+            }
+
+            // Risk assessments have no 1.6 equivalent, so they travel as namespaced properties
+            let risks = cbom
+                .declarations
+                .as_ref()
+                .and_then(|d| d.risk_assessments.as_ref())
+                .filter(|risks| !risks.is_empty());
+            if let Some(risks) = risks {
+                w.create_element("properties").write_inner_content(|w| -> XmlResult {
+                    for risk in risks {
+                        let name = format!("cryptoscan:risk:{}", risk.category);
+                        w.create_element("property")
+                            .with_attribute(("name", name.as_str()))
+                            .write_text_content(BytesText::new(&format!("{}: {}", risk.level, risk.description)))?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })?;
+
+    Ok(String::from_utf8_lossy(&w.into_inner()).into_owned())
+}
+
+fn write_component(w: &mut Writer<Vec<u8>>, component: &CbomComponent) -> XmlResult {
+    let algorithms = component
+        .crypto_properties
+        .as_ref()
+        .and_then(|p| p.algorithm_properties.as_deref())
+        .unwrap_or_default();
+
+    w.create_element("component")
+        .with_attribute(("type", component.component_type.as_str()))
+        .with_attribute(("bom-ref", component.bom_ref.as_str()))
+        .write_inner_content(|w| -> XmlResult {
+            text_element(w, "name", &component.name)?;
+            optional_element(w, "version", component.version.as_deref())?;
+            optional_element(w, "description", component.description.as_deref())?;
+
+            // cryptoProperties holds a single algorithm, so each one becomes a nested asset
+            if !algorithms.is_empty() {
+                w.create_element("components").write_inner_content(|w| -> XmlResult {
+                    for (i, algorithm) in algorithms.iter().enumerate() {
+                        let bom_ref = format!("{}-alg-{}", component.bom_ref, i + 1);
+                        write_algorithm_component(w, &bom_ref, algorithm)?;
+                    }
+                    Ok(())
+                })?;
+            }
+
+            if let Some(evidence) = &component.evidence {
+                write_evidence(w, evidence)?;
+            }
+
+            // An algorithm asset whose algorithms were nested above has nothing left to say
+            match &component.crypto_properties {
+                Some(properties) if !matches!(properties.asset_type, CryptoAssetType::Algorithm) || algorithms.is_empty() => {
+                    write_crypto_properties(w, properties)
+                }
+                _ => Ok(()),
+            }
+        })?;
+    Ok(())
+}
+
+fn write_evidence(w: &mut Writer<Vec<u8>>, evidence: &ComponentEvidence) -> XmlResult {
+    w.create_element("evidence").write_inner_content(|w| -> XmlResult {
+        w.create_element("occurrences").write_inner_content(|w| -> XmlResult {
+            for occurrence in &evidence.occurrences {
+                w.create_element("occurrence").write_inner_content(|w| -> XmlResult {
+                    text_element(w, "location", &occurrence.location)?;
+                    optional_element(w, "line", occurrence.line)
+                })?;
+            }
+            Ok(())
+        })?;
+        Ok(())
+    })?;
+    Ok(())
+}
+
+fn write_algorithm_component(w: &mut Writer<Vec<u8>>, bom_ref: &str, algorithm: &AlgorithmProperties) -> XmlResult {
+    w.create_element("component")
+        .with_attribute(("type", "cryptographic-asset"))
+        .with_attribute(("bom-ref", bom_ref))
+        .write_inner_content(|w| -> XmlResult {
+            text_element(w, "name", &algorithm.algorithm_name)?;
+            w.create_element("cryptoProperties").write_inner_content(|w| -> XmlResult {
+                text_element(w, "assetType", "algorithm")?;
+                w.create_element("algorithmProperties").write_inner_content(|w| -> XmlResult {
+                    text_element(w, "primitive", primitive(&algorithm.primitive))?;
+                    let parameter_set = algorithm
+                        .parameter_set_identifier
+                        .clone()
+                        .or_else(|| algorithm.key_length.map(|bits| bits.to_string()));
+                    optional_element(w, "parameterSetIdentifier", parameter_set)?;
+                    optional_element(w, "classicalSecurityLevel", algorithm.classical_security_level)?;
+                    optional_element(w, "nistQuantumSecurityLevel", algorithm.nist_security_level.filter(|level| *level <= 6))
+                })?;
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn write_crypto_properties(w: &mut Writer<Vec<u8>>, properties: &CryptoProperties) -> XmlResult {
+    w.create_element("cryptoProperties").write_inner_content(|w| -> XmlResult {
+        text_element(w, "assetType", asset_type(&properties.asset_type))?;
+        if let Some(certificate) = &properties.certificate_properties {
+            write_certificate_properties(w, certificate)?;
+        }
+        // The schema allows one set of related material per component
+        if let Some(material) = properties.related_crypto_material_properties.as_ref().and_then(|m| m.first()) {
+            write_related_material(w, material)?;
+        }
+        if let Some(protocol) = &properties.protocol_properties {
+            write_protocol_properties(w, protocol)?;
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+fn write_certificate_properties(w: &mut Writer<Vec<u8>>, certificate: &CertificateProperties) -> XmlResult {
+    w.create_element("certificateProperties").write_inner_content(|w| -> XmlResult {
+        optional_element(w, "subjectName", certificate.subject_name.as_deref())?;
+        optional_element(w, "issuerName", certificate.issuer_name.as_deref())?;
+        optional_element(w, "notValidBefore", certificate.not_valid_before.as_ref().map(date_time))?;
+        optional_element(w, "notValidAfter", certificate.not_valid_after.as_ref().map(date_time))?;
+        optional_element(w, "signatureAlgorithmRef", certificate.signature_algorithm_ref.as_deref())?;
+        optional_element(w, "subjectPublicKeyRef", certificate.subject_public_key_algorithm_ref.as_deref())?;
+        optional_element(w, "certificateFormat", certificate.certificate_format.as_deref())?;
+        let extensions = certificate.certificate_extension.as_ref().filter(|e| !e.is_empty()).map(|e| e.join(", "));
+        optional_element(w, "certificateExtension", extensions)
+    })?;
+    Ok(())
+}
+
+fn write_related_material(w: &mut Writer<Vec<u8>>, material: &RelatedCryptoMaterial) -> XmlResult {
+    let material_type = MATERIAL_TYPES
+        .iter()
+        .find(|t| **t == material.material_type)
+        .copied()
+        .unwrap_or("unknown");
+    let state = material.state.as_deref().filter(|s| MATERIAL_STATES.contains(s));
+
+    w.create_element("relatedCryptoMaterialProperties").write_inner_content(|w| -> XmlResult {
+        text_element(w, "type", material_type)?;
+        text_element(w, "id", &material.id)?;
+        optional_element(w, "state", state)?;
+        optional_element(w, "algorithmRef", material.algorithm_ref.as_deref())?;
+        optional_element(w, "creationDate", material.creation_time.as_ref().map(date_time))?;
+        optional_element(w, "activationDate", material.activation_time.as_ref().map(date_time))?;
+        optional_element(w, "updateDate", material.update_time.as_ref().map(date_time))?;
+        optional_element(w, "expirationDate", material.expiration_time.as_ref().map(date_time))
+    })?;
+    Ok(())
+}
+
+fn write_protocol_properties(w: &mut Writer<Vec<u8>>, protocol: &ProtocolProperties) -> XmlResult {
+    let protocol_type = protocol.protocol_type.to_lowercase();
+    let protocol_type = PROTOCOL_TYPES.iter().find(|t| **t == protocol_type).copied().unwrap_or("other");
+
+    w.create_element("protocolProperties").write_inner_content(|w| -> XmlResult {
+        text_element(w, "type", protocol_type)?;
+        optional_element(w, "version", protocol.version.as_deref())?;
+        let suites = protocol.cipher_suites.as_deref().unwrap_or_default();
+        if !suites.is_empty() {
+            w.create_element("cipherSuites").write_inner_content(|w| -> XmlResult {
+                for suite in suites {
+                    w.create_element("cipherSuite").write_inner_content(|w| -> XmlResult {
+                        text_element(w, "name", &suite.name)?;
+                        if !suite.algorithms.is_empty() {
+                            w.create_element("algorithms").write_inner_content(|w| -> XmlResult {
+                                suite.algorithms.iter().try_for_each(|a| text_element(w, "algorithm", a))
+                            })?;
+                        }
+                        let identifiers = suite.identifiers.as_deref().unwrap_or_default();
+                        if !identifiers.is_empty() {
+                            w.create_element("identifiers").write_inner_content(|w| -> XmlResult {
+                                identifiers.iter().try_for_each(|id| text_element(w, "identifier", id))
+                            })?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
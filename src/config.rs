@@ -1,4 +1,16 @@
 use clap::Parser;
+use crate::cbom::{parse_component_types, ComponentTypeMap, SbomFormat};
+use crate::scanner::archives::{DEFAULT_MAX_ARCHIVE_ENTRIES, DEFAULT_MAX_ARCHIVE_SIZE};
+use crate::scanner::cache::DEFAULT_CACHE_FILE;
+use crate::scanner::path_filter::parse_glob;
+use crate::scanner::severity_policy::{parse_severity_clamp, SeverityClamp};
+use crate::scanner::rules::{DEFAULT_REGEX_DFA_SIZE_LIMIT, DEFAULT_REGEX_SIZE_LIMIT};
+use globset::Glob;
+use log::LevelFilter;
+use std::net::IpAddr;
+use crate::utils::report::{parse_output_fields, FieldSelection, FindingOrder, OutputFormat, Severity};
+use crate::utils::file_utils::{DEFAULT_MAX_FILE_SIZE, DEFAULT_STREAM_ABOVE};
+use crate::utils::sink::FallbackOutput;
 
 /// Cryptoscan CLI arguments
 #[derive(Parser, Debug)]
@@ -9,6 +21,66 @@ pub struct Config {
     #[arg(short, long, default_value = "./src")]
     pub path: String,
 
+    /// Files to scan instead of walking --path, e.g. the staged files a pre-commit hook passes;
+    /// directories among them are not walked
+    #[arg(value_name = "FILES", conflicts_with_all = ["files_from", "since", "stdin", "watch"])]
+    pub files: Vec<String>,
+
+    /// Scan exactly the newline-separated files listed in FILE (`-` for stdin) instead of walking --path
+    #[arg(long, value_name = "FILE")]
+    pub files_from: Option<String>,
+
+    /// Scan content piped to stdin as a file in --language instead of walking --path;
+    /// findings name the file `<stdin>`
+    #[arg(long, requires = "language", conflicts_with_all = ["files_from", "since", "recent_days", "watch"])]
+    pub stdin: bool,
+
+    /// Language of the --stdin content, by name or extension: rust, python, java, javascript, go, yaml, env, dockerfile, ...
+    #[arg(long, value_name = "LANG")]
+    pub language: Option<String>,
+
+    /// Only scan files under --path changed between this git revision and HEAD, e.g. origin/main
+    #[arg(long, value_name = "REV", conflicts_with_all = ["files_from", "watch"])]
+    pub since: Option<String>,
+
+    /// Only scan files modified within the last N days (files whose mtime can't be read are kept)
+    #[arg(long, value_name = "N")]
+    pub recent_days: Option<u64>,
+
+    /// Treat recoverable problems (e.g. missing files in --files-from) as errors instead of warnings
+    #[arg(long, default_value_t = false)]
+    pub strict: bool,
+
+    /// Only print errors and the final summary: no progress bar, status lines or warnings (overrides RUST_LOG)
+    #[arg(short, long, default_value_t = false, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Log what the scan is doing, down to debug details (overrides RUST_LOG)
+    #[arg(short, long, default_value_t = false)]
+    pub verbose: bool,
+
+    /// Never draw the progress bar (it is already hidden when stderr is not a terminal)
+    #[arg(long, default_value_t = false)]
+    pub no_progress: bool,
+
+    /// Don't honor .gitignore, .git/info/exclude or the global gitignore when walking --path
+    #[arg(long, default_value_t = false)]
+    pub no_ignore: bool,
+
+    /// Follow symlinks when walking --path, with loop detection; links resolving outside --path
+    /// are skipped. By default directory symlinks are not followed, and only file symlinks
+    /// resolving inside --path are scanned
+    #[arg(long, default_value_t = false)]
+    pub follow_symlinks: bool,
+
+    /// Also scan paths matching GLOB (relative to --path), even inside built-in ignored folders like vendor/; repeatable
+    #[arg(long, value_name = "GLOB", value_parser = parse_glob)]
+    pub include: Vec<Glob>,
+
+    /// Skip paths matching GLOB (relative to --path); wins over --include; repeatable
+    #[arg(long, value_name = "GLOB", value_parser = parse_glob)]
+    pub exclude: Vec<Glob>,
+
     /// Enable MIME-type based file filtering
     #[arg(long, default_value_t = false)]
     pub use_mime_filter: bool,
@@ -17,6 +89,208 @@ pub struct Config {
     #[arg(long, default_value_t = false)]
     pub skip_secrets: bool,
 
+    /// Skip scanning for cryptographic libraries (imports and dependency manifests)
+    #[arg(long, default_value_t = false)]
+    pub skip_libraries: bool,
+
+    /// Skip reporting keystore artifacts (.pem, .jks, .p12, ...) by file extension
+    #[arg(long, default_value_t = false)]
+    pub skip_keystores: bool,
+
+    /// Number of threads to scan files with [default: one per CPU]
+    #[arg(short, long, value_name = "N")]
+    pub threads: Option<usize>,
+
+    /// Shannon entropy (bits/char) above which base64-like tokens are reported as secrets; hex tokens use 2/3 of it
+    #[arg(long, value_name = "BITS", default_value_t = 4.5)]
+    pub entropy_threshold: f64,
+
+    /// Directories whose findings get one severity level higher (comma-separated, `a/b` for nested)
+    #[arg(long, value_delimiter = ',', default_value = "src,app,lib,config/production,deploy,prod,production")]
+    pub production_paths: Vec<String>,
+
+    /// Directories whose findings get one severity level lower (comma-separated); wins over --production-paths
+    #[arg(long, value_delimiter = ',', default_value = "examples,example,samples,sample,docs,doc,demo,demos")]
+    pub non_production_paths: Vec<String>,
+
+    /// Keep a category's or scanner's severities within a range, e.g. secret=high.. or weak-crypto=..medium (repeatable)
+    #[arg(long, value_name = "SCOPE=FLOOR..CEILING", value_parser = parse_severity_clamp)]
+    pub severity_clamp: Vec<SeverityClamp>,
+
+    /// Extract strings and TLS key logs from .pcap/.pcapng captures (no protocol dissection)
+    #[arg(long, default_value_t = false)]
+    pub scan_captures: bool,
+
+    /// Also scan ELF/PE/Mach-O executables and libraries for crypto constants and embedded secrets
+    #[arg(long, default_value_t = false)]
+    pub scan_binaries: bool,
+
+    /// Also scan git commit messages and annotated tag messages of the repository containing --path for secrets
+    #[arg(long, default_value_t = false, conflicts_with = "watch")]
+    pub scan_commit_messages: bool,
+
+    /// Read at most this many commits (newest first, across all refs) for --scan-commit-messages
+    #[arg(long, value_name = "N")]
+    pub history_limit: Option<usize>,
+
+    /// Flag private keys and credential files that other users can read (Unix; for host/container audits)
+    #[arg(long, default_value_t = false)]
+    pub check_file_perms: bool,
+
+    /// Flag crypto dependencies pinned in lockfiles to versions with known vulnerabilities
+    #[arg(long, default_value_t = false)]
+    pub check_vulnerable_deps: bool,
+
+    /// Extra advisories (JSON list) to check dependencies against, on top of the built-in ones
+    #[arg(long, value_name = "FILE", requires = "check_vulnerable_deps")]
+    pub vuln_db: Option<String>,
+
+    /// Fetch extra advisories (JSON list) from this URL with curl; no network access happens without it
+    #[arg(long, value_name = "URL", requires = "check_vulnerable_deps")]
+    pub vuln_db_url: Option<String>,
+
+    /// Inventory a live TLS endpoint (host:port, repeatable) instead of scanning --path: negotiated
+    /// protocol, cipher suite and certificate chain are reported and included in the CBOM
+    #[arg(long, value_name = "HOST:PORT", conflicts_with = "watch")]
+    pub tls_scan: Vec<String>,
+
+    /// Inventory the TLS endpoints listed one per line in FILE (blank lines and # comments ignored)
+    #[arg(long, value_name = "FILE", conflicts_with = "watch")]
+    pub tls_scan_file: Option<String>,
+
+    /// Seconds to wait for each TLS endpoint to connect and for each handshake read
+    #[arg(long, value_name = "SECS", default_value_t = 5)]
+    pub tls_timeout: u64,
+
+    /// Keep running after the first scan, re-scanning changed files and rewriting the report
+    #[arg(long, default_value_t = false, conflicts_with = "files_from")]
+    pub watch: bool,
+
+    /// Quiet period in milliseconds to wait for before handling a burst of changes in --watch mode
+    #[arg(long, default_value_t = 500)]
+    pub watch_debounce_ms: u64,
+
+    /// Stream findings as JSON lines to the Unix domain socket listening at PATH while scanning
+    #[arg(long, value_name = "PATH", conflicts_with = "watch")]
+    pub emit_socket: Option<String>,
+
+    /// Only report findings missing from this earlier findings.json; exit with code 2 if there are any
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<String>,
+
+    /// Exit with code 2 if any reported finding is at or above this severity (info, low, medium,
+    /// high, critical); findings without a severity never count. With --baseline, only new ones count
+    #[arg(long, value_name = "SEVERITY")]
+    pub fail_on: Option<Severity>,
+
+    /// Also write all current findings (before --baseline filtering) as a baseline file
+    #[arg(long, value_name = "FILE", conflicts_with = "watch")]
+    pub write_baseline: Option<String>,
+
+    /// Output format for the findings report (json, gitlab-sast, sarif, csv, jsonl, html)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    pub format: OutputFormat,
+
+    /// Show secret values in HTML reports (--format html and the report bundle) instead of masking them
+    #[arg(long)]
+    pub no_redact: bool,
+
+    /// Keep only these comma-separated fields in JSON findings, e.g. file,line_number,rule_id,severity
+    #[arg(long, value_name = "FIELDS", value_parser = parse_output_fields)]
+    pub output_fields: Option<FieldSelection>,
+
+    /// Report destination: a file path, `-` for stdout, or an http:// URL to POST to (https:// is not supported)
+    /// [default: web/data/findings.json, or gl-sast-report.json for gitlab-sast]
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Where to send the report if the output path cannot be written (stdout, temp)
+    #[arg(long, value_enum)]
+    pub fallback_output: Option<FallbackOutput>,
+
+    /// Sort findings and leave the report file untouched when the new report is byte-identical to it
+    #[arg(long)]
+    pub output_findings_only_if_changed: bool,
+
+    /// Order of findings in the report: file (by file, line and rule) or none (as found).
+    /// A streamed jsonl report is always written as found
+    #[arg(long, value_enum, default_value_t = FindingOrder::File)]
+    pub sort: FindingOrder,
+
+    /// Also write a report bundle (findings, CBOM, HTML report, metadata) into this directory
+    #[arg(long, value_name = "DIR")]
+    pub report_bundle: Option<String>,
+
+    /// Write a JSON manifest of every scanned file (relative path, size, SHA-256, language)
+    #[arg(long, value_name = "FILE")]
+    pub manifest: Option<String>,
+
+    /// Write a JSON list of files whose content wasn't scanned, with the reason (unreadable, binary, too large, MIME-filtered)
+    #[arg(long, value_name = "FILE")]
+    pub report_skipped: Option<String>,
+
+    /// Skip (and report) files larger than this many bytes; 0 disables the limit
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_MAX_FILE_SIZE)]
+    pub max_file_size: u64,
+
+    /// --max-file-size in megabytes
+    #[arg(long, value_name = "MB", conflicts_with = "max_file_size")]
+    pub max_file_size_mb: Option<u64>,
+
+    /// Reuse the findings of files whose content is unchanged since the last --cache scan, read from
+    /// and written back to PATH (default .cryptoscan-cache.json)
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = DEFAULT_CACHE_FILE, conflicts_with = "watch")]
+    pub cache: Option<String>,
+
+    /// Scan every file and leave the cache alone, overriding an earlier --cache
+    #[arg(long, overrides_with = "cache")]
+    pub no_cache: bool,
+
+    /// Scan text files larger than this many bytes a line at a time, keeping only the longest line in memory; 0 reads every file whole
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_STREAM_ABOVE)]
+    pub stream_above: u64,
+
+    /// Stop extracting a .jar/.war/.zip/.tar.gz after this many uncompressed bytes (nested archives included)
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_MAX_ARCHIVE_SIZE)]
+    pub archive_max_size: u64,
+
+    /// Stop reading an archive after this many entries (nested archives included)
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_ARCHIVE_ENTRIES)]
+    pub archive_max_entries: usize,
+
+    /// Extra crypto library rules (TOML, or YAML by extension) merged with, and overriding, the built-in ones
+    #[arg(long, value_name = "FILE")]
+    pub rules: Option<String>,
+
+    /// Extra secret patterns (TOML, or YAML by extension) checked alongside the built-in ones
+    #[arg(long, value_name = "FILE")]
+    pub secret_rules: Option<String>,
+
+    /// Inline comment marker that silences secret findings on its line and the line below (empty disables it)
+    #[arg(long, value_name = "MARKER", default_value = "cryptoscan:ignore")]
+    pub ignore_marker: String,
+
+    /// Also report secrets on comment lines, which are skipped by default (for audits of keys left in comments)
+    #[arg(long, default_value_t = false)]
+    pub scan_comments: bool,
+
+    /// Keep findings that several scanners or patterns report identically on the same line (for debugging rules)
+    #[arg(long, default_value_t = false)]
+    pub allow_duplicates: bool,
+
+    /// Max compiled size in bytes of a pattern loaded from a rule file; larger patterns are rejected at load time
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_REGEX_SIZE_LIMIT, hide_short_help = true)]
+    pub regex_size_limit: usize,
+
+    /// Max lazy-DFA cache in bytes for a pattern loaded from a rule file
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_REGEX_DFA_SIZE_LIMIT, hide_short_help = true)]
+    pub regex_dfa_size_limit: usize,
+
+    /// Review a JSON findings report in a terminal UI instead of scanning; rejected findings are
+    /// added to .cryptoscanignore under --path (needs --features review)
+    #[arg(long, value_name = "FINDINGS", num_args = 0..=1, default_missing_value = "web/data/findings.json", conflicts_with_all = ["watch", "serve"])]
+    pub review: Option<String>,
+
     /// Start web server mode instead of CLI scan
     #[arg(long, default_value_t = false)]
     pub serve: bool,
@@ -25,6 +299,20 @@ pub struct Config {
     #[arg(long, default_value_t = 8080)]
     pub port: u16,
 
+    /// Address the web server listens on (only used with --serve); 0.0.0.0 or :: exposes the
+    /// dashboard and its scan API to the network
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1")]
+    pub bind: IpAddr,
+
+    /// Require `Authorization: Bearer <TOKEN>` on every /api request (only used with --serve)
+    #[arg(long, value_name = "TOKEN")]
+    pub api_token: Option<String>,
+
+    /// Refuse server scans of local paths outside this directory, after resolving `..` and
+    /// symlinks; relative paths are taken from it (only used with --serve)
+    #[arg(long, value_name = "DIR")]
+    pub scan_root: Option<String>,
+
     /// Path to web assets directory (only used with --serve)
     #[arg(long, default_value = "./web")]
     pub web_dir: String,
@@ -37,11 +325,137 @@ pub struct Config {
     #[arg(long, default_value = "json")]
     pub cbom_format: String,
 
+    /// Bill of materials standard for --cbom: cyclonedx, or spdx (SPDX 2.3 JSON)
+    #[arg(long, value_enum, default_value_t = SbomFormat::Cyclonedx)]
+    pub sbom_format: SbomFormat,
+
     /// CBOM output file path
     #[arg(long, default_value = "./cbom.json")]
     pub cbom_output: String,
 
+    /// Override CBOM component types as role=type pairs (roles: application, library, keystore), e.g. keystore=data
+    #[arg(long, value_name = "MAP", value_parser = parse_component_types)]
+    pub cbom_component_types: Option<ComponentTypeMap>,
+
     /// Application name for CBOM metadata
     #[arg(long)]
     pub app_name: Option<String>,
 }
+
+impl Config {
+    /// Whether `--tls-scan` or `--tls-scan-file` selected the TLS inventory mode
+    pub fn is_tls_scan(&self) -> bool {
+        !self.tls_scan.is_empty() || self.tls_scan_file.is_some()
+    }
+
+    /// Check what clap can't: that the scan path exists and thread count and size limits are sane
+    pub fn validate(&self) -> Result<(), String> {
+        let scans_path =
+            !self.serve && !self.is_tls_scan() && !self.stdin && self.files_from.is_none() && self.files.is_empty();
+        if scans_path {
+            if let Err(e) = std::fs::metadata(&self.path) {
+                return Err(format!("Cannot access scan path {}: {}", self.path, e));
+            }
+        }
+
+        if let Some(threads) = self.threads {
+            if threads == 0 {
+                return Err("Thread count must be greater than 0".to_string());
+            }
+            if threads > 1000 {
+                return Err("Thread count seems unreasonably high (max: 1000)".to_string());
+            }
+        }
+
+        // The report is uploaded after the scan, so a URL it can't be sent to is caught up front
+        if let Some(url) = self.output.as_deref().filter(|o| o.starts_with("http://") || o.starts_with("https://")) {
+            crate::utils::sink::parse_http_url(url).map_err(|e| e.to_string())?;
+        }
+
+        if self.recent_days == Some(0) {
+            return Err("--recent-days must be at least 1".to_string());
+        }
+
+        if let Some(mb) = self.max_file_size_mb {
+            if mb == 0 {
+                return Err("Maximum file size must be greater than 0 (--max-file-size 0 disables the limit)".to_string());
+            }
+            if mb > 1000 {
+                return Err("Maximum file size seems unreasonably high (max: 1000MB)".to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The file size limit in bytes, from `--max-file-size-mb` when given; 0 means no limit
+    pub fn max_file_size_bytes(&self) -> u64 {
+        self.max_file_size_mb.map_or(self.max_file_size, |mb| mb * 1024 * 1024)
+    }
+
+    /// The log level `--quiet` or `--verbose` asks for; `None` leaves it to `RUST_LOG`
+    pub fn log_level(&self) -> Option<LevelFilter> {
+        if self.quiet {
+            Some(LevelFilter::Error)
+        } else if self.verbose {
+            Some(LevelFilter::Debug)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Config {
+    /// Configuration equivalent to running `cryptoscan` with no arguments
+    fn default() -> Self {
+        Config::parse_from(["cryptoscan"])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_validation() {
+        let mut config = Config { path: ".".to_string(), threads: Some(4), ..Default::default() };
+        assert!(config.validate().is_ok());
+
+        config.threads = Some(0);
+        assert!(config.validate().is_err());
+        config.threads = Some(4);
+
+        config.max_file_size_mb = Some(0);
+        assert!(config.validate().is_err());
+        config.max_file_size_mb = None;
+
+        config.path = "does/not/exist".to_string();
+        assert!(config.validate().is_err());
+        // The path isn't scanned when the file list comes from elsewhere
+        config.files_from = Some("-".to_string());
+        assert!(config.validate().is_ok());
+
+        config.output = Some("http://[::1]:8080/ingest".to_string());
+        assert!(config.validate().is_ok());
+        config.output = Some("https://collector/ingest".to_string());
+        assert!(config.validate().is_err());
+        config.output = Some("http://collector:port/ingest".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_file_size_bytes_conversion() {
+        let config = Config::parse_from(["cryptoscan", "--max-file-size-mb", "5"]);
+        assert_eq!(config.max_file_size_bytes(), 5 * 1024 * 1024);
+        assert_eq!(Config::default().max_file_size_bytes(), 10 * 1024 * 1024);
+        assert!(Config::try_parse_from(["cryptoscan", "--max-file-size", "0", "--max-file-size-mb", "5"]).is_err());
+    }
+
+    #[test]
+    fn test_bind_address() {
+        assert!(Config::default().bind.is_loopback());
+        let config = Config::parse_from(["cryptoscan", "--serve", "--bind", "::"]);
+        assert!(config.bind.is_unspecified());
+        assert!(Config::try_parse_from(["cryptoscan", "--serve", "--bind", "localhost"]).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::report::Finding;
 use std::fmt;
 use std::io;
 
@@ -16,6 +17,15 @@ pub enum ScanError {
     FileProcessing(String),
     /// Scanner-specific errors
     Scanner(String),
+    /// The scan finished but its report could not be written
+    ReportWrite {
+        destination: String,
+        cause: Box<ScanError>,
+    },
+    /// The scan and its report succeeded, but found findings missing from the `--baseline`
+    NewFindings { findings: Vec<Finding> },
+    /// The scan was cancelled before finishing; `partial` holds the findings collected so far
+    Cancelled { partial: Vec<Finding> },
 }
 
 impl fmt::Display for ScanError {
@@ -27,6 +37,11 @@ impl fmt::Display for ScanError {
             ScanError::Json(err) => write!(f, "JSON error: {}", err),
             ScanError::FileProcessing(msg) => write!(f, "File processing error: {}", msg),
             ScanError::Scanner(msg) => write!(f, "Scanner error: {}", msg),
+            ScanError::ReportWrite { destination, cause } => {
+                write!(f, "Scan completed but the report could not be written to {}: {}", destination, cause)
+            }
+            ScanError::NewFindings { findings } => write!(f, "{} finding(s) not in the baseline", findings.len()),
+            ScanError::Cancelled { partial } => write!(f, "Scan cancelled after {} finding(s)", partial.len()),
         }
     }
 }
@@ -64,6 +79,14 @@ pub fn file_error(msg: &str) -> ScanError {
     ScanError::FileProcessing(msg.to_string())
 }
 
+/// Wrap a sink failure so callers can tell it apart from a failed scan
+pub fn report_write_error(destination: &str, cause: ScanError) -> ScanError {
+    ScanError::ReportWrite {
+        destination: destination.to_string(),
+        cause: Box::new(cause),
+    }
+}
+
 /// Utility function to create scanner errors
 pub fn scanner_error(msg: &str) -> ScanError {
     ScanError::Scanner(msg.to_string())
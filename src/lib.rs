@@ -1,7 +1,11 @@
 pub mod config;
+pub mod error;
 pub mod scanner;
 pub mod utils;
 pub mod cbom;
 
 #[cfg(feature = "server")]
 pub mod server;
+
+#[cfg(feature = "review")]
+pub mod review;
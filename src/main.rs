@@ -1,14 +1,21 @@
 use cryptoscan::config::Config;
+use cryptoscan::error::ScanError;
 use cryptoscan::scanner::scan_directory;
-use cryptoscan::cbom::{CbomGenerator, CbomDocument};
+use cryptoscan::scanner::stdin::scan_stdin;
+use cryptoscan::scanner::tls::scan_tls_endpoints;
+use cryptoscan::scanner::watch::watch_directory;
+use cryptoscan::cbom::spdx::SpdxGenerator;
+use cryptoscan::cbom::{CbomGenerator, CbomDocument, SbomFormat};
+use cryptoscan::utils::report::{count_at_or_above, Finding};
 use clap::Parser;
-use log::{info, error};
+use log::{info, error, warn};
 use std::process;
-use std::path::PathBuf;
 use std::fs;
 
 #[cfg(feature = "server")]
 use cryptoscan::server::start_server;
+#[cfg(feature = "server")]
+use std::path::PathBuf;
 
 #[cfg(feature = "server")]
 #[tokio::main]
@@ -23,14 +30,18 @@ fn main() {
 
 #[cfg(feature = "server")]
 async fn run_main() {
-    // Initialize logger
-    env_logger::init();
-    
     let config = Config::parse();
+    init_logging(&config);
+    check_config(&config);
+
+    if let Some(findings) = &config.review {
+        run_review(&config, findings);
+        return;
+    }
     
     if config.serve {
         // Server mode
-        info!("Starting CryptoScanner web server on port {}", config.port);
+        info!("Starting CryptoScanner web server on {}:{}", config.bind, config.port);
         info!("Web directory: {}", config.web_dir);
         
         let web_dir = PathBuf::from(&config.web_dir);
@@ -42,7 +53,8 @@ async fn run_main() {
         
         #[cfg(feature = "server")]
         {
-            if let Err(e) = start_server(config.port, web_dir).await {
+            let scan_root = config.scan_root.as_ref().map(PathBuf::from);
+            if let Err(e) = start_server(config.bind, config.port, web_dir, config.api_token.clone(), scan_root).await {
                 error!("Server failed to start: {}", e);
                 process::exit(1);
             }
@@ -58,19 +70,54 @@ async fn run_main() {
         info!("Starting CryptoScanner with path: {}", config.path);
         info!("MIME filtering: {}", config.use_mime_filter);
         info!("Skip secrets: {}", config.skip_secrets);
+        if config.check_file_perms && !cryptoscan::scanner::file_perms::is_supported() {
+            warn!("--check-file-perms is only supported on Unix; skipping file permission checks");
+        }
         
-        match scan_directory(&config) {
-            Ok(()) => {
+        // --watch runs until interrupted and has no final set of findings for a CBOM
+        let result = if config.watch {
+            watch_directory(&config).map(|()| Vec::new())
+        } else if config.is_tls_scan() {
+            scan_tls_endpoints(&config)
+        } else if config.stdin {
+            scan_stdin(&config)
+        } else {
+            scan_directory(&config)
+        };
+
+        match result {
+            Ok(findings) => {
                 info!("Scan completed successfully");
                 
                 // Generate CBOM if requested
-                if config.cbom {
-                    if let Err(e) = generate_cbom_report(&config) {
+                if config.cbom && !config.watch {
+                    if let Err(e) = generate_cbom_report(&config, &findings) {
                         error!("Failed to generate CBOM: {}", e);
                         process::exit(1);
                     }
                 }
+                exit_if_failing(&config, &findings);
             },
+            Err(ScanError::NewFindings { findings }) => {
+                // Everything was written; the exit code tells CI that new findings appeared
+                if config.cbom {
+                    if let Err(e) = generate_cbom_report(&config, &findings) {
+                        error!("Failed to generate CBOM: {}", e);
+                        process::exit(1);
+                    }
+                }
+                info!("{} new finding(s) not in the baseline", findings.len());
+                // With --fail-on, only new findings of that severity fail the run
+                if config.fail_on.is_none() {
+                    process::exit(2);
+                }
+                exit_if_failing(&config, &findings);
+            }
+            Err(e @ ScanError::ReportWrite { .. }) => {
+                // The scan itself succeeded; only the report destination was unusable
+                error!("{}", e);
+                process::exit(3);
+            }
             Err(e) => {
                 error!("Scan failed: {}", e);
                 process::exit(1);
@@ -81,10 +128,14 @@ async fn run_main() {
 
 #[cfg(not(feature = "server"))]
 fn run_main_sync() {
-    // Initialize logger
-    env_logger::init();
-    
     let config = Config::parse();
+    init_logging(&config);
+    check_config(&config);
+
+    if let Some(findings) = &config.review {
+        run_review(&config, findings);
+        return;
+    }
     
     if config.serve {
         error!("Server feature not enabled. Please compile with --features server");
@@ -94,19 +145,54 @@ fn run_main_sync() {
         info!("Starting CryptoScanner with path: {}", config.path);
         info!("MIME filtering: {}", config.use_mime_filter);
         info!("Skip secrets: {}", config.skip_secrets);
+        if config.check_file_perms && !cryptoscan::scanner::file_perms::is_supported() {
+            warn!("--check-file-perms is only supported on Unix; skipping file permission checks");
+        }
         
-        match scan_directory(&config) {
-            Ok(()) => {
+        // --watch runs until interrupted and has no final set of findings for a CBOM
+        let result = if config.watch {
+            watch_directory(&config).map(|()| Vec::new())
+        } else if config.is_tls_scan() {
+            scan_tls_endpoints(&config)
+        } else if config.stdin {
+            scan_stdin(&config)
+        } else {
+            scan_directory(&config)
+        };
+
+        match result {
+            Ok(findings) => {
                 info!("Scan completed successfully");
                 
                 // Generate CBOM if requested
-                if config.cbom {
-                    if let Err(e) = generate_cbom_report(&config) {
+                if config.cbom && !config.watch {
+                    if let Err(e) = generate_cbom_report(&config, &findings) {
                         error!("Failed to generate CBOM: {}", e);
                         process::exit(1);
                     }
                 }
+                exit_if_failing(&config, &findings);
             },
+            Err(ScanError::NewFindings { findings }) => {
+                // Everything was written; the exit code tells CI that new findings appeared
+                if config.cbom {
+                    if let Err(e) = generate_cbom_report(&config, &findings) {
+                        error!("Failed to generate CBOM: {}", e);
+                        process::exit(1);
+                    }
+                }
+                info!("{} new finding(s) not in the baseline", findings.len());
+                // With --fail-on, only new findings of that severity fail the run
+                if config.fail_on.is_none() {
+                    process::exit(2);
+                }
+                exit_if_failing(&config, &findings);
+            }
+            Err(e @ ScanError::ReportWrite { .. }) => {
+                // The scan itself succeeded; only the report destination was unusable
+                error!("{}", e);
+                process::exit(3);
+            }
             Err(e) => {
                 error!("Scan failed: {}", e);
                 process::exit(1);
@@ -115,23 +201,65 @@ fn run_main_sync() {
     }
 }
 
+/// Set up logging: `--quiet`/`--verbose` pick the level, otherwise `RUST_LOG` does as before
+fn init_logging(config: &Config) {
+    let mut builder = match config.log_level() {
+        Some(level) => {
+            let mut builder = env_logger::Builder::new();
+            builder.filter_level(level);
+            builder
+        }
+        None => env_logger::Builder::from_default_env(),
+    };
+    builder.init();
+}
+
+/// Exit on invalid options before anything runs
+fn check_config(config: &Config) {
+    if let Err(e) = config.validate() {
+        error!("{}", e);
+        process::exit(1);
+    }
+}
+
+/// Exit with code 2 when a reported finding reaches the `--fail-on` severity
+fn exit_if_failing(config: &Config, findings: &[Finding]) {
+    let Some(level) = config.fail_on else {
+        return;
+    };
+    let failing = count_at_or_above(findings, level);
+    if failing > 0 {
+        // Already counted in the status line the scan printed
+        info!("{} finding(s) at or above {} severity", failing, level);
+        process::exit(2);
+    }
+}
+
+/// Open the `--review` UI on a findings report
+#[cfg(feature = "review")]
+fn run_review(config: &Config, findings: &str) {
+    if let Err(e) = cryptoscan::review::review_findings(config, std::path::Path::new(findings)) {
+        error!("Review failed: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "review"))]
+fn run_review(_config: &Config, _findings: &str) {
+    error!("Review feature not enabled. Please compile with --features review");
+    process::exit(1);
+}
+
 /// Generate and export CBOM report
-fn generate_cbom_report(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
-    info!("Generating CycloneDX CBOM report...");
-    
-    // Load scan findings from the generated JSON file
-    let findings_path = "web/data/findings.json";
-    if !std::path::Path::new(findings_path).exists() {
-        return Err("Scan findings file not found. Please run a scan first.".into());
+fn generate_cbom_report(config: &Config, findings: &[Finding]) -> Result<(), Box<dyn std::error::Error>> {
+    if config.sbom_format == SbomFormat::Spdx {
+        return generate_spdx_report(config, findings);
     }
-    
-    let findings_json = fs::read_to_string(findings_path)?;
-    let findings: Vec<cryptoscan::utils::report::Finding> = serde_json::from_str(&findings_json)?;
-    
-    info!("Loaded {} findings for CBOM generation", findings.len());
+    info!("Generating CycloneDX CBOM report from {} findings...", findings.len());
     
     // Generate CBOM document
-    let cbom = CbomGenerator::generate_cbom(&findings, config.app_name.clone())?;
+    let component_types = config.cbom_component_types.clone().unwrap_or_default();
+    let cbom = CbomGenerator::generate_cbom_with_types(findings, config.app_name.clone(), &component_types)?;
     
     // Export in requested format
     let output_content = match config.cbom_format.to_lowercase().as_str() {
@@ -154,11 +282,37 @@ fn generate_cbom_report(config: &Config) -> Result<(), Box<dyn std::error::Error
     }
     
     // Print summary
-    print_cbom_summary(&cbom);
+    if !config.quiet {
+        print_cbom_summary(&cbom);
+    }
     
     Ok(())
 }
 
+/// Generate and export the SPDX 2.3 document selected by `--sbom-format spdx`
+fn generate_spdx_report(config: &Config, findings: &[Finding]) -> Result<(), Box<dyn std::error::Error>> {
+    if !config.cbom_format.eq_ignore_ascii_case("json") {
+        error!("SPDX documents are only written as JSON, not {}", config.cbom_format);
+        return Err(format!("Unsupported format for SPDX: {}", config.cbom_format).into());
+    }
+    info!("Generating SPDX 2.3 document from {} findings...", findings.len());
+
+    let document = SpdxGenerator::generate(findings, config.app_name.clone());
+    fs::write(&config.cbom_output, SpdxGenerator::export_json(&document)?)?;
+    info!("SPDX document generated successfully: {}", config.cbom_output);
+
+    if !config.quiet {
+        println!("\n📋 SPDX Generation Summary");
+        println!("├─ Spec Version: {}", document.spdx_version);
+        println!("├─ Namespace: {}", document.document_namespace);
+        println!("├─ Libraries Found: {}", document.packages.len() - 1);
+        println!("└─ Generated: {}", document.creation_info.created);
+        println!();
+    }
+
+    Ok(())
+}
+
 /// Print CBOM generation summary
 fn print_cbom_summary(cbom: &CbomDocument) {
     println!("\n📋 CBOM Generation Summary");
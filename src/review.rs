@@ -0,0 +1,211 @@
+//! `--review`: a terminal UI for triaging a findings report. Each finding is shown with its
+//! file, line and context and can be accepted, rejected or skipped. On quitting, the
+//! decisions are exported with [`annotations`](crate::utils::annotations) and rejected
+//! findings are appended to `.cryptoscanignore` so later scans suppress them.
+
+use crate::config::Config;
+use crate::error::{config_error, ScanResult};
+use crate::utils::annotations::{
+    annotations_path, append_ignore_entries, ignore_entry, load_annotations, write_annotations, Annotation, Decision,
+};
+use crate::utils::report::Finding;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const HELP: &str = "a accept  r reject  s skip  u undo  ↑/↓ move  q save and quit  Ctrl-C quit without saving";
+
+/// Findings being reviewed and the decision taken on each so far
+struct Review<'a> {
+    findings: &'a [Finding],
+    decisions: Vec<Option<Decision>>,
+    state: ListState,
+}
+
+impl<'a> Review<'a> {
+    fn new(findings: &'a [Finding], earlier: &[Annotation]) -> Self {
+        let decisions: Vec<Option<Decision>> = findings
+            .iter()
+            .map(|finding| earlier.iter().find(|a| a.is_for(finding)).map(|a| a.decision))
+            .collect();
+        // Resume at the first finding without a decision
+        let first = decisions.iter().position(Option::is_none).unwrap_or(0);
+        let mut state = ListState::default();
+        state.select(Some(first));
+        Review { findings, decisions, state }
+    }
+
+    fn selected(&self) -> usize {
+        self.state.selected().unwrap_or(0)
+    }
+
+    fn select(&mut self, index: usize) {
+        self.state.select(Some(index.min(self.findings.len() - 1)));
+    }
+
+    fn decide(&mut self, decision: Option<Decision>) {
+        let index = self.selected();
+        self.decisions[index] = decision;
+        if decision.is_some() {
+            self.select(index + 1);
+        }
+    }
+
+    fn annotations(&self) -> Vec<Annotation> {
+        self.findings
+            .iter()
+            .zip(&self.decisions)
+            .filter_map(|(finding, decision)| decision.map(|d| Annotation::new(finding, d)))
+            .collect()
+    }
+}
+
+/// Review the findings in `findings_path`, a JSON report, with `config.path` as the scan root
+pub fn review_findings(config: &Config, findings_path: &Path) -> ScanResult<()> {
+    let json = fs::read_to_string(findings_path)
+        .map_err(|e| config_error(&format!("Cannot read findings {}: {}", findings_path.display(), e)))?;
+    let findings: Vec<Finding> = serde_json::from_str(&json).map_err(|e| {
+        config_error(&format!(
+            "{} is not a full JSON findings report (--format json without --output-fields): {}",
+            findings_path.display(),
+            e
+        ))
+    })?;
+    if findings.is_empty() {
+        if !config.quiet {
+            eprintln!("✅ No findings to review in {}", findings_path.display());
+        }
+        return Ok(());
+    }
+
+    let annotations_file = annotations_path(findings_path);
+    let mut review = Review::new(&findings, &load_annotations(&annotations_file)?);
+    if !run_terminal(&mut review)? {
+        return Ok(());
+    }
+
+    let annotations = review.annotations();
+    write_annotations(&annotations_file, &annotations)?;
+    let root = Path::new(&config.path);
+    let rejected: Vec<String> = findings
+        .iter()
+        .zip(&review.decisions)
+        .filter(|(_, decision)| **decision == Some(Decision::Reject))
+        .map(|(finding, _)| ignore_entry(root, finding))
+        .collect();
+    let added = append_ignore_entries(root, &rejected)?;
+
+    if !config.quiet {
+        eprintln!(
+            "📝 Reviewed {} of {} finding(s); decisions saved to {}",
+            annotations.len(),
+            findings.len(),
+            annotations_file.display()
+        );
+        if added > 0 {
+            eprintln!("🙈 Added {} entry(ies) to {}", added, root.join(crate::scanner::suppressions::IGNORE_FILE).display());
+        }
+    }
+    Ok(())
+}
+
+/// Restores the terminal when the review ends, including on error
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+    }
+}
+
+/// Run the UI until the reviewer quits; `false` if they quit without saving
+fn run_terminal(review: &mut Review) -> io::Result<bool> {
+    enable_raw_mode()?;
+    let _guard = TerminalGuard;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    loop {
+        terminal.draw(|frame| draw(frame, review))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(false),
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(true),
+            KeyCode::Char('a') => review.decide(Some(Decision::Accept)),
+            KeyCode::Char('r') => review.decide(Some(Decision::Reject)),
+            KeyCode::Char('s') | KeyCode::Char(' ') => review.decide(Some(Decision::Skip)),
+            KeyCode::Char('u') => review.decide(None),
+            KeyCode::Down | KeyCode::Char('j') => review.select(review.selected() + 1),
+            KeyCode::Up | KeyCode::Char('k') => review.select(review.selected().saturating_sub(1)),
+            KeyCode::PageDown => review.select(review.selected() + 10),
+            KeyCode::PageUp => review.select(review.selected().saturating_sub(10)),
+            _ => {}
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, review: &mut Review) {
+    let areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(45), Constraint::Min(8), Constraint::Length(1)])
+        .split(frame.size());
+
+    let items: Vec<ListItem> = review
+        .findings
+        .iter()
+        .zip(&review.decisions)
+        .map(|(finding, decision)| {
+            let (marker, color) = match decision {
+                Some(Decision::Accept) => ("[✓]", Color::Green),
+                Some(Decision::Reject) => ("[✗]", Color::Red),
+                Some(Decision::Skip) => ("[-]", Color::DarkGray),
+                None => ("[ ]", Color::Reset),
+            };
+            ListItem::new(format!("{} {}:{}  {}", marker, finding.file, finding.line_number, finding.rule_id()))
+                .style(Style::default().fg(color))
+        })
+        .collect();
+    let decided = review.decisions.iter().filter(|d| d.is_some()).count();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " Findings ({}/{} reviewed) ",
+            decided,
+            review.findings.len()
+        )))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, areas[0], &mut review.state);
+
+    let finding = &review.findings[review.selected()];
+    let severity = finding.severity.map(|s| s.to_string()).unwrap_or_else(|| "unrated".to_string());
+    let mut lines = vec![
+        Line::from(format!("File:     {}:{}", finding.file, finding.line_number)),
+        Line::from(format!("Rule:     {} ({})", finding.rule_id(), severity)),
+        Line::from(format!("Language: {}", finding.language)),
+        Line::from(""),
+        Line::styled(finding.line_content.trim().to_string(), Style::default().add_modifier(Modifier::BOLD)),
+        Line::from(""),
+    ];
+    lines.extend(finding.context.lines().map(|line| Line::from(line.to_string())));
+    let details = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(" Details "))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(details, areas[1]);
+
+    frame.render_widget(Paragraph::new(HELP).style(Style::default().fg(Color::DarkGray)), areas[2]);
+}
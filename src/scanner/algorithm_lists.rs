@@ -0,0 +1,184 @@
+use crate::scanner::secrets::is_comment_line;
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::lang_ident::detect_language;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+lazy_static! {
+    /// Algorithm preference lists: (pattern, list name). Group 1 holds the raw list.
+    static ref ALGORITHM_LISTS: Vec<(Regex, &'static str)> = vec![
+        // OpenSSH: Ciphers, MACs, KexAlgorithms, HostKeyAlgorithms, PubkeyAcceptedAlgorithms
+        (Regex::new(r"^\s*(?i:ciphers|macs|kexalgorithms|hostkeyalgorithms|pubkeyaccepted(?:keytypes|algorithms))\s+([^\s=:(]\S*)").unwrap(), "SSH algorithm"),
+        // nginx ssl_ciphers, Apache SSLCipherSuite
+        (Regex::new(r#"(?i)\b(?:ssl_ciphers|sslciphersuite|proxy_ssl_ciphers)\s+['"]?([^'";]+)"#).unwrap(), "TLS cipher"),
+        // nginx ssl_protocols, Apache SSLProtocol
+        (Regex::new(r"(?i)\b(?:ssl_protocols|sslprotocol)\s+([^;]+)").unwrap(), "TLS protocol"),
+        // ciphers = "..." / cipher_suites: "..." in code and config
+        (Regex::new(r#"(?i)\b(?:ssl_?ciphers?|ciphers|cipher_?suites|set_ciphers)\s*(?:[:=]|\()\s*['"]([^'"]+)['"]"#).unwrap(), "TLS cipher"),
+        // JWT: algorithms=["HS256", "none"], algorithms: ['RS256']
+        (Regex::new(r#"(?i)\balgorithms['"]?\s*[:=]\s*\[([^\]]+)\]"#).unwrap(), "JWT algorithm"),
+    ];
+}
+
+/// Rate a single list entry, returning its severity and why it is weak
+fn classify_entry(entry: &str) -> Option<(Severity, &'static str)> {
+    let e = entry.to_lowercase();
+    let tokens: Vec<&str> = e.split(['-', '_', '@']).collect();
+    let has = |t: &str| tokens.contains(&t);
+
+    let rating = if e == "none" {
+        (Severity::Critical, "unsigned tokens are accepted")
+    } else if e.contains("null") {
+        (Severity::Critical, "no encryption or no authentication")
+    } else if e.starts_with("exp") {
+        (Severity::Critical, "export-grade cipher")
+    } else if e.contains("rc4") || e.starts_with("arcfour") {
+        (Severity::Critical, "RC4 is broken")
+    } else if e == "sslv2" || e == "sslv3" {
+        (Severity::Critical, "SSL protocol versions are broken")
+    } else if e.contains("3des") || e.contains("des-cbc3") || e.contains("des-ede3") || e.contains("des_ede3") {
+        (Severity::High, "3DES has a 64-bit block (Sweet32)")
+    } else if has("des") {
+        (Severity::Critical, "single DES has a 56-bit key")
+    } else if has("rc2") {
+        (Severity::High, "RC2 is obsolete")
+    } else if has("md5") {
+        (Severity::High, "MD5-based integrity")
+    } else if e == "diffie-hellman-group1-sha1" || e == "ssh-dss" {
+        (Severity::High, "1024-bit / DSA algorithm")
+    } else if e == "tlsv1" || e == "tlsv1.0" {
+        (Severity::High, "TLS 1.0 is deprecated")
+    } else if e == "tlsv1.1" {
+        (Severity::Medium, "TLS 1.1 is deprecated")
+    } else if e.ends_with("-cbc") {
+        (Severity::Medium, "CBC mode SSH cipher")
+    } else if e.starts_with("hmac-sha1") || e == "diffie-hellman-group14-sha1" || e == "diffie-hellman-group-exchange-sha1" || e == "ssh-rsa" {
+        (Severity::Medium, "SHA-1 based algorithm")
+    } else {
+        return None;
+    };
+    Some(rating)
+}
+
+/// Split a raw list into entries, dropping OpenSSL exclusions (`!RC4`, `-MD5`)
+fn list_entries(raw: &str) -> Vec<String> {
+    raw.split([',', ':', ' ', '\t'])
+        .map(|e| e.trim().trim_matches(|c| c == '"' || c == '\''))
+        .filter(|e| !e.is_empty() && !e.starts_with('!') && !e.starts_with('-'))
+        .map(|e| e.trim_start_matches(['+', '^']).to_string())
+        .collect()
+}
+
+/// Scans a file for algorithm lists that include weak fallback entries
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded content, reporting each weak list entry separately
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let language = detect_language(path);
+
+    for (line_num, line) in content.lines().enumerate() {
+        if is_comment_line(line) {
+            continue;
+        }
+
+        for (regex, list_name) in ALGORITHM_LISTS.iter() {
+            let Some(raw) = regex.captures(line).and_then(|c| c.get(1)) else {
+                continue;
+            };
+            let raw = raw.as_str().trim();
+
+            for entry in list_entries(raw) {
+                let Some((severity, reason)) = classify_entry(&entry) else {
+                    continue;
+                };
+                findings.push(Finding {
+                    file: path.display().to_string(),
+                    line_number: line_num + 1,
+                    end_line_number: None,
+                    column_start: None,
+                    column_end: None,
+                    line_content: line.to_string(),
+                    match_type: "algorithm-list".to_string(),
+                    keyword: entry.clone(),
+                    context: format!("Weak entry {} in {} list ({}); full list: {}", entry, list_name, reason, raw),
+                    version: None,
+                    language: language.clone(),
+                    source: "config".to_string(),
+                    category: "weak-algorithm-in-list".to_string(),
+                    severity: Some(severity),
+                    file_hash: None,
+                    cwe: None,
+                    remediation: None,
+                });
+            }
+            // A line describes one list; don't re-parse it with a looser pattern
+            break;
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cipher_list_with_aes_gcm_and_rc4() {
+        let content = "ssl_ciphers 'ECDHE-RSA-AES128-GCM-SHA256:AES256-GCM-SHA384:RC4-SHA:!aNULL:!MD5';\n";
+        let findings = scan_content(Path::new("nginx.conf"), content);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].keyword, "RC4-SHA");
+        assert_eq!(findings[0].category, "weak-algorithm-in-list");
+        assert_eq!(findings[0].severity, Some(Severity::Critical));
+        assert!(findings[0].context.contains("ECDHE-RSA-AES128-GCM-SHA256:AES256-GCM-SHA384:RC4-SHA"));
+    }
+
+    #[test]
+    fn test_ssh_lists_flag_each_weak_entry() {
+        let content = "Ciphers chacha20-poly1305@openssh.com,aes256-gcm@openssh.com,aes128-cbc,3des-cbc\n\
+                       MACs hmac-sha2-256-etm@openssh.com,hmac-md5\n\
+                       KexAlgorithms curve25519-sha256,diffie-hellman-group1-sha1\n";
+        let findings = scan_content(Path::new("sshd_config"), content);
+        let rated: Vec<(&str, Severity)> = findings
+            .iter()
+            .map(|f| (f.keyword.as_str(), f.severity.unwrap()))
+            .collect();
+
+        assert_eq!(rated, vec![
+            ("aes128-cbc", Severity::Medium),
+            ("3des-cbc", Severity::High),
+            ("hmac-md5", Severity::High),
+            ("diffie-hellman-group1-sha1", Severity::High),
+        ]);
+    }
+
+    #[test]
+    fn test_jwt_algorithms_list_with_none() {
+        let content = "payload = jwt.decode(token, key, algorithms=[\"RS256\", \"none\"])\n";
+        let findings = scan_content(Path::new("auth.py"), content);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].keyword, "none");
+        assert_eq!(findings[0].severity, Some(Severity::Critical));
+    }
+
+    #[test]
+    fn test_tls_protocols_and_strong_lists() {
+        let findings = scan_content(Path::new("nginx.conf"), "ssl_protocols TLSv1 TLSv1.1 TLSv1.2 TLSv1.3;\n");
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].severity, Some(Severity::High));
+        assert_eq!(findings[1].severity, Some(Severity::Medium));
+
+        let findings = scan_content(Path::new("nginx.conf"), "ssl_protocols TLSv1.2 TLSv1.3;\n");
+        assert!(findings.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
-use crate::utils::file_utils::read_file_to_string;
-use crate::utils::report::Finding;
+use crate::scanner::pem::{describe_block, pem_blocks};
+use crate::utils::file_utils::{read_file_lossy, sha256_file};
+use crate::utils::report::{Finding, Severity};
 use std::path::Path;
 
 const KEYSTORE_EXTENSIONS: &[(&str, &str)] = &[
@@ -28,61 +29,119 @@ const KEY_COMMAND_PATTERNS: &[(&str, &str, &str)] = &[
     ("cfssl genkey", "CFSSL", "Shell"),
 ];
 
-/// Detect keystore files by extension
-pub fn scan_keystore_file(path: &Path) -> Option<Finding> {
-    path.extension()
+/// Keystore extensions whose files are usually PEM text, parsed block by block
+const PEM_EXTENSIONS: &[&str] = &["pem", "crt", "cer", "key"];
+
+/// Detect keystore files. A PEM file yields one finding per block, spanning its BEGIN to END
+/// lines, with the block's key type and size in `context`; other keystores, and PEM-named
+/// files with no blocks (DER certificates), one finding classified by extension.
+pub fn scan_keystore_file(path: &Path) -> Vec<Finding> {
+    let Some((key_ext, label)) = path
+        .extension()
         .and_then(|ext| ext.to_str())
-        .and_then(|ext| {
-            let ext = ext.to_lowercase();
-            for (key_ext, label) in KEYSTORE_EXTENSIONS {
-                if ext == *key_ext {
-                    return Some(Finding {
-                        file: path.display().to_string(),
-                        line_number: 0,
-                        line_content: "".to_string(),
-                        match_type: "keystore".to_string(),
-                        keyword: key_ext.to_string(),
-                        context: label.to_string(),
-                        version: None,
-                        language: "Binary/File".to_string(),
-                        source: "file extension".to_string(),
-                        category: "keystore".to_string(),
-                    });
-                }
+        .and_then(|ext| KEYSTORE_EXTENSIONS.iter().find(|(key_ext, _)| ext.eq_ignore_ascii_case(key_ext)))
+    else {
+        return Vec::new();
+    };
+    let file_hash = sha256_file(path).ok().map(|(_, digest)| digest);
+
+    if PEM_EXTENSIONS.contains(key_ext) {
+        if let Ok(content) = read_file_lossy(path) {
+            let lines: Vec<&str> = content.lines().collect();
+            let findings: Vec<Finding> = pem_blocks(&content)
+                .iter()
+                .map(|block| Finding {
+                    file: path.display().to_string(),
+                    line_number: block.begin_line,
+                    end_line_number: block.end_line,
+                    column_start: None,
+                    column_end: None,
+                    line_content: lines[block.begin_line - 1].to_string(),
+                    match_type: "keystore".to_string(),
+                    keyword: block.label.clone(),
+                    context: describe_block(block),
+                    version: None,
+                    language: "PEM".to_string(),
+                    source: "pem block".to_string(),
+                    category: "keystore".to_string(),
+                    severity: Some(Severity::Medium),
+                    file_hash: file_hash.clone(),
+                    cwe: None,
+                    remediation: None,
+                })
+                .collect();
+            if !findings.is_empty() {
+                return findings;
             }
-            None
-        })
+        }
+    }
+
+    vec![Finding {
+        file: path.display().to_string(),
+        line_number: 0,
+        end_line_number: None,
+        column_start: None,
+        column_end: None,
+        line_content: "".to_string(),
+        match_type: "keystore".to_string(),
+        keyword: key_ext.to_string(),
+        context: label.to_string(),
+        version: None,
+        language: "Binary/File".to_string(),
+        source: "file extension".to_string(),
+        category: "keystore".to_string(),
+        severity: Some(Severity::Medium),
+        file_hash,
+        cwe: None,
+        remediation: None,
+    }]
 }
 
 /// Detect CLI key management commands in plaintext/script files
 pub fn scan_key_commands(path: &Path) -> Vec<Finding> {
-    let mut findings = Vec::new();
+    match read_file_lossy(path) {
+        Ok(content) => scan_key_commands_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
 
-    if let Ok(content) = read_file_to_string(path) {
-        for (i, line) in content.lines().enumerate() {
-            let trimmed = line.trim_start();
-            if trimmed.starts_with('#') || trimmed.starts_with("//") || trimmed.starts_with('*') {
-                continue;
-            }
+/// Detect CLI key management commands in already-loaded content
+pub fn scan_key_commands_content(path: &Path, content: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        findings.extend(scan_key_commands_line(path, i + 1, line));
+    }
+    findings
+}
 
-            for (pattern, label, language) in KEY_COMMAND_PATTERNS {
-                if line.contains(pattern) {
-                    findings.push(Finding {
-                        file: path.display().to_string(),
-                        line_number: i + 1,
-                        line_content: line.to_string(),
-                        match_type: "command".to_string(),
-                        keyword: pattern.to_string(),
-                        context: label.to_string(),
-                        version: None,
-                        language: language.to_string(),
-                        source: "command".to_string(),
-                        category: "key-command".to_string(),
-                    });
-                }
-            }
-        }
+/// Detect CLI key management commands on one line, for files scanned a line at a time
+pub fn scan_key_commands_line(path: &Path, line_number: usize, line: &str) -> Vec<Finding> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') || trimmed.starts_with("//") || trimmed.starts_with('*') {
+        return Vec::new();
     }
 
-    findings
+    KEY_COMMAND_PATTERNS
+        .iter()
+        .filter(|(pattern, _, _)| line.contains(pattern))
+        .map(|(pattern, label, language)| Finding {
+            file: path.display().to_string(),
+            line_number,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: line.to_string(),
+            match_type: "command".to_string(),
+            keyword: pattern.to_string(),
+            context: label.to_string(),
+            version: None,
+            language: language.to_string(),
+            source: "command".to_string(),
+            category: "key-command".to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        })
+        .collect()
 }
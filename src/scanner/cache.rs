@@ -0,0 +1,234 @@
+//! `--cache`: reuse the findings of files that haven't changed since the previous scan.
+//!
+//! Entries are keyed by path. A file whose mtime and size both match its entry is reused
+//! without reading it; otherwise its SHA-256 decides, so a `git checkout` that rewrites
+//! mtimes without changing content still hits. An mtime within [`RACY_WINDOW`] of the scan
+//! isn't recorded, as the file could still change within the same mtime tick, so such a file
+//! is always hashed next time. What is cached is the scanners' own output;
+//! library versions, path-based severity, `--severity-clamp`, suppressions and the
+//! file-permission check are applied afresh on every scan. A cache written by another
+//! version or with different scanner options is discarded as a whole.
+
+use crate::scanner::FileScan;
+use crate::utils::file_utils::sha256_file;
+use crate::utils::report::Finding;
+use crate::utils::skipped::{SkipReason, SkippedFile};
+use crate::utils::summary::CacheStats;
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// Where `--cache` without a path keeps the cache
+pub const DEFAULT_CACHE_FILE: &str = ".cryptoscan-cache.json";
+
+/// How recent an mtime must be to not be trusted on the next scan
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// What one file's scan produced, and the content it was produced from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    sha256: String,
+    /// The file's mtime when scanned, if it was old enough to trust
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mtime: Option<SystemTime>,
+    #[serde(default)]
+    len: u64,
+    findings: Vec<Finding>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    skipped: Vec<SkippedFile>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// [`crate::scanner::ScanOptions::cache_fingerprint`] of the scan that wrote it
+    fingerprint: String,
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+/// The cache of one scan: the entries loaded from disk, and those scanned since
+#[derive(Debug)]
+pub struct ScanCache {
+    path: PathBuf,
+    fingerprint: String,
+    previous: BTreeMap<String, CacheEntry>,
+    scanned: Mutex<BTreeMap<String, CacheEntry>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl ScanCache {
+    /// Load the cache at `path`. A missing, unreadable or stale cache starts empty; it is
+    /// only a speed-up, so it never fails the scan.
+    pub fn open(path: &Path, fingerprint: String) -> Self {
+        let previous = match fs::read_to_string(path) {
+            Ok(json) => match serde_json::from_str::<CacheFile>(&json) {
+                Ok(file) if file.fingerprint == fingerprint => {
+                    info!("Loaded {} cached file(s) from {}", file.entries.len(), path.display());
+                    file.entries
+                }
+                Ok(_) => {
+                    info!("Discarding cache {}: written with other scanner options or version", path.display());
+                    BTreeMap::new()
+                }
+                Err(e) => {
+                    info!("Discarding unreadable cache {}: {}", path.display(), e);
+                    BTreeMap::new()
+                }
+            },
+            Err(e) => {
+                debug!("No cache read from {}: {}", path.display(), e);
+                BTreeMap::new()
+            }
+        };
+        ScanCache {
+            path: path.to_path_buf(),
+            fingerprint,
+            previous,
+            scanned: Mutex::default(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Whether `path` is the cache file itself, which is never scanned
+    pub fn is_cache_file(&self, path: &Path) -> bool {
+        path.file_name() == self.path.file_name()
+            && fs::canonicalize(path).ok().is_some_and(|path| fs::canonicalize(&self.path).ok() == Some(path))
+    }
+
+    /// The cached scan of `path` if its content is unchanged, otherwise `scan`'s result,
+    /// which is cached unless the file couldn't be read. The file is only hashed when its
+    /// mtime or size differ from the entry's. A file that can't be read is scanned without
+    /// counting as a hit or miss.
+    pub fn scan(&self, path: &Path, scan: impl FnOnce() -> FileScan) -> FileScan {
+        let Ok(metadata) = fs::metadata(path) else {
+            return scan();
+        };
+        let len = metadata.len();
+        let mtime = metadata.modified().ok().filter(|mtime| mtime.elapsed().is_ok_and(|age| age > RACY_WINDOW));
+        let key = path.display().to_string();
+        let previous = self.previous.get(&key);
+
+        let sha256 = match previous.filter(|entry| entry.mtime.is_some() && entry.mtime == mtime && entry.len == len) {
+            Some(entry) => entry.sha256.clone(),
+            None => match sha256_file(path) {
+                Ok((_, sha256)) => sha256,
+                Err(_) => return scan(),
+            },
+        };
+        if let Some(entry) = previous.filter(|entry| entry.sha256 == sha256) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            let entry = CacheEntry { mtime, len, ..entry.clone() };
+            let result = FileScan { findings: entry.findings.clone(), skipped: entry.skipped.clone() };
+            self.scanned.lock().unwrap().insert(key, entry);
+            return result;
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let result = scan();
+        if !result.skipped.iter().any(|skipped| skipped.reason == SkipReason::IoError) {
+            let entry = CacheEntry { sha256, mtime, len, findings: result.findings.clone(), skipped: result.skipped.clone() };
+            self.scanned.lock().unwrap().insert(key, entry);
+        }
+        result
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats { hits: self.hits.load(Ordering::Relaxed), misses: self.misses.load(Ordering::Relaxed) }
+    }
+
+    /// Write the cache back: this scan's entries, plus the loaded entries of files it didn't
+    /// visit (a `--since` or file-list scan) that still exist
+    pub fn save(&self) -> io::Result<()> {
+        let mut entries: BTreeMap<String, CacheEntry> = self
+            .previous
+            .iter()
+            .filter(|(file, _)| Path::new(file).is_file())
+            .map(|(file, entry)| (file.clone(), entry.clone()))
+            .collect();
+        entries.extend(self.scanned.lock().unwrap().iter().map(|(file, entry)| (file.clone(), entry.clone())));
+
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = CacheFile { fingerprint: self.fingerprint.clone(), entries };
+        fs::write(&self.path, serde_json::to_string(&file)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_hits_follow_content_not_mtime() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("app.py");
+        let cache_path = dir.path().join("cache/scan.json");
+        fs::write(&source, "password = \"hunter2hunter2\"\n").unwrap();
+        let password = Finding::for_test(&source.display().to_string(), 1, "Password", "secret");
+        let scan = || FileScan { findings: vec![password.clone()], skipped: Vec::new() };
+
+        let cache = ScanCache::open(&cache_path, "options".to_string());
+        assert_eq!(cache.scan(&source, scan).findings.len(), 1);
+        assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 1 });
+        cache.save().unwrap();
+
+        // Rewritten with the same content: a hit, without scanning
+        fs::write(&source, "password = \"hunter2hunter2\"\n").unwrap();
+        let cache = ScanCache::open(&cache_path, "options".to_string());
+        let cached = cache.scan(&source, || panic!("unchanged file was rescanned"));
+        assert_eq!(cached.findings[0].keyword, "Password");
+        assert!(cache.is_cache_file(&cache_path) && !cache.is_cache_file(&source));
+
+        fs::write(&source, "print('changed')\n").unwrap();
+        assert!(cache.scan(&source, FileScan::default).findings.is_empty());
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+
+        // Other scanner options invalidate everything
+        cache.save().unwrap();
+        let cache = ScanCache::open(&cache_path, "other options".to_string());
+        assert!(cache.previous.is_empty());
+    }
+
+    #[test]
+    fn test_unchanged_mtime_and_size_skip_hashing() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("app.py");
+        let cache_path = dir.path().join("scan.json");
+        let an_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        let write = |content: &str, mtime: SystemTime| {
+            fs::write(&source, content).unwrap();
+            fs::File::options().write(true).open(&source).unwrap().set_modified(mtime).unwrap();
+        };
+
+        write("password = \"hunter2hunter2\"\n", an_hour_ago);
+        let cache = ScanCache::open(&cache_path, "options".to_string());
+        let password = Finding::for_test(&source.display().to_string(), 1, "Password", "secret");
+        cache.scan(&source, || FileScan { findings: vec![password], skipped: Vec::new() });
+        cache.save().unwrap();
+
+        // Same size and mtime: trusted without reading the file, even though it changed
+        write("password = \"hunter3hunter3\"\n", an_hour_ago);
+        let cache = ScanCache::open(&cache_path, "options".to_string());
+        let cached = cache.scan(&source, || panic!("file with unchanged mtime and size was rescanned"));
+        assert_eq!(cached.findings.len(), 1);
+
+        // A different mtime falls back to the hash, which has changed
+        write("password = \"hunter3hunter3\"\n", an_hour_ago + Duration::from_secs(60));
+        assert!(cache.scan(&source, FileScan::default).findings.is_empty());
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1 });
+
+        // A file modified just now isn't trusted by mtime
+        fs::write(&source, "print('fresh')\n").unwrap();
+        cache.scan(&source, FileScan::default);
+        assert_eq!(cache.scanned.lock().unwrap()[&source.display().to_string()].mtime, None);
+    }
+}
@@ -1,18 +1,32 @@
-use crate::utils::file_utils::read_file_to_string;
-use crate::utils::report::Finding;
+use crate::error::ScanResult;
+use crate::scanner::rules::{compile_user_pattern, load_rule_file, RegexLimits};
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::lang_ident::detect_language;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Deserialize;
+use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::fs;
 use std::path::Path;
 
-/// Map of match-pattern -> (label, source, language, optional version)
-fn get_crypto_keywords() -> HashMap<&'static str, (&'static str, &'static str, &'static str, Option<&'static str>)> {
+/// (label, source, language, optional version) of a built-in library
+type KeywordEntry = (&'static str, &'static str, &'static str, Option<&'static str>);
+
+/// Map of match-pattern -> built-in library entry
+type CryptoKeywords = HashMap<&'static str, KeywordEntry>;
+
+fn get_crypto_keywords() -> CryptoKeywords {
     let mut map = HashMap::new();
 
     // Rust
-    map.insert("openssl", ("openssl", "use", "Rust", Some("0.10")));
+    map.insert("openssl", ("openssl", "use", "Rust", None));
     map.insert("ring", ("ring", "use", "Rust", None));
     map.insert("rustls", ("rustls", "use", "Rust", None));
     map.insert("secrecy", ("secrecy", "use", "Rust", None));
+    map.insert("oqs", ("oqs", "use", "Rust", None));
+    map.insert("pqcrypto", ("pqcrypto", "use", "Rust", None));
 
     // Python
     map.insert("cryptography", ("cryptography", "import", "Python", None));
@@ -27,31 +41,253 @@ fn get_crypto_keywords() -> HashMap<&'static str, (&'static str, &'static str, &
     map.insert("bouncycastle", ("bouncycastle", "import", "Java", None));
     map.insert("java.security", ("java.security", "import", "Java", None));
     map.insert("sun.security", ("sun.security", "import", "Java", None));
+    map.insert("org.bouncycastle.pqc", ("bouncycastle-pqc", "import", "Java", None));
 
-    // JS / Node
-    map.insert("require('crypto')", ("crypto", "require", "JavaScript", None));
-    map.insert("require(\"crypto\")", ("crypto", "require", "JavaScript", None));
-    map.insert("require('jsonwebtoken')", ("jsonwebtoken", "require", "JavaScript", None));
-    map.insert("require(\"jsonwebtoken\")", ("jsonwebtoken", "require", "JavaScript", None));
-    map.insert("require('bcrypt')", ("bcrypt", "require", "JavaScript", None));
-    map.insert("require(\"argon2\")", ("argon2", "require", "JavaScript", None));
-    map.insert("require('node-forge')", ("node-forge", "require", "JavaScript", None));
-
-    // Go
+    // JS / Node: matched against the module names of import/require statements
+    map.insert("crypto", ("crypto", "require", "JavaScript", None));
+    map.insert("jsonwebtoken", ("jsonwebtoken", "require", "JavaScript", None));
+    map.insert("bcrypt", ("bcrypt", "require", "JavaScript", None));
+    map.insert("argon2", ("argon2", "require", "JavaScript", None));
+    map.insert("node-forge", ("node-forge", "require", "JavaScript", None));
+    map.insert("@noble/post-quantum", ("@noble/post-quantum", "require", "JavaScript", None));
+
+    // Go: module path prefixes, matched against import paths and go.mod requirements
+    // (`crypto/` keeps the stdlib key apart from the JS `crypto` module)
     map.insert("crypto/", ("crypto", "import", "Go", None));
-    map.insert("golang.org/x/crypto", ("golang.crypto", "import", "Go", None));
+    map.insert("golang.org/x/crypto", ("golang.org/x/crypto", "import", "Go", None));
+    map.insert("github.com/cloudflare/circl", ("github.com/cloudflare/circl", "import", "Go", None));
+    map.insert("github.com/ProtonMail/go-crypto", ("github.com/ProtonMail/go-crypto", "import", "Go", None));
+    map.insert("filippo.io/age", ("filippo.io/age", "import", "Go", None));
+    map.insert("filippo.io/edwards25519", ("filippo.io/edwards25519", "import", "Go", None));
+    map.insert("github.com/golang-jwt/jwt", ("github.com/golang-jwt/jwt", "import", "Go", None));
+    map.insert("github.com/lestrrat-go/jwx", ("github.com/lestrrat-go/jwx", "import", "Go", None));
+    map.insert("github.com/tink-crypto/tink-go", ("github.com/tink-crypto/tink-go", "import", "Go", None));
+    map.insert("go.step.sm/crypto", ("go.step.sm/crypto", "import", "Go", None));
+    map.insert("software.sslmate.com/src/go-pkcs12", ("software.sslmate.com/src/go-pkcs12", "import", "Go", None));
+    map.insert("github.com/miekg/pkcs11", ("github.com/miekg/pkcs11", "import", "Go", None));
 
     // C / C++
     map.insert("#include <openssl", ("openssl", "include", "C/C++", None));
     map.insert("#include <sodium.h>", ("libsodium", "include", "C/C++", None));
     map.insert("#include <mbedtls", ("mbedtls", "include", "C/C++", None));
     map.insert("#include <wolfssl", ("wolfssl", "include", "C/C++", None));
+    map.insert("#include <oqs/", ("liboqs", "include", "C/C++", None));
 
     map
 }
 
+/// Algorithms each built-in library label typically provides, most common defaults first.
+/// Ids are catalogued by the CBOM generator; Go standard library packages are listed by path.
+pub const LIBRARY_ALGORITHMS: &[(&str, &[&str])] = &[
+    // Rust
+    ("openssl", &["AES-256", "SHA-256", "RSA-2048", "ECDSA-P256", "ECDH-P256"]),
+    ("ring", &["AES-256-GCM", "ChaCha20-Poly1305", "SHA-256", "HMAC-SHA256", "HKDF-SHA256", "ECDSA-P256", "Ed25519", "X25519", "RSA-2048"]),
+    ("rustls", &["AES-256-GCM", "ChaCha20-Poly1305", "X25519", "ECDH-P256", "ECDSA-P256", "RSA-2048", "Ed25519"]),
+    ("secrecy", &[]),
+    ("oqs", &["ML-KEM-768", "ML-DSA-65", "SLH-DSA-SHA2-128s"]),
+    ("pqcrypto", &["ML-KEM-768", "ML-DSA-65", "SLH-DSA-SHA2-128s"]),
+    // Python
+    ("cryptography", &["AES-256-GCM", "ChaCha20-Poly1305", "SHA-256", "HMAC-SHA256", "RSA-2048", "ECDSA-P256", "Ed25519", "X25519"]),
+    ("pycrypto", &["AES-256", "DES", "SHA-256", "RSA-2048"]),
+    ("pycryptodome", &["AES-256-GCM", "ChaCha20-Poly1305", "SHA-256", "RSA-2048", "ECDSA-P256"]),
+    ("ssl", &["AES-256-GCM", "ChaCha20-Poly1305", "ECDH-P256", "RSA-2048"]),
+    ("hashlib", &["SHA-256", "SHA-512", "SHA3-256", "BLAKE2b", "SHA-1", "MD5"]),
+    ("jwt", &["HMAC-SHA256", "RSA-2048", "ECDSA-P256"]),
+    // Java
+    ("javax.crypto", &["AES-256", "HMAC-SHA256", "PBKDF2-SHA256"]),
+    ("bouncycastle", &["AES-256", "SHA-256", "RSA-2048", "ECDSA-P256", "Ed25519"]),
+    ("bouncycastle-pqc", &["ML-KEM-768", "ML-DSA-65", "SLH-DSA-SHA2-128s"]),
+    ("java.security", &["SHA-256", "RSA-2048", "ECDSA-P256"]),
+    ("sun.security", &["RSA-2048", "ECDSA-P256"]),
+    // JS / Node
+    ("crypto", &["AES-256-GCM", "SHA-256", "HMAC-SHA256", "PBKDF2-SHA256", "RSA-2048", "ECDH-P256"]),
+    ("jsonwebtoken", &["HMAC-SHA256", "RSA-2048", "ECDSA-P256"]),
+    ("bcrypt", &["bcrypt"]),
+    ("argon2", &["Argon2id"]),
+    ("node-forge", &["AES-256", "SHA-256", "RSA-2048"]),
+    ("@noble/post-quantum", &["ML-KEM-768", "ML-DSA-65", "SLH-DSA-SHA2-128s"]),
+    // Go modules
+    ("golang.org/x/crypto", &["ChaCha20-Poly1305", "X25519", "Ed25519", "bcrypt", "Argon2id", "scrypt", "HKDF-SHA256"]),
+    ("github.com/cloudflare/circl", &["ML-KEM-768", "ML-DSA-65", "X25519", "Ed25519"]),
+    ("github.com/ProtonMail/go-crypto", &["AES-256", "RSA-2048", "Ed25519", "X25519"]),
+    ("filippo.io/age", &["X25519", "ChaCha20-Poly1305", "scrypt"]),
+    ("filippo.io/edwards25519", &["Ed25519"]),
+    ("github.com/golang-jwt/jwt", &["HMAC-SHA256", "RSA-2048", "ECDSA-P256"]),
+    ("github.com/lestrrat-go/jwx", &["HMAC-SHA256", "RSA-2048", "ECDSA-P256"]),
+    ("github.com/tink-crypto/tink-go", &["AES-256-GCM", "HMAC-SHA256", "ECDSA-P256", "Ed25519"]),
+    ("go.step.sm/crypto", &["ECDSA-P256", "RSA-2048", "Ed25519"]),
+    ("software.sslmate.com/src/go-pkcs12", &["AES-256", "PBKDF2-SHA256", "RSA-2048", "3DES"]),
+    ("github.com/miekg/pkcs11", &["RSA-2048", "ECDSA-P256", "AES-256"]),
+    // Go standard library
+    ("crypto/aes", &["AES-256"]),
+    ("crypto/cipher", &["AES-256-GCM"]),
+    ("crypto/des", &["DES", "3DES"]),
+    ("crypto/dsa", &["DSA-2048"]),
+    ("crypto/ecdh", &["ECDH-P256", "X25519"]),
+    ("crypto/ecdsa", &["ECDSA-P256"]),
+    ("crypto/ed25519", &["Ed25519"]),
+    ("crypto/elliptic", &["ECDSA-P256", "ECDH-P256"]),
+    ("crypto/hkdf", &["HKDF-SHA256"]),
+    ("crypto/hmac", &["HMAC-SHA256"]),
+    ("crypto/md5", &["MD5"]),
+    ("crypto/mlkem", &["ML-KEM-768"]),
+    ("crypto/pbkdf2", &["PBKDF2-SHA256"]),
+    ("crypto/rc4", &["RC4"]),
+    ("crypto/rsa", &["RSA-2048"]),
+    ("crypto/sha1", &["SHA-1"]),
+    ("crypto/sha256", &["SHA-256"]),
+    ("crypto/sha3", &["SHA3-256"]),
+    ("crypto/sha512", &["SHA-512"]),
+    ("crypto/tls", &["AES-256-GCM", "ChaCha20-Poly1305", "X25519", "ECDSA-P256", "RSA-2048"]),
+    ("crypto/x509", &["RSA-2048", "ECDSA-P256", "Ed25519"]),
+    // C / C++
+    ("libsodium", &["XSalsa20-Poly1305", "ChaCha20-Poly1305", "X25519", "Ed25519", "BLAKE2b", "Argon2id"]),
+    ("mbedtls", &["AES-256-GCM", "SHA-256", "RSA-2048", "ECDSA-P256", "ECDH-P256"]),
+    ("wolfssl", &["AES-256-GCM", "ChaCha20-Poly1305", "SHA-256", "RSA-2048", "ECDSA-P256", "ECDH-P256"]),
+    ("liboqs", &["ML-KEM-768", "ML-DSA-65", "SLH-DSA-SHA2-128s"]),
+];
+
+/// The [`LIBRARY_ALGORITHMS`] entry for a library label, ignoring case
+pub fn library_algorithms(label: &str) -> Option<&'static [&'static str]> {
+    LIBRARY_ALGORITHMS
+        .iter()
+        .find(|(library, _)| library.eq_ignore_ascii_case(label))
+        .map(|(_, algorithms)| *algorithms)
+}
+
+lazy_static! {
+    /// Crate named by a `use` or `extern crate` item
+    static ref RUST_IMPORT: Regex = Regex::new(
+        r"^\s*(?:pub(?:\([^)]*\))?\s+)?(use\s+(?:::)?|extern\s+crate\s+)([A-Za-z_][A-Za-z0-9_]*)"
+    ).unwrap();
+
+    /// Module named by an `import`/`export ... from` statement or a `require()`/`import()` call
+    static ref JS_IMPORT: Regex = Regex::new(
+        r#"(?:^\s*import\s+(?:[\w*{}\s,$]+\s+from\s+)?|^\s*export\s+[\w*{}\s,$]+\s+from\s+|\b(require|import)\s*\(\s*)['"]([^'"]+)['"]"#
+    ).unwrap();
+
+    /// A Cargo.toml table holding dependencies, e.g. `[dependencies]` or `[target.'cfg(unix)'.dev-dependencies]`
+    static ref CARGO_DEPENDENCY_TABLE: Regex = Regex::new(
+        r"^\s*\[(?:.*\.)?(?:dependencies|dev-dependencies|build-dependencies)(?:\.([A-Za-z0-9_-]+))?\s*\]"
+    ).unwrap();
+
+    /// Dependency key inside a Cargo.toml dependency table: `name = ...` or `name.workspace = true`
+    static ref CARGO_DEPENDENCY: Regex = Regex::new(r"^\s*([A-Za-z0-9_-]+)\s*(?:\.|=)").unwrap();
+
+    /// Quoted path of a Go import spec, after any `alias`, `_` or `.` name
+    static ref GO_IMPORT_PATH: Regex = Regex::new(r#"(?:^|;)\s*(?:[A-Za-z_][A-Za-z0-9_]*\s+|[._]\s*)?["`]([^"`]+)["`]"#).unwrap();
+
+    /// A go.mod requirement: `module version`, inside or after `require`
+    static ref GO_REQUIREMENT: Regex = Regex::new(r"^\s*(?:require\s+)?([^\s()]+)\s+(v[^\s]+)").unwrap();
+}
+
+/// A library rule from a `--rules` file
+#[derive(Debug, Clone)]
+pub struct LibraryRule {
+    /// The pattern as written; a built-in entry with the same pattern is replaced
+    pub pattern: String,
+    /// Matched against each line, or for Rust/JavaScript/Go against the whole imported module name
+    regex: Regex,
+    pub label: String,
+    pub language: String,
+    pub source: String,
+    pub version: Option<String>,
+}
+
+impl LibraryRule {
+    fn entry(&self) -> (&str, &str, Option<&str>) {
+        (&self.label, &self.language, self.version.as_deref())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LibraryRuleEntry {
+    pattern: String,
+    label: String,
+    language: String,
+    source: String,
+    version: Option<String>,
+}
+
+/// Other sections (e.g. `[[secret]]` for `--secret-rules`) may share the file
+#[derive(Debug, Deserialize)]
+struct LibraryRuleFile {
+    #[serde(default)]
+    library: Vec<LibraryRuleEntry>,
+}
+
+/// Load and compile the `[[library]]` entries of a `--rules` file
+pub fn load_library_rules(path: &Path, limits: RegexLimits) -> ScanResult<Vec<LibraryRule>> {
+    let file: LibraryRuleFile = load_rule_file(path)?;
+    file.library
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let name = format!("{} (entry {} in {})", entry.label, i + 1, path.display());
+            // Module names are matched whole so `openssl` doesn't also match `openssl_probe`
+            let regex = if is_import_language(&entry.language) {
+                compile_user_pattern(&name, &format!("^(?:{})$", entry.pattern), limits)?
+            } else {
+                compile_user_pattern(&name, &entry.pattern, limits)?
+            };
+            Ok(LibraryRule {
+                pattern: entry.pattern,
+                regex,
+                label: entry.label,
+                language: entry.language,
+                source: entry.source,
+                version: entry.version,
+            })
+        })
+        .collect()
+}
+
+fn is_import_language(language: &str) -> bool {
+    matches!(language, "Rust" | "JavaScript" | "Go")
+}
+
+/// Languages whose library usage is only taken from parsed import statements
+fn import_language(path: &Path) -> Option<&'static str> {
+    match detect_language(path).as_str() {
+        "Rust" => Some("Rust"),
+        "JavaScript" | "TypeScript" | "JSX" => Some("JavaScript"),
+        "Go" => Some("Go"),
+        _ => None,
+    }
+}
+
+/// Whether this is a Cargo manifest, whose dependency tables count as Rust library usage
+pub fn is_cargo_manifest(path: &Path) -> bool {
+    path.file_name().and_then(|f| f.to_str()) == Some("Cargo.toml")
+}
+
+/// Whether this is a Go module file, whose requirements count as Go library usage
+pub fn is_go_manifest(path: &Path) -> bool {
+    path.file_name().and_then(|f| f.to_str()) == Some("go.mod")
+}
+
+/// The imported Rust crate, or JS package (without `node:` prefix or subpath), on this line
+fn imported_module(language: &str, line: &str) -> Option<(String, &'static str)> {
+    match language {
+        "Rust" => {
+            let caps = RUST_IMPORT.captures(line)?;
+            let kind = if caps[1].starts_with("use") { "use" } else { "extern crate" };
+            Some((caps[2].to_string(), kind))
+        }
+        _ => {
+            let caps = JS_IMPORT.captures(line)?;
+            let kind = if caps.get(1).is_some_and(|call| call.as_str() == "require") { "require" } else { "import" };
+            let module = caps[2].strip_prefix("node:").unwrap_or(&caps[2]);
+            // `@scope/pkg/sub` -> `@scope/pkg`, `pkg/sub` -> `pkg`
+            let segments = if module.starts_with('@') { 2 } else { 1 };
+            Some((module.split('/').take(segments).collect::<Vec<_>>().join("/"), kind))
+        }
+    }
+}
+
 fn to_safe_regex(pattern: &str) -> Regex {
-    if pattern.contains("require(") || pattern.starts_with("#include") || pattern.contains('/') {
+    if pattern.starts_with("#include") || pattern.contains('/') {
         Regex::new(&regex::escape(pattern)).unwrap()
     } else {
         Regex::new(&format!(r"\b{}\b", regex::escape(pattern))).unwrap()
@@ -60,35 +296,608 @@ fn to_safe_regex(pattern: &str) -> Regex {
 
 /// Scans a source file for crypto-related patterns
 pub fn scan_file(path: &Path) -> Vec<Finding> {
-    let mut findings = Vec::new();
-    let keywords = get_crypto_keywords();
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded source content for crypto-related patterns
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    scan_content_with_rules(path, content, &[])
+}
 
-    if let Ok(content) = read_file_to_string(path) {
-        for (i, line) in content.lines().enumerate() {
-            let trimmed = line.trim_start();
+/// Like [`scan_content`], with `--rules` library rules merged into the built-in map
+pub fn scan_content_with_rules(path: &Path, content: &str, rules: &[LibraryRule]) -> Vec<Finding> {
+    if let Some(mut scanner) = LibraryScanner::new(path, rules) {
+        for line in content.lines() {
+            scanner.scan_line(line);
+        }
+        return scanner.finish();
+    }
+
+    let keywords = merged_keywords(rules);
+    if is_cargo_manifest(path) {
+        scan_cargo_manifest(path, content, &keywords, rules)
+    } else if is_go_manifest(path) {
+        scan_go_manifest(path, content, &keywords, rules)
+    } else {
+        scan_go_imports(path, content, &keywords, rules)
+    }
+}
+
+/// The built-in library map, less the entries `rules` replace
+fn merged_keywords(rules: &[LibraryRule]) -> CryptoKeywords {
+    let mut keywords = get_crypto_keywords();
+    // A user rule with the same pattern as a built-in entry replaces it
+    keywords.retain(|pattern, _| !rules.iter().any(|rule| rule.pattern == *pattern));
+    keywords
+}
+
+fn library_finding(path: &Path, line_number: usize, line: &str, kind: &str, entry: (&str, &str, Option<&str>)) -> Finding {
+    let (label, language, version) = entry;
+    Finding {
+        file: path.display().to_string(),
+        line_number,
+        end_line_number: None,
+        column_start: None,
+        column_end: None,
+        line_content: line.to_string(),
+        match_type: kind.to_string(),
+        keyword: label.to_string(),
+        context: kind.to_string(),
+        version: version.map(|v| v.to_string()),
+        language: language.to_string(),
+        source: kind.to_string(),
+        category: "library".to_string(),
+        severity: Some(Severity::Info),
+        file_hash: None,
+        cwe: None,
+        remediation: None,
+    }
+}
+
+/// Built-in entries and rules of `language` naming this imported module, as (label, language, version)
+fn module_entries<'a>(
+    module: &str,
+    language: &str,
+    keywords: &'a CryptoKeywords,
+    rules: &'a [LibraryRule],
+) -> Vec<(&'a str, &'a str, Option<&'a str>)> {
+    let builtin = keywords
+        .get(module)
+        .filter(|(_, _, l, _)| *l == language)
+        .map(|(label, _, language, version)| (*label, *language, *version));
+    let user = rules
+        .iter()
+        .filter(|rule| rule.language == language && rule.regex.is_match(module))
+        .map(LibraryRule::entry);
+    builtin.into_iter().chain(user).collect()
+}
+
+/// The library scan of [`scan_content_with_rules`] fed one line at a time, so a file too large
+/// to hold in memory can be streamed through it. Cargo and Go manifests and Go sources are
+/// only scanned whole.
+pub struct LibraryScanner<'a> {
+    path: &'a Path,
+    /// Rust/JS: libraries named by import statements only, so comments, strings and
+    /// look-alike identifiers (`openssl_probe`, `crypto_utils`) don't match
+    import_language: Option<&'static str>,
+    keywords: CryptoKeywords,
+    /// Other languages: each built-in keyword compiled once, with its entry
+    patterns: Vec<(Regex, KeywordEntry)>,
+    rules: &'a [LibraryRule],
+    /// 1-based number of the last line scanned
+    line_number: usize,
+    findings: Vec<Finding>,
+}
+
+impl<'a> LibraryScanner<'a> {
+    /// A scanner for `path`, or `None` for the files only scanned whole
+    pub fn new(path: &'a Path, rules: &'a [LibraryRule]) -> Option<Self> {
+        let import_language = import_language(path);
+        if is_cargo_manifest(path) || is_go_manifest(path) || import_language == Some("Go") {
+            return None;
+        }
+        let mut keywords = merged_keywords(rules);
+        let mut patterns = Vec::new();
+        if import_language.is_none() {
+            // Their bare module names would match any word; those languages are matched by import
+            keywords.retain(|_, (_, _, language, _)| !is_import_language(language));
+            patterns = keywords.iter().map(|(pattern, entry)| (to_safe_regex(pattern), *entry)).collect();
+        }
+        Some(LibraryScanner { path, import_language, keywords, patterns, rules, line_number: 0, findings: Vec::new() })
+    }
+
+    pub fn scan_line(&mut self, line: &str) {
+        self.line_number += 1;
+        let trimmed = line.trim_start();
+        let Some(language) = self.import_language else {
             if trimmed.starts_with('*') {
+                return;
+            }
+            for (regex, (label, source, language, version)) in &self.patterns {
+                if regex.is_match(line) {
+                    self.findings.push(library_finding(self.path, self.line_number, line, source, (label, language, *version)));
+                }
+            }
+            for rule in self.rules.iter().filter(|rule| !is_import_language(&rule.language) && rule.regex.is_match(line)) {
+                self.findings.push(library_finding(self.path, self.line_number, line, &rule.source, rule.entry()));
+            }
+            return;
+        };
+
+        if trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.starts_with('*') {
+            return;
+        }
+        let Some((module, kind)) = imported_module(language, line) else {
+            return;
+        };
+        for entry in module_entries(&module, language, &self.keywords, self.rules) {
+            self.findings.push(library_finding(self.path, self.line_number, line, kind, entry));
+        }
+    }
+
+    /// The findings, once every line has been scanned
+    pub fn finish(self) -> Vec<Finding> {
+        self.findings
+    }
+}
+
+/// Cargo.toml: Rust crypto crates declared in dependency tables
+fn scan_cargo_manifest(path: &Path, content: &str, keywords: &CryptoKeywords, rules: &[LibraryRule]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut in_dependencies = false;
+
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let name = if trimmed.starts_with('[') {
+            // `[dependencies.openssl]` names the crate in the header itself
+            let table = CARGO_DEPENDENCY_TABLE.captures(line);
+            in_dependencies = table.as_ref().is_some_and(|caps| caps.get(1).is_none());
+            table.and_then(|caps| caps.get(1)).map(|m| m.as_str().to_string())
+        } else if in_dependencies {
+            CARGO_DEPENDENCY.captures(line).map(|caps| caps[1].to_string())
+        } else {
+            None
+        };
+
+        let Some(name) = name else { continue };
+        for entry in module_entries(&name, "Rust", keywords, rules) {
+            findings.push(library_finding(path, i + 1, line, "dependency", entry));
+        }
+    }
+
+    findings
+}
+
+/// A library match as (label, language, version)
+type LibraryEntry<'a> = (&'a str, &'a str, Option<&'a str>);
+
+/// Whether `path` is the Go module or package `prefix` or a package below it
+fn is_go_subpath(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix).is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Standard library packages have no dot in their first path element
+fn is_go_stdlib(path: &str) -> bool {
+    !path.split('/').next().unwrap_or_default().contains('.')
+}
+
+/// Built-in entries and rules of Go naming this import path or module, as (source, (label, language, version)).
+/// Built-in entries are module prefixes and report the path itself, e.g. `crypto/aes` or `golang.org/x/crypto/bcrypt`.
+fn go_entries<'a>(
+    module: &'a str,
+    version: Option<&'a str>,
+    keywords: &CryptoKeywords,
+    rules: &'a [LibraryRule],
+) -> Vec<(&'a str, LibraryEntry<'a>)> {
+    let builtin = keywords
+        .iter()
+        .any(|(prefix, (_, _, language, _))| *language == "Go" && is_go_subpath(module, prefix.trim_end_matches('/')))
+        .then_some(("import", (module, "Go", version)));
+    let user = rules
+        .iter()
+        .filter(|rule| rule.language == "Go" && rule.regex.is_match(module))
+        .map(|rule| {
+            let (label, language, rule_version) = rule.entry();
+            (rule.source.as_str(), (label, language, rule_version.or(version)))
+        });
+    builtin.into_iter().chain(user).collect()
+}
+
+/// Drop `//` and `/* */` comments outside string literals; `in_comment` carries a block comment across lines
+fn strip_go_comments(line: &str, in_comment: &mut bool) -> String {
+    let mut code = String::new();
+    let mut quote = None;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if *in_comment {
+            if c == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                *in_comment = false;
+            }
+            continue;
+        }
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '`' => quote = Some(c),
+            None if c == '/' && chars.peek() == Some(&'/') => break,
+            None if c == '/' && chars.peek() == Some(&'*') => {
+                chars.next();
+                *in_comment = true;
                 continue;
             }
+            None => {}
+        }
+        code.push(c);
+    }
+    code
+}
+
+/// The `(line index, import path)` of every spec in `import "x"`, `import alias "x"`
+/// and grouped `import ( ... )` declarations
+fn go_imports(content: &str) -> Vec<(usize, String)> {
+    let mut imports = Vec::new();
+    let mut in_block = false;
+    let mut in_comment = false;
 
-            for (pattern, (label, source, language, version)) in &keywords {
-                let re = to_safe_regex(pattern);
-                if re.is_match(line) {
-                    findings.push(Finding {
-                        file: path.display().to_string(),
-                        line_number: i + 1,
-                        line_content: line.to_string(),
-                        match_type: source.to_string(),
-                        keyword: label.to_string(),
-                        context: source.to_string(),
-                        version: version.map(|v| v.to_string()),
-                        language: language.to_string(),
-                        source: source.to_string(),
-                        category: "library".to_string(), // ✅ new field populated
-                    });
+    for (i, line) in content.lines().enumerate() {
+        let code = strip_go_comments(line, &mut in_comment);
+        let mut specs = code.trim();
+        if !in_block {
+            // `import` followed by a spec or `(`, not an identifier such as `imported`
+            let Some(rest) = specs.strip_prefix("import") else { continue };
+            if !rest.starts_with(|c: char| c.is_whitespace() || matches!(c, '(' | '"' | '`')) {
+                continue;
+            }
+            specs = rest.trim_start();
+            if let Some(block) = specs.strip_prefix('(') {
+                in_block = true;
+                specs = block;
+            }
+        }
+        if in_block {
+            if let Some(end) = specs.find(')') {
+                specs = &specs[..end];
+                in_block = false;
+            }
+        }
+        for caps in GO_IMPORT_PATH.captures_iter(specs) {
+            imports.push((i, caps[1].to_string()));
+        }
+    }
+
+    imports
+}
+
+/// The `(line index, module, version)` requirements of a go.mod, `// indirect` ones included
+fn go_requirements(content: &str) -> Vec<(usize, String, String)> {
+    let mut requirements = Vec::new();
+    let mut in_require = false;
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_require {
+            if line.starts_with(')') {
+                in_require = false;
+                continue;
+            }
+        } else if let Some(rest) = line.strip_prefix("require") {
+            if rest.trim_start().starts_with('(') {
+                in_require = true;
+                continue;
+            }
+        } else {
+            continue;
+        }
+        if let Some(caps) = GO_REQUIREMENT.captures(line) {
+            requirements.push((i, caps[1].to_string(), caps[2].to_string()));
+        }
+    }
+
+    requirements
+}
+
+/// Versions from the go.mod, and the go.sum beside it, governing a Go source file
+#[derive(Debug, Default)]
+struct GoModule {
+    /// The `go` directive, reported for standard library packages
+    go_version: Option<String>,
+    /// Module paths and versions, go.mod requirements before go.sum entries
+    requirements: Vec<(String, String)>,
+}
+
+impl GoModule {
+    /// The nearest go.mod in the directories above `path`
+    fn find(path: &Path) -> Option<Self> {
+        let dir = path.ancestors().skip(1).find(|dir| dir.join("go.mod").is_file())?;
+        let mut module = Self::parse(&fs::read_to_string(dir.join("go.mod")).ok()?);
+        if let Ok(sum) = fs::read_to_string(dir.join("go.sum")) {
+            // `module version[/go.mod] hash`
+            for line in sum.lines() {
+                let mut parts = line.split_whitespace();
+                if let (Some(name), Some(version)) = (parts.next(), parts.next()) {
+                    module.requirements.push((name.to_string(), version.trim_end_matches("/go.mod").to_string()));
                 }
             }
         }
+        Some(module)
+    }
+
+    fn parse(go_mod: &str) -> Self {
+        let go_version = go_mod
+            .lines()
+            .find_map(|line| line.split("//").next().unwrap_or_default().trim().strip_prefix("go "))
+            .map(|version| version.trim().to_string());
+        let requirements = go_requirements(go_mod).into_iter().map(|(_, name, version)| (name, version)).collect();
+        GoModule { go_version, requirements }
+    }
+
+    /// The Go release for standard library packages, otherwise the version of the longest module containing the package
+    fn version_of(&self, import_path: &str) -> Option<&str> {
+        if is_go_stdlib(import_path) {
+            return self.go_version.as_deref();
+        }
+        self.requirements
+            .iter()
+            .filter(|(name, _)| is_go_subpath(import_path, name))
+            .min_by_key(|(name, _)| Reverse(name.len()))
+            .map(|(_, version)| version.as_str())
+    }
+}
+
+/// Go: packages named by import declarations, with versions from the nearest go.mod/go.sum
+fn scan_go_imports(path: &Path, content: &str, keywords: &CryptoKeywords, rules: &[LibraryRule]) -> Vec<Finding> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut findings = Vec::new();
+    // Only looked up once a crypto import is found
+    let mut module: Option<Option<GoModule>> = None;
+
+    for (i, import_path) in go_imports(content) {
+        if go_entries(&import_path, None, keywords, rules).is_empty() {
+            continue;
+        }
+        let module = module.get_or_insert_with(|| GoModule::find(path));
+        let version = module.as_ref().and_then(|module| module.version_of(&import_path));
+        for (kind, entry) in go_entries(&import_path, version, keywords, rules) {
+            findings.push(library_finding(path, i + 1, lines[i], kind, entry));
+        }
     }
 
     findings
 }
+
+/// go.mod: Go crypto modules in `require` directives, with the required version
+fn scan_go_manifest(path: &Path, content: &str, keywords: &CryptoKeywords, rules: &[LibraryRule]) -> Vec<Finding> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut findings = Vec::new();
+
+    for (i, name, version) in go_requirements(content) {
+        for (_, entry) in go_entries(&name, Some(&version), keywords, rules) {
+            findings.push(library_finding(path, i + 1, lines[i], "dependency", entry));
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keywords(file: &str, content: &str) -> Vec<String> {
+        scan_content(Path::new(file), content).into_iter().map(|f| f.keyword).collect()
+    }
+
+    #[test]
+    fn test_rust_imports_only() {
+        let content = r#"
+use openssl::ssl::SslContext;
+pub(crate) use ring::digest;
+extern crate rustls;
+use openssl_probe::init_ssl_cert_env_vars;
+// use secrecy::Secret;
+let msg = "openssl is not linked";
+let ring = 3;
+"#;
+        assert_eq!(keywords("lib.rs", content), vec!["openssl", "ring", "rustls"]);
+    }
+
+    #[test]
+    fn test_js_imports_only() {
+        let content = r#"
+const crypto = require('crypto');
+import jwt from "jsonwebtoken";
+import { pki } from 'node-forge/lib/pki';
+const { scrypt } = await import("node:crypto");
+const crypto_utils = require('./crypto_utils');
+// require('bcrypt')
+console.log("uses crypto");
+"#;
+        assert_eq!(keywords("app.js", content), vec!["crypto", "jsonwebtoken", "node-forge", "crypto"]);
+    }
+
+    #[test]
+    fn test_every_builtin_library_has_algorithms() {
+        for (label, ..) in get_crypto_keywords().values() {
+            assert!(library_algorithms(label).is_some(), "{} has no LIBRARY_ALGORITHMS entry", label);
+        }
+        assert_eq!(library_algorithms("LibSodium").map(|a| a[0]), Some("XSalsa20-Poly1305"));
+    }
+
+    #[test]
+    fn test_cargo_manifest_dependencies() {
+        let content = r#"
+[package]
+name = "ring"
+
+[dependencies]
+openssl = "0.10"
+serde = "1"
+
+[target.'cfg(unix)'.dev-dependencies]
+rustls.workspace = true
+
+[dependencies.ring]
+version = "0.17"
+"#;
+        let findings = scan_content(Path::new("Cargo.toml"), content);
+        let found: Vec<(&str, &str)> = findings.iter().map(|f| (f.keyword.as_str(), f.match_type.as_str())).collect();
+        assert_eq!(found, vec![("openssl", "dependency"), ("rustls", "dependency"), ("ring", "dependency")]);
+    }
+
+    #[test]
+    fn test_go_single_line_imports() {
+        let content = r#"
+package main
+
+import "crypto/sha256"
+import tls "crypto/tls"
+import _ "golang.org/x/crypto/bcrypt" // registers nothing
+// import "crypto/md5"
+import "fmt"
+
+var imported = "crypto/rand"
+"#;
+        assert_eq!(keywords("main.go", content), vec!["crypto/sha256", "crypto/tls", "golang.org/x/crypto/bcrypt"]);
+    }
+
+    #[test]
+    fn test_go_grouped_imports() {
+        let content = r#"
+package main
+
+import (
+    "crypto/aes"
+    "fmt" /* "crypto/des" */
+    gcm "crypto/cipher"
+    . "github.com/cloudflare/circl/kem/kyber/kyber768"
+    "cryptography.example.com/notcrypto"
+)
+
+import ("crypto/rand"; "os")
+
+func main() { fmt.Println("crypto/x509") }
+"#;
+        assert_eq!(
+            keywords("main.go", content),
+            vec!["crypto/aes", "crypto/cipher", "github.com/cloudflare/circl/kem/kyber/kyber768", "crypto/rand"]
+        );
+    }
+
+    #[test]
+    fn test_go_versions_from_go_mod_and_go_sum() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("go.mod"), "module example.com/app\n\ngo 1.22\n\nrequire (\n\tgolang.org/x/crypto v0.21.0\n\tgithub.com/google/uuid v1.6.0 // indirect\n)\n").unwrap();
+        std::fs::write(dir.path().join("go.sum"), "github.com/cloudflare/circl v1.3.7 h1:abc=\ngithub.com/cloudflare/circl v1.3.7/go.mod h1:def=\n").unwrap();
+        let source = dir.path().join("cmd/app/main.go");
+
+        let content = "import (\n\t\"crypto/ed25519\"\n\t\"golang.org/x/crypto/argon2\"\n\t\"github.com/cloudflare/circl/sign/ed448\"\n)\n";
+        let findings = scan_content(&source, content);
+        let found: Vec<(&str, Option<&str>, usize)> =
+            findings.iter().map(|f| (f.keyword.as_str(), f.version.as_deref(), f.line_number)).collect();
+        assert_eq!(found, vec![
+            ("crypto/ed25519", Some("1.22"), 2),
+            ("golang.org/x/crypto/argon2", Some("v0.21.0"), 3),
+            ("github.com/cloudflare/circl/sign/ed448", Some("v1.3.7"), 4),
+        ]);
+        assert!(findings.iter().all(|f| f.language == "Go" && f.source == "import"));
+    }
+
+    #[test]
+    fn test_go_mod_requirements() {
+        let content = "module example.com/app\n\ngo 1.21\n\nrequire golang.org/x/crypto v0.17.0\n\nrequire (\n\tgithub.com/golang-jwt/jwt/v5 v5.2.0\n\tgithub.com/spf13/cobra v1.8.0\n)\n";
+        let findings = scan_content(Path::new("go.mod"), content);
+        let found: Vec<(&str, &str, Option<&str>)> =
+            findings.iter().map(|f| (f.keyword.as_str(), f.match_type.as_str(), f.version.as_deref())).collect();
+        assert_eq!(found, vec![
+            ("golang.org/x/crypto", "dependency", Some("v0.17.0")),
+            ("github.com/golang-jwt/jwt/v5", "dependency", Some("v5.2.0")),
+        ]);
+    }
+
+    fn write_rules(name: &str, content: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_library_rules_merge_and_override() {
+        let (_dir, path) = write_rules("rules.toml", r#"
+[[library]]
+pattern = "lt_crypto(\\.\\w+)*"
+label = "lt-crypto"
+language = "Python"
+source = "import"
+version = "2.1"
+
+[[library]]
+pattern = "openssl"
+label = "openssl"
+language = "Rust"
+source = "use"
+version = "0.10.64"
+
+[[library]]
+pattern = "@lt/(crypto|vault)"
+label = "lt-js-crypto"
+language = "JavaScript"
+source = "import"
+"#);
+        let rules = load_library_rules(&path, RegexLimits::default()).unwrap();
+        assert_eq!(rules.len(), 3);
+
+        let findings = scan_content_with_rules(Path::new("app.py"), "from lt_crypto.aead import seal\nimport hashlib\n", &rules);
+        let found: Vec<(&str, Option<&str>)> = findings.iter().map(|f| (f.keyword.as_str(), f.version.as_deref())).collect();
+        assert!(found.contains(&("lt-crypto", Some("2.1"))));
+        assert!(found.contains(&("hashlib", None)));
+
+        // The user entry replaces the built-in `openssl` one rather than adding a second finding
+        let findings = scan_content_with_rules(Path::new("lib.rs"), "use openssl::ssl;\nuse openssl_probe::init;\n", &rules);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].version.as_deref(), Some("0.10.64"));
+
+        let findings = scan_content_with_rules(Path::new("app.js"), "import { seal } from '@lt/vault/aead';\n", &rules);
+        assert_eq!(findings[0].keyword, "lt-js-crypto");
+    }
+
+    #[test]
+    fn test_library_rules_yaml() {
+        let (_dir, path) = write_rules("rules.yaml", "library:\n  - pattern: 'import ltcrypto'\n    label: ltcrypto\n    language: Go\n    source: import\n");
+        let rules = load_library_rules(&path, RegexLimits::default()).unwrap();
+        assert_eq!(rules[0].label, "ltcrypto");
+        assert_eq!(rules[0].version, None);
+    }
+
+    #[test]
+    fn test_invalid_library_rule_names_entry() {
+        let (_dir, path) = write_rules("rules.toml", r#"
+[[library]]
+pattern = "fine"
+label = "fine"
+language = "Go"
+source = "import"
+
+[[library]]
+pattern = "lt_crypto("
+label = "broken-wrapper"
+language = "Python"
+source = "import"
+"#);
+        let err = load_library_rules(&path, RegexLimits::default()).unwrap_err().to_string();
+        assert!(err.contains("broken-wrapper (entry 2 in"), "{}", err);
+
+        let (_dir, path) = write_rules("rules.toml", "[[library]]\npattern = \"x\"\nlabel = \"x\"\n");
+        let err = load_library_rules(&path, RegexLimits::default()).unwrap_err().to_string();
+        assert!(err.contains("Invalid rule file") && err.contains("language"), "{}", err);
+    }
+
+    #[test]
+    fn test_other_languages_skip_rust_and_js_names() {
+        assert_eq!(keywords("tool.py", "import hashlib\nring = 'crypto'\n"), vec!["hashlib"]);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::scanner::secrets::{is_comment_line, CWE_HARDCODED_CREDENTIALS, SECRETS_MANAGEMENT_GUIDE};
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::lang_ident::detect_language;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+lazy_static! {
+    /// Environment variable names that hold key material or credentials
+    static ref SECRET_VAR_NAME: Regex = Regex::new(
+        r"(?i)(secret|key|token|passw|pwd|credential|salt|private|auth|signing)"
+    ).unwrap();
+
+    /// Environment lookups with a literal fallback: (pattern, lookup description).
+    /// Group 1 is the variable name, group 2 the default value.
+    static ref ENV_DEFAULT_PATTERNS: Vec<(Regex, &'static str)> = vec![
+        // os.environ.get("KEY", "x"), os.getenv("KEY", "x"), os.getenv("KEY", default="x")
+        (
+            Regex::new(r#"os\.(?:environ\.get|getenv)\(\s*['"](\w+)['"]\s*,\s*(?:default\s*=\s*)?['"]([^'"]+)['"]"#).unwrap(),
+            "os.environ.get",
+        ),
+        // std::env::var("KEY").unwrap_or("x".to_string()), .unwrap_or_else(|_| "x".into())
+        (
+            Regex::new(r#"env::var\(\s*"(\w+)"\s*\)\s*\.unwrap_or(?:_else)?\(\s*(?:\|_\|\s*)?"([^"]+)""#).unwrap(),
+            "std::env::var",
+        ),
+        // process.env.KEY || "x", process.env["KEY"] ?? "x"
+        (
+            Regex::new(r#"process\.env(?:\.|\[\s*['"])(\w+)(?:['"]\s*\])?\s*(?:\|\||\?\?)\s*['"`]([^'"`]+)['"`]"#).unwrap(),
+            "process.env",
+        ),
+        // System.getenv().getOrDefault("KEY", "x")
+        (
+            Regex::new(r#"getenv\(\s*\)\s*\.getOrDefault\(\s*"(\w+)"\s*,\s*"([^"]+)""#).unwrap(),
+            "System.getenv().getOrDefault",
+        ),
+    ];
+}
+
+/// Scans a source file for secret-named environment lookups with a hardcoded fallback
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded file content for insecure environment variable defaults
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let language = detect_language(path);
+
+    for (line_num, line) in content.lines().enumerate() {
+        if is_comment_line(line) {
+            continue;
+        }
+
+        for (regex, lookup) in ENV_DEFAULT_PATTERNS.iter() {
+            for caps in regex.captures_iter(line) {
+                let name = &caps[1];
+                if !SECRET_VAR_NAME.is_match(name) || caps[2].trim().is_empty() {
+                    continue;
+                }
+
+                findings.push(Finding {
+                    file: path.display().to_string(),
+                    line_number: line_num + 1,
+                    end_line_number: None,
+                    column_start: None,
+                    column_end: None,
+                    line_content: line.to_string(),
+                    match_type: "secret".to_string(),
+                    keyword: name.to_string(),
+                    context: format!(
+                        "{} falls back to a hardcoded literal when {} is unset; the default is effectively a hardcoded secret",
+                        lookup, name
+                    ),
+                    version: None,
+                    language: language.clone(),
+                    source: "hardcoded".to_string(),
+                    category: "insecure-default-secret".to_string(),
+                    severity: Some(Severity::High),
+                    file_hash: None,
+                    cwe: Some(CWE_HARDCODED_CREDENTIALS.to_string()),
+                    remediation: Some(SECRETS_MANAGEMENT_GUIDE.to_string()),
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(file: &str, content: &str) -> Vec<Finding> {
+        scan_content(Path::new(file), content)
+    }
+
+    #[test]
+    fn test_python_environ_default() {
+        let findings = scan("app.py", r#"
+key = os.environ.get("SECRET_KEY", "defaultsecret")
+token = os.getenv("API_TOKEN", default="dev-token")
+debug = os.environ.get("DEBUG", "false")
+"#);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].keyword, "SECRET_KEY");
+        assert_eq!(findings[0].line_number, 2);
+        assert_eq!(findings[0].category, "insecure-default-secret");
+        assert_eq!(findings[0].severity, Some(Severity::High));
+        assert_eq!(findings[0].cwe.as_deref(), Some(CWE_HARDCODED_CREDENTIALS));
+        assert_eq!(findings[1].keyword, "API_TOKEN");
+    }
+
+    #[test]
+    fn test_rust_env_var_unwrap_or() {
+        let findings = scan("main.rs", r#"
+let key = std::env::var("JWT_SECRET").unwrap_or("changeme".to_string());
+let pw = env::var("DB_PASSWORD").unwrap_or_else(|_| "postgres".into());
+let port = env::var("PORT").unwrap_or("8080".to_string());
+"#);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].keyword, "JWT_SECRET");
+        assert_eq!(findings[1].keyword, "DB_PASSWORD");
+    }
+
+    #[test]
+    fn test_javascript_process_env_fallback() {
+        let findings = scan("server.js", r#"
+const secret = process.env.SESSION_SECRET || 'keyboard cat';
+const key = process.env["ENCRYPTION_KEY"] ?? "0123456789abcdef";
+const host = process.env.HOST || "localhost";
+"#);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].keyword, "SESSION_SECRET");
+        assert_eq!(findings[1].keyword, "ENCRYPTION_KEY");
+    }
+
+    #[test]
+    fn test_java_get_or_default() {
+        let findings = scan("Config.java", r#"
+String key = System.getenv().getOrDefault("SIGNING_KEY", "s3cr3t");
+String region = System.getenv().getOrDefault("AWS_REGION", "eu-west-1");
+"#);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].keyword, "SIGNING_KEY");
+    }
+
+    #[test]
+    fn test_lookups_without_default_not_flagged() {
+        let findings = scan("app.py", r#"
+key = os.environ["SECRET_KEY"]
+token = os.getenv("API_TOKEN")
+empty = os.environ.get("SECRET_KEY", "")
+# key = os.environ.get("SECRET_KEY", "old-default")
+"#);
+        assert!(findings.is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+//! `--since <rev>`: scan only the files changed between a git revision and `HEAD`.
+//!
+//! The changed files come from `git diff --name-only <rev>..HEAD`, limited to the scan
+//! path; deleted files are left out. Uncommitted changes are not included, so a PR check
+//! compares the PR's commits with its base.
+
+use crate::error::{config_error, scanner_error, ScanResult};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Run `git` in `dir`, returning its stdout, or its stderr as the error
+fn git(dir: &Path, args: &[&str]) -> ScanResult<Result<Vec<u8>, String>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| scanner_error(&format!("Cannot run git: {}", e)))?;
+    Ok(if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    })
+}
+
+/// Files under `root` changed between `rev` and `HEAD`, as paths under `root`
+pub fn changed_files(root: &Path, rev: &str) -> ScanResult<Vec<PathBuf>> {
+    // Would be read by `git diff` as an option (`--output=<file>` writes a file), not a revision
+    if rev.starts_with('-') {
+        return Err(config_error(&format!("Invalid --since revision '{}': revisions cannot start with '-'", rev)));
+    }
+    if git(root, &["rev-parse", "--is-inside-work-tree"])?.is_err() {
+        return Err(config_error(&format!("--since needs a git repository, but {} is not inside one", root.display())));
+    }
+    let range = format!("{}..HEAD", rev);
+    let names = git(root, &["diff", "--name-only", "-z", "--relative", "--diff-filter=d", &range, "--"])?
+        .map_err(|e| config_error(&format!("Cannot list files changed since {}: {}", rev, e)))?;
+
+    Ok(names
+        .split(|byte| *byte == 0)
+        .filter(|name| !name.is_empty())
+        .map(|name| root.join(String::from_utf8_lossy(name).as_ref()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::process::Stdio;
+
+    fn run(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["-c", "user.name=Dev One", "-c", "user.email=dev@example.org", "-c", "commit.gpgsign=false"])
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn test_changed_files_since_rev() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path();
+        fs::create_dir_all(repo.join("src")).unwrap();
+        fs::write(repo.join("src/old.py"), "import ssl\n").unwrap();
+        fs::write(repo.join("src/gone.py"), "import ssl\n").unwrap();
+        fs::write(repo.join("README.md"), "docs\n").unwrap();
+        run(repo, &["init", "-q"]);
+        run(repo, &["add", "."]);
+        run(repo, &["commit", "-q", "-m", "base"]);
+        run(repo, &["tag", "base"]);
+
+        fs::write(repo.join("src/new.py"), "import hashlib\n").unwrap();
+        fs::write(repo.join("README.md"), "more docs\n").unwrap();
+        fs::remove_file(repo.join("src/gone.py")).unwrap();
+        run(repo, &["add", "-A"]);
+        run(repo, &["commit", "-q", "-m", "change"]);
+
+        // Limited to the scan path, without the deleted file
+        let src = repo.join("src");
+        assert_eq!(changed_files(&src, "base").unwrap(), vec![src.join("new.py")]);
+        let mut all = changed_files(repo, "base").unwrap();
+        all.sort();
+        assert_eq!(all, vec![repo.join("README.md"), repo.join("src/new.py")]);
+
+        let err = changed_files(repo, "no-such-rev").unwrap_err().to_string();
+        assert!(err.contains("Cannot list files changed since no-such-rev"), "{}", err);
+    }
+
+    #[test]
+    fn test_since_outside_repository() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let err = changed_files(dir.path(), "main").unwrap_err().to_string();
+        assert!(err.contains("is not inside one"), "{}", err);
+    }
+
+    #[test]
+    fn test_since_option_like_revision_rejected() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = dir.path();
+        run(repo, &["init", "-q"]);
+        fs::write(repo.join("a.py"), "import ssl\n").unwrap();
+        run(repo, &["add", "."]);
+        run(repo, &["commit", "-q", "-m", "base"]);
+
+        let out = repo.join("written.txt");
+        let err = changed_files(repo, &format!("--output={}", out.display())).unwrap_err().to_string();
+        assert!(err.contains("cannot start with '-'"), "{}", err);
+        assert!(!out.exists());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::scanner::secrets::is_template_reference;
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+/// Extensions of standalone Go template files
+const GO_TEMPLATE_EXTENSIONS: &[&str] = &["tpl", "gotmpl", "tmpl"];
+
+lazy_static! {
+    /// A `{{ ... }}` action; group 1 holds its body without trim markers
+    static ref TEMPLATE_ACTION: Regex = Regex::new(r"\{\{-?\s*(.*?)\s*-?\}\}").unwrap();
+
+    /// Names that mark a value as secret
+    static ref SECRET_NAME: Regex = Regex::new(
+        r"(?i)(password|passwd|secret|token|api_?key|apikey|private_?key|credential|auth)"
+    ).unwrap();
+
+    /// String literals used as values: `default "x"`, `"x" | b64enc`, `b64enc "x"`
+    static ref LITERAL_VALUE: Regex = Regex::new(
+        r#"(?:\bdefault\s+"([^"]+)"|"([^"]+)"\s*\|\s*b64enc|\bb64enc\s+"([^"]+)")"#
+    ).unwrap();
+
+    /// Chart files pulled into the rendered output
+    static ref FILES_GET: Regex = Regex::new(r#"\.Files\.(?:Get|Glob)\s+"([^"]+)""#).unwrap();
+
+    /// File names that hold key or certificate material
+    static ref KEY_MATERIAL: Regex = Regex::new(r"(?i)(\.key|\.pem|\.p12|\.pfx|\.jks|\.crt|id_rsa|id_ed25519|secret)").unwrap();
+
+    /// `.Values` paths that name a secret
+    static ref VALUES_REFERENCE: Regex = Regex::new(r"\.Values(?:\.[A-Za-z0-9_]+)+").unwrap();
+}
+
+/// Check whether a file is a Helm chart template or another Go template
+pub fn is_helm_template(path: &Path) -> bool {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if GO_TEMPLATE_EXTENSIONS.contains(&ext.as_str()) {
+        return true;
+    }
+
+    let in_templates_dir = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|d| d.to_str())
+        .is_some_and(|d| d == "templates");
+    in_templates_dir && matches!(ext.as_str(), "yaml" | "yml")
+}
+
+/// Scans a template file for secret references, hardcoded literals and key material
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded template content. Template actions are recognized, never evaluated.
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let language = if path.extension().is_some_and(|e| e == "yaml" || e == "yml") {
+        "Helm Template"
+    } else {
+        "Go Template"
+    };
+    let mut in_comment = false;
+
+    for (line_num, line) in content.lines().enumerate() {
+        // Multi-line `{{/* ... */}}` comments
+        if in_comment {
+            in_comment = !line.contains("*/");
+            continue;
+        }
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        let key = line.split(':').next().unwrap_or_default();
+        let mut push = |keyword: &str, context: String, category: &str, severity: Severity| {
+            findings.push(Finding {
+                file: path.display().to_string(),
+                line_number: line_num + 1,
+                end_line_number: None,
+                column_start: None,
+                column_end: None,
+                line_content: line.to_string(),
+                match_type: "template".to_string(),
+                keyword: keyword.to_string(),
+                context,
+                version: None,
+                language: language.to_string(),
+                source: "helm-template".to_string(),
+                category: category.to_string(),
+                severity: Some(severity),
+                file_hash: None,
+                cwe: None,
+                remediation: None,
+            });
+        };
+
+        for caps in TEMPLATE_ACTION.captures_iter(line) {
+            let action = caps.get(1).map_or("", |m| m.as_str());
+            if action.starts_with("/*") {
+                continue;
+            }
+
+            if let Some(file) = FILES_GET.captures(action).and_then(|c| c.get(1)) {
+                if KEY_MATERIAL.is_match(file.as_str()) {
+                    push(
+                        "Key Material Reference",
+                        format!("Template embeds chart file {}; key material packaged in the chart ships with every release", file.as_str()),
+                        "keystore",
+                        Severity::Medium,
+                    );
+                }
+                continue;
+            }
+
+            let is_secret = SECRET_NAME.is_match(action) || SECRET_NAME.is_match(key);
+            if !is_secret {
+                continue;
+            }
+
+            let literal = LITERAL_VALUE
+                .captures(action)
+                .and_then(|c| c.get(1).or_else(|| c.get(2)).or_else(|| c.get(3)))
+                .map(|m| m.as_str())
+                .filter(|value| !is_template_reference(value));
+            if let Some(value) = literal {
+                push(
+                    "Hardcoded Template Secret",
+                    format!("Literal {:?} used as a secret value inside a template expression; inject it through values or an external secret store", value),
+                    "secret",
+                    Severity::High,
+                );
+            } else if let Some(reference) = VALUES_REFERENCE.find(action) {
+                push(
+                    "Secret Reference",
+                    format!("Secret supplied through {}", reference.as_str()),
+                    "secret-reference",
+                    Severity::Info,
+                );
+            }
+        }
+
+        // A comment opened on this line that doesn't close on it
+        if let Some(start) = line.find("{{/*").or_else(|| line.find("{{- /*")) {
+            in_comment = !line[start..].contains("*/");
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_helm_template_recognition() {
+        assert!(is_helm_template(&PathBuf::from("chart/templates/secret.yaml")));
+        assert!(is_helm_template(&PathBuf::from("chart/templates/_helpers.tpl")));
+        assert!(is_helm_template(&PathBuf::from("deploy/config.gotmpl")));
+        assert!(!is_helm_template(&PathBuf::from("chart/values.yaml")));
+    }
+
+    #[test]
+    fn test_references_literals_and_key_material() {
+        let content = r#"apiVersion: v1
+kind: Secret
+data:
+  password: {{ .Values.db.password | b64enc | quote }}
+  api-token: {{ .Values.apiToken | default "s3cr3t-t0ken" | b64enc }}
+  tls.key: {{ .Files.Get "certs/tls.key" | b64enc }}
+  replicas: {{ .Values.replicas | default "3" }}
+"#;
+        let findings = scan_content(Path::new("chart/templates/secret.yaml"), content);
+        let summary: Vec<(usize, &str, Option<Severity>)> = findings
+            .iter()
+            .map(|f| (f.line_number, f.keyword.as_str(), f.severity))
+            .collect();
+
+        assert_eq!(summary, vec![
+            (4, "Secret Reference", Some(Severity::Info)),
+            (5, "Hardcoded Template Secret", Some(Severity::High)),
+            (6, "Key Material Reference", Some(Severity::Medium)),
+        ]);
+        assert!(findings.iter().all(|f| f.source == "helm-template"));
+        assert_eq!(findings[0].language, "Helm Template");
+    }
+
+    #[test]
+    fn test_template_comments_skipped() {
+        let content = r#"{{/* password: {{ .Values.password | default "hunter2-default" }} */}}
+{{- /*
+  token: {{ default "old-token-value" .Values.token }}
+*/ -}}
+{{ define "chart.secret" }}{{ default "real-default-pw" .Values.password }}{{ end }}
+"#;
+        let findings = scan_content(Path::new("templates/_helpers.tpl"), content);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line_number, 5);
+        assert_eq!(findings[0].language, "Go Template");
+    }
+}
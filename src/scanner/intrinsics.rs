@@ -0,0 +1,252 @@
+use crate::scanner::secrets::is_comment_line;
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::lang_ident::detect_language;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+/// Crypto intrinsics and the algorithm and instruction set they implement
+const INTRINSICS: &[(&str, &str, &str)] = &[
+    // x86 AES-NI and VAES
+    ("_mm_aesenc_si128", "AES", "AES-NI"),
+    ("_mm_aesenclast_si128", "AES", "AES-NI"),
+    ("_mm_aesdec_si128", "AES", "AES-NI"),
+    ("_mm_aesdeclast_si128", "AES", "AES-NI"),
+    ("_mm_aesimc_si128", "AES", "AES-NI"),
+    ("_mm_aeskeygenassist_si128", "AES", "AES-NI"),
+    ("_mm256_aesenc_epi128", "AES", "VAES"),
+    ("_mm256_aesenclast_epi128", "AES", "VAES"),
+    ("_mm256_aesdec_epi128", "AES", "VAES"),
+    ("_mm256_aesdeclast_epi128", "AES", "VAES"),
+    ("_mm512_aesenc_epi128", "AES", "VAES"),
+    ("_mm512_aesenclast_epi128", "AES", "VAES"),
+    ("_mm512_aesdec_epi128", "AES", "VAES"),
+    ("_mm512_aesdeclast_epi128", "AES", "VAES"),
+    // x86 SHA extensions
+    ("_mm_sha1rnds4_epu32", "SHA-1", "x86 SHA"),
+    ("_mm_sha1nexte_epu32", "SHA-1", "x86 SHA"),
+    ("_mm_sha1msg1_epu32", "SHA-1", "x86 SHA"),
+    ("_mm_sha1msg2_epu32", "SHA-1", "x86 SHA"),
+    ("_mm_sha256rnds2_epu32", "SHA-256", "x86 SHA"),
+    ("_mm_sha256msg1_epu32", "SHA-256", "x86 SHA"),
+    ("_mm_sha256msg2_epu32", "SHA-256", "x86 SHA"),
+    ("_mm256_sha512rnds2_epi64", "SHA-512", "x86 SHA512"),
+    ("_mm256_sha512msg1_epi64", "SHA-512", "x86 SHA512"),
+    ("_mm256_sha512msg2_epi64", "SHA-512", "x86 SHA512"),
+    // ARMv8 Crypto Extensions
+    ("vaeseq_u8", "AES", "ARMv8 Crypto"),
+    ("vaesdq_u8", "AES", "ARMv8 Crypto"),
+    ("vaesmcq_u8", "AES", "ARMv8 Crypto"),
+    ("vaesimcq_u8", "AES", "ARMv8 Crypto"),
+    ("vsha1cq_u32", "SHA-1", "ARMv8 Crypto"),
+    ("vsha1mq_u32", "SHA-1", "ARMv8 Crypto"),
+    ("vsha1pq_u32", "SHA-1", "ARMv8 Crypto"),
+    ("vsha1h_u32", "SHA-1", "ARMv8 Crypto"),
+    ("vsha1su0q_u32", "SHA-1", "ARMv8 Crypto"),
+    ("vsha1su1q_u32", "SHA-1", "ARMv8 Crypto"),
+    ("vsha256hq_u32", "SHA-256", "ARMv8 Crypto"),
+    ("vsha256h2q_u32", "SHA-256", "ARMv8 Crypto"),
+    ("vsha256su0q_u32", "SHA-256", "ARMv8 Crypto"),
+    ("vsha256su1q_u32", "SHA-256", "ARMv8 Crypto"),
+    ("vsha512hq_u64", "SHA-512", "ARMv8.2 SHA512"),
+    ("vsha512h2q_u64", "SHA-512", "ARMv8.2 SHA512"),
+    ("vsha512su0q_u64", "SHA-512", "ARMv8.2 SHA512"),
+    ("vsha512su1q_u64", "SHA-512", "ARMv8.2 SHA512"),
+];
+
+/// Crypto instruction mnemonics looked for inside inline assembly
+const MNEMONICS: &[(&str, &str, &str)] = &[
+    ("aesenc", "AES", "AES-NI"),
+    ("aesenclast", "AES", "AES-NI"),
+    ("aesdec", "AES", "AES-NI"),
+    ("aesdeclast", "AES", "AES-NI"),
+    ("aesimc", "AES", "AES-NI"),
+    ("aeskeygenassist", "AES", "AES-NI"),
+    ("vaesenc", "AES", "VAES"),
+    ("vaesenclast", "AES", "VAES"),
+    ("vaesdec", "AES", "VAES"),
+    ("vaesdeclast", "AES", "VAES"),
+    ("aese", "AES", "ARMv8 Crypto"),
+    ("aesd", "AES", "ARMv8 Crypto"),
+    ("aesmc", "AES", "ARMv8 Crypto"),
+    ("sha1rnds4", "SHA-1", "x86 SHA"),
+    ("sha1nexte", "SHA-1", "x86 SHA"),
+    ("sha1msg1", "SHA-1", "x86 SHA"),
+    ("sha1msg2", "SHA-1", "x86 SHA"),
+    ("sha256rnds2", "SHA-256", "x86 SHA"),
+    ("sha256msg1", "SHA-256", "x86 SHA"),
+    ("sha256msg2", "SHA-256", "x86 SHA"),
+    ("sha1c", "SHA-1", "ARMv8 Crypto"),
+    ("sha1m", "SHA-1", "ARMv8 Crypto"),
+    ("sha1p", "SHA-1", "ARMv8 Crypto"),
+    ("sha256h", "SHA-256", "ARMv8 Crypto"),
+    ("sha256h2", "SHA-256", "ARMv8 Crypto"),
+    ("sha256su0", "SHA-256", "ARMv8 Crypto"),
+    ("sha256su1", "SHA-256", "ARMv8 Crypto"),
+    ("sha512h", "SHA-512", "ARMv8.2 SHA512"),
+    ("sha512h2", "SHA-512", "ARMv8.2 SHA512"),
+];
+
+lazy_static! {
+    static ref INTRINSIC_CALL: Regex = Regex::new(&format!(
+        r"\b({})\b",
+        INTRINSICS.iter().map(|(name, _, _)| *name).collect::<Vec<_>>().join("|")
+    ))
+    .unwrap();
+
+    /// A mnemonic at the start of an instruction: after a quote, `;`, `{`, `\n`/`\t` or the line start
+    static ref ASM_INSTRUCTION: Regex = Regex::new(&format!(
+        r#"(?:^|[";{{]|\\n|\\t)\s*({})\b"#,
+        MNEMONICS.iter().map(|(name, _, _)| *name).collect::<Vec<_>>().join("|")
+    ))
+    .unwrap();
+
+    /// Start of an inline assembly block: Rust `asm!`/`global_asm!`, GCC `asm`/`__asm__`, MSVC `__asm`
+    static ref ASM_START: Regex = Regex::new(r"\b(?:(?:global_)?asm!|__asm__|__asm|asm)\s*(?:volatile|__volatile__|goto)?\s*[({]").unwrap();
+}
+
+/// Whether this file's language can call these intrinsics or embed this assembly
+fn is_systems_language(path: &Path) -> bool {
+    matches!(detect_language(path).as_str(), "C" | "C++" | "Rust")
+}
+
+/// Scans a C/C++/Rust source file for hand-optimized crypto intrinsics and inline assembly
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded content for crypto intrinsics and inline assembly
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if !is_systems_language(path) {
+        return findings;
+    }
+    let language = detect_language(path);
+
+    // Paren/brace depth of the inline assembly block being read, if any
+    let mut asm_depth: Option<i32> = None;
+    for (line_num, line) in content.lines().enumerate() {
+        if is_comment_line(line) {
+            continue;
+        }
+
+        let mut matches: Vec<(&str, &str, &str, &str)> = Vec::new();
+        for caps in INTRINSIC_CALL.captures_iter(line) {
+            let name = caps.get(1).map_or("", |m| m.as_str());
+            if let Some((name, algorithm, isa)) = INTRINSICS.iter().find(|(n, _, _)| *n == name) {
+                matches.push((name, algorithm, isa, "intrinsic"));
+            }
+        }
+
+        let asm_start = ASM_START.find(line);
+        if asm_depth.is_none() && asm_start.is_some() {
+            asm_depth = Some(0);
+        }
+        if let Some(depth) = asm_depth {
+            let body = asm_start.map_or(line, |start| &line[start.start()..]);
+            for caps in ASM_INSTRUCTION.captures_iter(body) {
+                let name = caps.get(1).map_or("", |m| m.as_str());
+                if let Some((name, algorithm, isa)) = MNEMONICS.iter().find(|(n, _, _)| *n == name) {
+                    matches.push((name, algorithm, isa, "inline-asm"));
+                }
+            }
+            let opened = body.matches(['(', '{']).count() as i32;
+            let closed = body.matches([')', '}']).count() as i32;
+            let depth = depth + opened - closed;
+            asm_depth = (depth > 0).then_some(depth);
+        }
+
+        matches.sort();
+        matches.dedup();
+        for (name, algorithm, isa, match_type) in matches {
+            let kind = if match_type == "intrinsic" { "intrinsic" } else { "instruction in inline assembly" };
+            findings.push(Finding {
+                file: path.display().to_string(),
+                line_number: line_num + 1,
+                end_line_number: None,
+                column_start: None,
+                column_end: None,
+                line_content: line.to_string(),
+                match_type: match_type.to_string(),
+                keyword: algorithm.to_string(),
+                context: format!("{} via {} {} `{}`", algorithm, isa, kind, name),
+                version: None,
+                language: language.clone(),
+                source: "intrinsic".to_string(),
+                category: "crypto-intrinsic".to_string(),
+                severity: Some(Severity::Info),
+                file_hash: None,
+                cwe: None,
+                remediation: None,
+            });
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(file: &str, content: &str) -> Vec<Finding> {
+        scan_content(Path::new(file), content)
+    }
+
+    #[test]
+    fn test_aesni_intrinsic_is_aes() {
+        let findings = scan("aes.c", r#"
+#include <wmmintrin.h>
+__m128i encrypt_round(__m128i state, __m128i key) {
+    return _mm_aesenc_si128(state, key);
+}
+"#);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].keyword, "AES");
+        assert_eq!(findings[0].category, "crypto-intrinsic");
+        assert_eq!(findings[0].match_type, "intrinsic");
+        assert_eq!(findings[0].line_number, 4);
+        assert!(findings[0].context.contains("_mm_aesenc_si128"));
+    }
+
+    #[test]
+    fn test_arm_and_sha_intrinsics() {
+        let findings = scan("hash.rs", r#"
+let block = vaesmcq_u8(vaeseq_u8(block, key));
+let abcd = _mm_sha256rnds2_epu32(abcd, efgh, msg);
+"#);
+        let algorithms: Vec<&str> = findings.iter().map(|f| f.keyword.as_str()).collect();
+        assert_eq!(algorithms, vec!["AES", "AES", "SHA-256"]);
+    }
+
+    #[test]
+    fn test_inline_assembly() {
+        let findings = scan("aes.rs", r#"
+unsafe {
+    asm!(
+        "aesenc {state}, {key}",
+        "aesenclast {state}, {last}",
+        state = inout(xmm_reg) state,
+    );
+}
+let aesenc = 1;
+"#);
+        let names: Vec<&str> = findings.iter().map(|f| f.context.rsplit('`').nth(1).unwrap()).collect();
+        assert_eq!(names, vec!["aesenc", "aesenclast"]);
+        assert!(findings.iter().all(|f| f.match_type == "inline-asm" && f.keyword == "AES"));
+
+        let findings = scan("sha.c", "__asm__ volatile(\"sha256rnds2 %%xmm0, %1, %0\" : \"+x\"(s) : \"x\"(m));\n");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].keyword, "SHA-256");
+    }
+
+    #[test]
+    fn test_other_languages_and_comments_ignored() {
+        assert!(scan("notes.py", "x = _mm_aesenc_si128(a, b)\n").is_empty());
+        assert!(scan("aes.c", "// _mm_aesenc_si128 is used in the fast path\n").is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+//! Versions of detected libraries, read from the project's dependency files.
+//!
+//! A library finding without a version looks in the directories above its file for
+//! lockfiles (`Cargo.lock`, `package-lock.json`, `yarn.lock`, `poetry.lock`, `Pipfile.lock`,
+//! pinned `requirements.txt`, `conanfile.txt`) and `pom.xml`, then for the ranges declared
+//! in `Cargo.toml` and `package.json`. A locked version anywhere above the file wins over a
+//! nearer manifest range, as workspaces keep a single lockfile at their root.
+
+use crate::scanner::vulnerable_deps::{normalize_package, pinned_versions};
+use crate::utils::report::Finding;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// `(ecosystem, name, version)`; names are normalized as for advisories, Maven ones are `group:artifact`
+type Dependency = (&'static str, String, String);
+
+/// Files with exact versions, read through `vulnerable_deps`
+const LOCKFILES: &[&str] = &[
+    "Cargo.lock", "package-lock.json", "npm-shrinkwrap.json", "yarn.lock",
+    "poetry.lock", "Pipfile.lock", "requirements.txt", "conanfile.txt",
+];
+
+/// Manifests whose versions are only used when no lockfile has the library
+const MANIFESTS: &[&str] = &["Cargo.toml", "package.json"];
+
+/// Python import names whose distribution is called something else
+const PYTHON_DISTRIBUTIONS: &[(&str, &str)] = &[
+    ("jwt", "pyjwt"),
+    ("nacl", "pynacl"),
+    ("OpenSSL", "pyopenssl"),
+    ("Crypto", "pycryptodome"),
+];
+
+/// Java libraries reported by name but published under a Maven group
+const MAVEN_GROUPS: &[(&str, &str)] = &[("bouncycastle", "org.bouncycastle:"), ("bouncycastle-pqc", "org.bouncycastle:")];
+
+lazy_static! {
+    static ref POM_DEPENDENCY: Regex = Regex::new(r"(?s)<dependency>(.*?)</dependency>").unwrap();
+    static ref POM_GROUP: Regex = Regex::new(r"<groupId>\s*([^<\s]+)\s*</groupId>").unwrap();
+    static ref POM_ARTIFACT: Regex = Regex::new(r"<artifactId>\s*([^<\s]+)\s*</artifactId>").unwrap();
+    static ref POM_VERSION: Regex = Regex::new(r"<version>\s*([^<\s]+)\s*</version>").unwrap();
+    static ref POM_PROPERTIES: Regex = Regex::new(r"(?s)<properties>(.*?)</properties>").unwrap();
+    static ref POM_PROPERTY: Regex = Regex::new(r"<([A-Za-z0-9_.\-]+)>\s*([^<\s]+)\s*</").unwrap();
+}
+
+/// The dependency versions of one directory's files
+#[derive(Debug, Default)]
+struct DirVersions {
+    locked: Vec<Dependency>,
+    declared: Vec<Dependency>,
+}
+
+impl DirVersions {
+    fn read(dir: &Path) -> Self {
+        let mut versions = DirVersions::default();
+        for name in LOCKFILES {
+            let path = dir.join(name);
+            if let Ok(content) = fs::read_to_string(&path) {
+                versions.locked.extend(pinned_versions(&path, &content));
+            }
+        }
+        // Maven has no lockfile; pom.xml versions are exact
+        if let Ok(content) = fs::read_to_string(dir.join("pom.xml")) {
+            versions.locked.extend(pom_dependencies(&content));
+        }
+        if let Ok(content) = fs::read_to_string(dir.join("Cargo.toml")) {
+            versions.declared.extend(cargo_requirements(&content));
+        }
+        if let Ok(content) = fs::read_to_string(dir.join("package.json")) {
+            versions.declared.extend(package_json_requirements(&content));
+        }
+        versions
+    }
+}
+
+/// Resolves library versions from dependency files, reading each directory once per scan
+#[derive(Debug, Default)]
+pub struct VersionResolver {
+    dirs: Mutex<HashMap<PathBuf, Arc<DirVersions>>>,
+}
+
+impl VersionResolver {
+    /// Fill in the version of library findings in `file` that don't carry one
+    pub fn resolve(&self, file: &Path, findings: &mut [Finding]) {
+        let mut wanted = findings.iter_mut().filter(|f| f.category == "library" && f.version.is_none()).peekable();
+        if wanted.peek().is_none() {
+            return;
+        }
+        let dirs: Vec<Arc<DirVersions>> = file.ancestors().skip(1).map(|dir| self.dir(dir)).collect();
+
+        for finding in wanted {
+            let Some(ecosystem) = ecosystem(&finding.language) else {
+                continue;
+            };
+            let keyword = finding.keyword.as_str();
+            let lookup = |dependencies: &[Dependency]| {
+                dependencies
+                    .iter()
+                    .find(|(eco, name, _)| *eco == ecosystem && provides(ecosystem, keyword, name))
+                    .map(|(_, _, version)| version.clone())
+            };
+            let version = dirs
+                .iter()
+                .find_map(|dir| lookup(&dir.locked))
+                .or_else(|| dirs.iter().find_map(|dir| lookup(&dir.declared)));
+            finding.version = version;
+        }
+    }
+
+    /// Forget what was read from `path`'s directory if `path` is a dependency file, so watch mode sees edits
+    pub fn invalidate(&self, path: &Path) {
+        let is_dependency_file = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| LOCKFILES.contains(&name) || MANIFESTS.contains(&name) || name == "pom.xml");
+        if let (true, Some(dir)) = (is_dependency_file, path.parent()) {
+            self.lock().remove(dir);
+        }
+    }
+
+    fn dir(&self, dir: &Path) -> Arc<DirVersions> {
+        if let Some(versions) = self.lock().get(dir) {
+            return versions.clone();
+        }
+        // Read outside the lock; a racing reader of the same directory finds the same versions
+        let versions = Arc::new(DirVersions::read(dir));
+        self.lock().entry(dir.to_path_buf()).or_insert(versions).clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, Arc<DirVersions>>> {
+        self.dirs.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The package ecosystem of a library finding's language
+fn ecosystem(language: &str) -> Option<&'static str> {
+    match language {
+        "Rust" => Some("crates.io"),
+        "JavaScript" => Some("npm"),
+        "Python" => Some("PyPI"),
+        "Java" => Some("Maven"),
+        "C/C++" => Some("ConanCenter"),
+        _ => None,
+    }
+}
+
+/// Whether the package `name` provides the library reported as `keyword`
+fn provides(ecosystem: &str, keyword: &str, name: &str) -> bool {
+    match ecosystem {
+        "Maven" => match MAVEN_GROUPS.iter().find(|(library, _)| *library == keyword) {
+            Some((_, group)) => name.starts_with(group),
+            None => name.rsplit(':').next() == Some(keyword),
+        },
+        "PyPI" => {
+            let distribution = PYTHON_DISTRIBUTIONS.iter().find(|(module, _)| *module == keyword).map_or(keyword, |(_, d)| d);
+            name == normalize_package(distribution)
+        }
+        _ => name == normalize_package(keyword),
+    }
+}
+
+/// `[dependencies]`-style tables of a Cargo.toml, including `[workspace.dependencies]` and target-specific ones
+fn cargo_requirements(content: &str) -> Vec<Dependency> {
+    let Ok(manifest) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let mut tables = vec![&manifest];
+    if let Some(workspace) = manifest.get("workspace").and_then(|w| w.as_table()) {
+        tables.push(workspace);
+    }
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        tables.extend(targets.values().filter_map(|target| target.as_table()));
+    }
+
+    let mut requirements = Vec::new();
+    for table in tables {
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            let Some(dependencies) = table.get(section).and_then(|d| d.as_table()) else {
+                continue;
+            };
+            for (name, spec) in dependencies {
+                let version = spec.as_str().or_else(|| spec.get("version").and_then(|v| v.as_str()));
+                if let Some(version) = version {
+                    requirements.push(("crates.io", normalize_package(name), version.to_string()));
+                }
+            }
+        }
+    }
+    requirements
+}
+
+/// Version ranges from the dependency maps of a package.json
+fn package_json_requirements(content: &str) -> Vec<Dependency> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    ["dependencies", "devDependencies", "peerDependencies", "optionalDependencies"]
+        .iter()
+        .filter_map(|section| manifest.get(section)?.as_object())
+        .flatten()
+        .filter_map(|(name, version)| Some(("npm", normalize_package(name), version.as_str()?.to_string())))
+        .collect()
+}
+
+/// `<dependency>` entries of a pom.xml, with `${property}` versions looked up in `<properties>`
+fn pom_dependencies(content: &str) -> Vec<Dependency> {
+    let properties: HashMap<&str, &str> = POM_PROPERTIES
+        .captures(content)
+        .map(|block| {
+            POM_PROPERTY
+                .captures_iter(block.get(1).map_or("", |m| m.as_str()))
+                .map(|caps| (caps.get(1).map_or("", |m| m.as_str()), caps.get(2).map_or("", |m| m.as_str())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    POM_DEPENDENCY
+        .captures_iter(content)
+        .filter_map(|block| {
+            let block = block.get(1)?.as_str();
+            let group = POM_GROUP.captures(block)?.get(1)?.as_str();
+            let artifact = POM_ARTIFACT.captures(block)?.get(1)?.as_str();
+            let version = POM_VERSION.captures(block)?.get(1)?.as_str();
+            let version = match version.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+                Some(property) => *properties.get(property)?,
+                None => version,
+            };
+            Some(("Maven", format!("{}:{}", group, artifact), version.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::code::scan_content;
+
+    fn library_findings(file: &Path, content: &str) -> Vec<(String, Option<String>)> {
+        let mut findings = scan_content(file, content);
+        VersionResolver::default().resolve(file, &mut findings);
+        findings.into_iter().map(|f| (f.keyword, f.version)).collect()
+    }
+
+    #[test]
+    fn test_locked_version_wins_over_manifest_range() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("crates/core/src")).unwrap();
+        fs::write(root.join("Cargo.lock"), "[[package]]\nname = \"ring\"\nversion = \"0.17.8\"\n").unwrap();
+        fs::write(
+            root.join("crates/core/Cargo.toml"),
+            "[package]\nname = \"core\"\n\n[dependencies]\nring = \"0.17\"\nrustls = { version = \"0.23\", default-features = false }\n",
+        )
+        .unwrap();
+
+        let found = library_findings(&root.join("crates/core/src/lib.rs"), "use ring::aead;\nuse rustls::ClientConfig;\nuse secrecy::Secret;\n");
+        assert_eq!(found, vec![
+            ("ring".to_string(), Some("0.17.8".to_string())),
+            ("rustls".to_string(), Some("0.23".to_string())),
+            ("secrecy".to_string(), None),
+        ]);
+    }
+
+    #[test]
+    fn test_npm_python_and_maven_versions() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("package.json"), r#"{"dependencies": {"jsonwebtoken": "^9.0.0", "node-forge": "^1.3.0"}}"#).unwrap();
+        fs::write(
+            root.join("package-lock.json"),
+            "{\n  \"packages\": {\n    \"node_modules/jsonwebtoken\": {\n      \"version\": \"9.0.2\"\n    }\n  }\n}\n",
+        )
+        .unwrap();
+        fs::write(root.join("requirements.txt"), "PyJWT==2.8.0\ncryptography==42.0.5\n").unwrap();
+        fs::write(
+            root.join("pom.xml"),
+            "<project><properties><bc.version>1.78</bc.version></properties><dependencies>\
+             <dependency><groupId>org.bouncycastle</groupId><artifactId>bcprov-jdk18on</artifactId><version>${bc.version}</version></dependency>\
+             </dependencies></project>",
+        )
+        .unwrap();
+
+        let js = library_findings(&root.join("app.js"), "const jwt = require('jsonwebtoken');\nimport forge from 'node-forge';\n");
+        assert_eq!(js, vec![
+            ("jsonwebtoken".to_string(), Some("9.0.2".to_string())),
+            ("node-forge".to_string(), Some("^1.3.0".to_string())),
+        ]);
+
+        let py = library_findings(&root.join("auth.py"), "import jwt\nfrom cryptography.fernet import Fernet\n");
+        assert!(py.contains(&("jwt".to_string(), Some("2.8.0".to_string()))), "{:?}", py);
+        assert!(py.contains(&("cryptography".to_string(), Some("42.0.5".to_string()))), "{:?}", py);
+
+        let java = library_findings(&root.join("Signer.java"), "import org.bouncycastle.crypto.Signer;\n");
+        assert!(java.contains(&("bouncycastle".to_string(), Some("1.78".to_string()))), "{:?}", java);
+    }
+}
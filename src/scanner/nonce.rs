@@ -0,0 +1,255 @@
+use crate::scanner::secrets::is_comment_line;
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::lang_ident::detect_language;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+/// How many lines either side of a suspicious nonce an AEAD/stream cipher call must appear
+const PROXIMITY_LINES: usize = 6;
+
+/// Identifiers naming a nonce or IV (`nonce`, `gcm_nonce`, `iv`, `iv_bytes`, `aesIv`) but not words that merely contain "iv"
+const NONCE_IDENT: &str = r"\b(?:\w*(?i:nonce)\w*|(?i:iv)|(?i:iv)_\w+|iv[A-Z]\w*|\w+_(?i:iv)|\w*[a-z](?:Iv|IV))\b";
+
+const REMEDIATION: &str = "Generate a fresh random nonce from a CSPRNG for every message, or use a strictly unique nonce per key; never reuse a nonce with the same key";
+
+/// Reusing a Nonce, Key Pair in Encryption
+const CWE_NONCE_REUSE: &str = "CWE-323";
+/// NIST SP 800-38D, whose section 8 sets the uniqueness requirement on GCM IVs
+const NONCE_GUIDE: &str = "https://csrc.nist.gov/pubs/sp/800/38/d/final";
+
+lazy_static! {
+    /// GCM and stream cipher constructions where nonce reuse is catastrophic
+    static ref CIPHER_CALL: Regex = Regex::new(
+        r#"(?i)(aes[-_]?(\d{3}[-_]?)?gcm|gcmparameterspec|/gcm/|cipher\.newgcm|chacha20|xchacha|salsa20|createcipheriv\s*\(\s*['"](aes-\d{3}-gcm|chacha20))"#
+    ).unwrap();
+
+    /// Calls that fill a buffer from a CSPRNG, which make a zero-initialised nonce buffer safe
+    static ref RANDOM_FILL: Regex = Regex::new(
+        r"(?i)(urandom|randombytes|random_bytes|get_random_bytes|token_bytes|securerandom|nextbytes|getrandomvalues|fill_bytes|try_fill|generate_nonce|osrng|thread_rng|readfull\(\s*rand|rand\.read|getrandom)"
+    ).unwrap();
+
+    /// Suspicious nonce sources: (pattern, keyword, description, needs_random_fill_check)
+    static ref NONCE_SOURCES: Vec<(Regex, &'static str, &'static str, bool)> = vec![
+        (
+            Regex::new(&format!(r#"{}\s*:?=\s*(?i:0\b|b?['"](\\x00|0)+['"](\s*\*\s*\d+)?|\[0u8;\s*\d+\]|new\s+byte\s*\[\s*\d+\s*\]|buffer\.alloc\(\s*\d+\s*\)|bytes\(\s*\d+\s*\)|bytearray\(\s*\d+\s*\)|make\(\[\]byte,\s*\w+)"#, NONCE_IDENT)).unwrap(),
+            "Constant Nonce",
+            "Nonce/IV is a zero-filled or constant buffer",
+            true,
+        ),
+        (
+            Regex::new(&format!(r#"{}\s*:?=\s*b?['"][^'"]{{8,}}['"]"#, NONCE_IDENT)).unwrap(),
+            "Constant Nonce",
+            "Nonce/IV is a hardcoded string literal",
+            false,
+        ),
+        (
+            Regex::new(r#"(?i)nonce::(from_slice|from|clone_from_slice)\(\s*(b"|&\[0u8)|nonce::default\(\)"#).unwrap(),
+            "Constant Nonce",
+            "Nonce is built from a literal or default value",
+            false,
+        ),
+        (
+            Regex::new(&format!(r"{0}\s*(\+=\s*1\b|\+\+)|\+\+\s*{0}", NONCE_IDENT)).unwrap(),
+            "Counter-derived Nonce",
+            "Nonce/IV is incremented per message",
+            false,
+        ),
+        (
+            Regex::new(&format!(r"{}.*\b\w*(?i:counter|sequence|seq_no|seqno|msg_num|message_id)\w*", NONCE_IDENT)).unwrap(),
+            "Counter-derived Nonce",
+            "Nonce/IV is derived from a persisted counter",
+            false,
+        ),
+        (
+            Regex::new(&format!(r"{}\s*:?=.*(?i:time\.time\(|\bnow\(|currenttimemillis|nanotime|date\.now|unixnano|systemtime|timestamp)", NONCE_IDENT)).unwrap(),
+            "Predictable Nonce",
+            "Nonce/IV is derived from the clock",
+            false,
+        ),
+        (
+            Regex::new(&format!(r"{}\s*:?=.*\b(?i:md5|sha1|sha256|hash|digest)\w*\(", NONCE_IDENT)).unwrap(),
+            "Predictable Nonce",
+            "Nonce/IV is derived from a hash of non-random data",
+            false,
+        ),
+    ];
+}
+
+/// Scans a source file for constant, counter-based or predictable nonces feeding GCM/stream ciphers
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded file content for nonce reuse patterns
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let lines: Vec<&str> = content.lines().collect();
+
+    // Cheap pre-check: nothing to do if the file never touches an AEAD/stream cipher
+    if !lines.iter().any(|line| CIPHER_CALL.is_match(line)) {
+        return findings;
+    }
+
+    let language = detect_language(path);
+
+    for (i, line) in lines.iter().enumerate() {
+        if is_comment_line(line) {
+            continue;
+        }
+
+        let window_start = i.saturating_sub(PROXIMITY_LINES);
+        let window_end = (i + PROXIMITY_LINES + 1).min(lines.len());
+        let window = &lines[window_start..window_end];
+
+        if !window.iter().any(|l| CIPHER_CALL.is_match(l)) {
+            continue;
+        }
+
+        for (regex, keyword, description, needs_fill_check) in NONCE_SOURCES.iter() {
+            if !regex.is_match(line) {
+                continue;
+            }
+            // A zeroed buffer that is immediately filled from a CSPRNG is the correct idiom
+            if *needs_fill_check && window.iter().any(|l| RANDOM_FILL.is_match(l)) {
+                continue;
+            }
+
+            findings.push(Finding {
+                file: path.display().to_string(),
+                line_number: i + 1,
+                end_line_number: None,
+                column_start: None,
+                column_end: None,
+                line_content: line.to_string(),
+                match_type: "nonce".to_string(),
+                keyword: keyword.to_string(),
+                context: format!("{}. {}", description, REMEDIATION),
+                version: None,
+                language: language.clone(),
+                source: "heuristic".to_string(),
+                category: "nonce-reuse".to_string(),
+                severity: Some(Severity::Critical),
+                file_hash: None,
+                cwe: Some(CWE_NONCE_REUSE.to_string()),
+                remediation: Some(NONCE_GUIDE.to_string()),
+            });
+            // One finding per line is enough even if several heuristics agree
+            break;
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(file: &str, content: &str) -> Vec<Finding> {
+        scan_content(Path::new(file), content)
+    }
+
+    #[test]
+    fn test_python_constant_nonce() {
+        let findings = scan("enc.py", r#"
+from cryptography.hazmat.primitives.ciphers.aead import AESGCM
+aesgcm = AESGCM(key)
+nonce = b"\x00" * 12
+ct = aesgcm.encrypt(nonce, data, None)
+"#);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].category, "nonce-reuse");
+        assert_eq!(findings[0].severity, Some(Severity::Critical));
+        assert_eq!(findings[0].line_number, 4);
+        assert!(findings[0].context.ends_with(REMEDIATION));
+        assert_eq!(findings[0].cwe.as_deref(), Some(CWE_NONCE_REUSE));
+        assert!(findings[0].remediation.as_deref().is_some_and(|url| url.starts_with("https://")));
+    }
+
+    #[test]
+    fn test_python_random_nonce_not_flagged() {
+        let findings = scan("enc.py", r#"
+aesgcm = AESGCM(key)
+nonce = os.urandom(12)
+ct = aesgcm.encrypt(nonce, data, None)
+"#);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_rust_literal_nonce() {
+        let findings = scan("enc.rs", r#"
+let cipher = Aes256Gcm::new(&key);
+let nonce = Nonce::from_slice(b"unique nonce");
+let ciphertext = cipher.encrypt(nonce, b"plaintext".as_ref())?;
+"#);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].keyword, "Constant Nonce");
+    }
+
+    #[test]
+    fn test_java_zero_iv() {
+        let findings = scan("Enc.java", r#"
+Cipher cipher = Cipher.getInstance("AES/GCM/NoPadding");
+byte[] iv = new byte[12];
+cipher.init(Cipher.ENCRYPT_MODE, key, new GCMParameterSpec(128, iv));
+"#);
+        assert_eq!(findings.len(), 1);
+    }
+
+    #[test]
+    fn test_java_secure_random_iv_not_flagged() {
+        let findings = scan("Enc.java", r#"
+Cipher cipher = Cipher.getInstance("AES/GCM/NoPadding");
+byte[] iv = new byte[12];
+new SecureRandom().nextBytes(iv);
+cipher.init(Cipher.ENCRYPT_MODE, key, new GCMParameterSpec(128, iv));
+"#);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_javascript_counter_nonce() {
+        let findings = scan("enc.js", r#"
+const iv = Buffer.from(messageCounter.toString().padStart(12, '0'));
+const cipher = crypto.createCipheriv('aes-256-gcm', key, iv);
+"#);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].keyword, "Counter-derived Nonce");
+    }
+
+    #[test]
+    fn test_go_counter_nonce() {
+        let findings = scan("enc.go", r#"
+aead, _ := chacha20poly1305.New(key)
+nonce := make([]byte, aead.NonceSize())
+binary.BigEndian.PutUint64(nonce[4:], state.counter)
+out := aead.Seal(nil, nonce, msg, nil)
+"#);
+        assert!(findings
+            .iter()
+            .any(|f| f.line_number == 4 && f.keyword == "Counter-derived Nonce"));
+    }
+
+    #[test]
+    fn test_unrelated_identifiers_not_flagged() {
+        let findings = scan("enc.py", r#"
+aesgcm = AESGCM(key)
+private_key = "0123456789abcdef0123"
+derivative = 0
+"#);
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_nonce_without_cipher_not_flagged() {
+        // OAuth-style nonces unrelated to encryption must not be reported
+        let findings = scan("auth.py", "nonce = 0\nrequest.params['nonce'] = nonce\n");
+        assert!(findings.is_empty());
+    }
+}
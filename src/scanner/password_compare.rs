@@ -0,0 +1,172 @@
+use crate::scanner::secrets::is_comment_line;
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::lang_ident::detect_language;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+/// A comparison operand: identifier chain (`user.password`, `row['pw']`, `getPassword()`) or literal
+const OPERAND: &str = r#"(?:[A-Za-z_$][\w$]*(?:(?:\.|->|::)[A-Za-z_$][\w$]*|\[[^\]]*\]|\(\s*\))*|"[^"]*"|'[^']*'|\d+)"#;
+
+const COMPARE_REMEDIATION: &str = "Verify passwords with a constant-time password-hash check (bcrypt/argon2/scrypt verify, hmac.compare_digest, crypto.timingSafeEqual, MessageDigest.isEqual)";
+
+lazy_static! {
+    /// Equality comparisons: `a == b`, `a === b`, `a != b`, `a.equals(b)`, `strcmp(a, b)`
+    static ref COMPARISONS: Vec<Regex> = vec![
+        Regex::new(&format!(r"({0})\s*(?:===?|!==?)\s*({0})", OPERAND)).unwrap(),
+        Regex::new(&format!(r"({0})\s*\.equals(?:IgnoreCase)?\(\s*({0})\s*\)", OPERAND)).unwrap(),
+        Regex::new(&format!(r"\bstrn?cmp\(\s*({0})\s*,\s*({0})", OPERAND)).unwrap(),
+    ];
+
+    /// Operand names that hold a password
+    static ref PASSWORD_NAME: Regex = Regex::new(r"(?i)(password|passwd|passphrase|pwd|\bpw\b|\bpass\b)").unwrap();
+
+    /// Operand names that show a stored/persisted value
+    static ref STORED_NAME: Regex = Regex::new(r"(?i)(\.|\[|->|^(stored|db|saved|expected|correct|real|actual|user)_?)").unwrap();
+
+    /// Operand names showing the value is already hashed
+    static ref HASHED_NAME: Regex = Regex::new(r"(?i)(hash|digest|crypt|bcrypt|argon|scrypt|pbkdf)").unwrap();
+
+    /// Comparing against a confirmation field is input validation, not verification
+    static ref CONFIRMATION_NAME: Regex = Regex::new(r"(?i)(confirm|repeat|retype|again|verify|new_?pass|old_?pass)").unwrap();
+
+    /// Lines already using a constant-time comparison
+    static ref CONSTANT_TIME_CALL: Regex = Regex::new(
+        r"(?i)(compare_digest|constant_?time|timingsafeequal|secure_compare|messagedigest\.isequal|subtle\.|checkpw|password_verify|check_password_hash|\.verify\()"
+    ).unwrap();
+}
+
+/// Literal operands that make the comparison an emptiness/null check rather than verification
+fn is_trivial_literal(operand: &str) -> bool {
+    let lower = operand.to_lowercase();
+    matches!(lower.as_str(), "\"\"" | "''" | "none" | "null" | "nil" | "undefined" | "true" | "false")
+        || operand.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Scans a source file for passwords compared with plain (timing-unsafe) equality
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded content for insecure password comparisons
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let language = detect_language(path);
+
+    for (line_num, line) in content.lines().enumerate() {
+        if is_comment_line(line) || CONSTANT_TIME_CALL.is_match(line) {
+            continue;
+        }
+
+        let Some((left, right)) = COMPARISONS.iter().find_map(|regex| {
+            regex.captures_iter(line).find_map(|caps| {
+                let (left, right) = (caps.get(1)?.as_str(), caps.get(2)?.as_str());
+                let involves_password = PASSWORD_NAME.is_match(left) || PASSWORD_NAME.is_match(right);
+                let is_verification = !is_trivial_literal(left)
+                    && !is_trivial_literal(right)
+                    && !CONFIRMATION_NAME.is_match(left)
+                    && !CONFIRMATION_NAME.is_match(right);
+                (involves_password && is_verification).then(|| (left.to_string(), right.to_string()))
+            })
+        }) else {
+            continue;
+        };
+
+        let finding = |keyword: &str, context: String| Finding {
+            file: path.display().to_string(),
+            line_number: line_num + 1,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: line.to_string(),
+            match_type: "comparison".to_string(),
+            keyword: keyword.to_string(),
+            context,
+            version: None,
+            language: language.clone(),
+            source: "heuristic".to_string(),
+            category: "insecure-password-compare".to_string(),
+            severity: Some(Severity::High),
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        };
+
+        findings.push(finding(
+            "Timing-unsafe Password Comparison",
+            format!("{} compared with {} using plain equality, which leaks timing information. {}", left, right, COMPARE_REMEDIATION),
+        ));
+
+        // A stored password that isn't named as a hash is most likely kept in plaintext
+        let stored = [&left, &right]
+            .into_iter()
+            .find(|op| PASSWORD_NAME.is_match(op) && STORED_NAME.is_match(op) && !HASHED_NAME.is_match(op));
+        if let Some(stored) = stored {
+            findings.push(finding(
+                "Plaintext Password Storage",
+                format!("{} appears to be a stored plaintext password; store a salted password hash (bcrypt/argon2/scrypt) instead", stored),
+            ));
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keywords(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.keyword.as_str()).collect()
+    }
+
+    #[test]
+    fn test_python_plaintext_compare() {
+        let findings = scan_content(Path::new("auth.py"), "    if pw == user.password:\n        login(user)\n");
+        assert_eq!(keywords(&findings), vec!["Timing-unsafe Password Comparison", "Plaintext Password Storage"]);
+        assert!(findings.iter().all(|f| f.category == "insecure-password-compare"));
+        assert!(findings.iter().all(|f| f.severity == Some(Severity::High)));
+        assert_eq!(findings[0].line_number, 1);
+    }
+
+    #[test]
+    fn test_hash_compare_is_timing_only() {
+        let findings = scan_content(Path::new("auth.py"), "if hash_password(pw) and password_hash == stored_hash:\n");
+        assert_eq!(keywords(&findings), vec!["Timing-unsafe Password Comparison"]);
+    }
+
+    #[test]
+    fn test_javascript_strict_equality() {
+        let findings = scan_content(Path::new("login.js"), "if (req.body.password === row.password) { ok(); }\n");
+        assert_eq!(keywords(&findings), vec!["Timing-unsafe Password Comparison", "Plaintext Password Storage"]);
+    }
+
+    #[test]
+    fn test_java_equals() {
+        let findings = scan_content(Path::new("Auth.java"), "if (inputPassword.equals(user.getPassword())) {\n");
+        assert_eq!(findings.len(), 2);
+    }
+
+    #[test]
+    fn test_go_db_password() {
+        let findings = scan_content(Path::new("auth.go"), "if input_password == db_password {\n");
+        assert_eq!(keywords(&findings), vec!["Timing-unsafe Password Comparison", "Plaintext Password Storage"]);
+    }
+
+    #[test]
+    fn test_safe_and_non_verification_comparisons_ignored() {
+        let content = r#"
+if hmac.compare_digest(password_hash, stored_hash):
+if password == "":
+if password is None or password == None:
+if password == password_confirmation:
+if len(password) == 8:
+if bcrypt.checkpw(pw, user.password_hash):
+"#;
+        assert!(scan_content(Path::new("auth.py"), content).is_empty());
+    }
+}
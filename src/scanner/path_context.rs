@@ -0,0 +1,166 @@
+use crate::utils::report::{Finding, Severity};
+use std::path::{Component, Path};
+
+/// Directories whose findings are likely real production exposures
+pub const DEFAULT_PRODUCTION_PATHS: &[&str] = &[
+    "src", "app", "lib", "config/production", "deploy", "prod", "production",
+];
+
+/// Directories whose findings are usually illustrative
+pub const DEFAULT_NON_PRODUCTION_PATHS: &[&str] = &[
+    "examples", "example", "samples", "sample", "docs", "doc", "demo", "demos",
+];
+
+/// How a file's location affects confidence in its findings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathClass {
+    Production,
+    NonProduction,
+    Neutral,
+}
+
+/// Classifies files by directory and shifts finding severity accordingly.
+///
+/// Each pattern is a directory name or a `/`-separated run of directory names
+/// (e.g. `config/production`) matched against the file's parent directories,
+/// case-insensitively. Non-production patterns win when both match, so
+/// `src/examples/` counts as non-production.
+#[derive(Debug, Clone)]
+pub struct PathClassifier {
+    production: Vec<Vec<String>>,
+    non_production: Vec<Vec<String>>,
+}
+
+impl Default for PathClassifier {
+    fn default() -> Self {
+        PathClassifier::new(DEFAULT_PRODUCTION_PATHS, DEFAULT_NON_PRODUCTION_PATHS)
+    }
+}
+
+fn split_pattern(pattern: &str) -> Option<Vec<String>> {
+    let parts: Vec<String> = pattern
+        .split(['/', '\\'])
+        .filter(|p| !p.is_empty())
+        .map(|p| p.to_lowercase())
+        .collect();
+    (!parts.is_empty()).then_some(parts)
+}
+
+impl PathClassifier {
+    pub fn new<S: AsRef<str>>(production: &[S], non_production: &[S]) -> Self {
+        PathClassifier {
+            production: production.iter().filter_map(|p| split_pattern(p.as_ref())).collect(),
+            non_production: non_production.iter().filter_map(|p| split_pattern(p.as_ref())).collect(),
+        }
+    }
+
+    /// Classify a file by the directories it lives in
+    pub fn classify(&self, path: &Path) -> PathClass {
+        let dirs: Vec<String> = path
+            .parent()
+            .map(|parent| {
+                parent
+                    .components()
+                    .filter_map(|c| match c {
+                        Component::Normal(name) => Some(name.to_string_lossy().to_lowercase()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let matches = |patterns: &[Vec<String>]| {
+            patterns
+                .iter()
+                .any(|pattern| dirs.windows(pattern.len()).any(|window| window == pattern.as_slice()))
+        };
+
+        if matches(&self.non_production) {
+            PathClass::NonProduction
+        } else if matches(&self.production) {
+            PathClass::Production
+        } else {
+            PathClass::Neutral
+        }
+    }
+
+    /// Raise or lower a rated finding's severity by one level based on its location.
+    /// Unrated and informational findings (library inventory) are left untouched.
+    pub fn adjust(&self, finding: &mut Finding) {
+        let Some(severity) = finding.severity.filter(|s| *s != Severity::Info) else {
+            return;
+        };
+
+        match self.classify(Path::new(&finding.file)) {
+            PathClass::Production => {
+                finding.severity = Some(severity.raised());
+                if finding.severity != Some(severity) {
+                    finding.context.push_str(" [severity raised: production path]");
+                }
+            }
+            PathClass::NonProduction => {
+                finding.severity = Some(severity.lowered());
+                if finding.severity != Some(severity) {
+                    finding.context.push_str(" [severity lowered: example/documentation path]");
+                }
+            }
+            PathClass::Neutral => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_classification() {
+        let classifier = PathClassifier::default();
+        assert_eq!(classifier.classify(Path::new("./repo/src/api/client.py")), PathClass::Production);
+        assert_eq!(classifier.classify(Path::new("repo/config/production/db.yml")), PathClass::Production);
+        assert_eq!(classifier.classify(Path::new("repo/docs/quickstart.py")), PathClass::NonProduction);
+        assert_eq!(classifier.classify(Path::new("repo/src/examples/demo.py")), PathClass::NonProduction);
+        assert_eq!(classifier.classify(Path::new("repo/scripts/run.sh")), PathClass::Neutral);
+        // Only directories count, not the file name itself
+        assert_eq!(classifier.classify(Path::new("repo/docs.py")), PathClass::Neutral);
+    }
+
+    #[test]
+    fn test_severity_adjustment() {
+        let classifier = PathClassifier::default();
+
+        let mut prod = Finding {
+            severity: Some(Severity::High),
+            ..Finding::for_test("repo/app/settings.py", 1, "API Key", "secret")
+        };
+        classifier.adjust(&mut prod);
+        assert_eq!(prod.severity, Some(Severity::Critical));
+        assert!(prod.context.contains("production path"));
+
+        let mut example = Finding {
+            severity: Some(Severity::High),
+            ..Finding::for_test("repo/examples/settings.py", 1, "API Key", "secret")
+        };
+        classifier.adjust(&mut example);
+        assert_eq!(example.severity, Some(Severity::Medium));
+
+        let mut unrated = Finding::for_test("repo/src/settings.py", 1, "API Key", "secret");
+        classifier.adjust(&mut unrated);
+        assert_eq!(unrated.severity, None);
+
+        let mut inventory = Finding {
+            severity: Some(Severity::Info),
+            ..Finding::for_test("repo/src/settings.py", 1, "API Key", "secret")
+        };
+        classifier.adjust(&mut inventory);
+        assert_eq!(inventory.severity, Some(Severity::Info));
+    }
+
+    #[test]
+    fn test_custom_paths() {
+        let classifier = PathClassifier::new(&["services"], &["sandbox", ""]);
+        assert_eq!(classifier.classify(Path::new("services/billing/key.rs")), PathClass::Production);
+        assert_eq!(classifier.classify(Path::new("src/key.rs")), PathClass::Neutral);
+        assert_eq!(classifier.classify(Path::new("sandbox/key.rs")), PathClass::NonProduction);
+    }
+}
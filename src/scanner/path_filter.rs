@@ -0,0 +1,89 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::Path;
+
+/// How `--include`/`--exclude` patterns treat a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    /// Matched an `--exclude` pattern; never scanned
+    Excluded,
+    /// Matched an `--include` pattern; scanned even inside the built-in ignored folders
+    Included,
+    /// Matched neither; the built-in rules decide
+    Unmatched,
+}
+
+/// User-supplied include/exclude globs, matched against paths relative to the scan root.
+/// Excludes take precedence over includes.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl Default for PathFilter {
+    fn default() -> Self {
+        PathFilter { include: GlobSet::empty(), exclude: GlobSet::empty() }
+    }
+}
+
+fn build_set(globs: &[Glob]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(glob.clone());
+    }
+    builder.build().expect("individually valid globs combine into a glob set")
+}
+
+/// Parse a single `--include`/`--exclude` pattern
+pub fn parse_glob(pattern: &str) -> Result<Glob, String> {
+    Glob::new(pattern).map_err(|e| e.to_string())
+}
+
+impl PathFilter {
+    pub fn new(include: &[Glob], exclude: &[Glob]) -> Self {
+        PathFilter { include: build_set(include), exclude: build_set(exclude) }
+    }
+
+    /// Filter from glob pattern strings, for callers building [`ScanOptions`](super::ScanOptions) in code
+    pub fn from_patterns(include: &[&str], exclude: &[&str]) -> Result<Self, String> {
+        let parse = |patterns: &[&str]| patterns.iter().map(|p| parse_glob(p)).collect::<Result<Vec<_>, _>>();
+        Ok(PathFilter::new(&parse(include)?, &parse(exclude)?))
+    }
+
+    /// Classify a path relative to the scan root
+    pub fn select(&self, relative: &Path) -> Selection {
+        if self.exclude.is_match(relative) {
+            Selection::Excluded
+        } else if self.include.is_match(relative) {
+            Selection::Included
+        } else {
+            Selection::Unmatched
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn globs(patterns: &[&str]) -> Vec<Glob> {
+        patterns.iter().map(|p| parse_glob(p).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_exclude_wins_over_include() {
+        let filter = PathFilter::new(&globs(&["vendor/mycrypto/**"]), &globs(&["tests/**", "**/*_test.go"]));
+
+        assert_eq!(filter.select(Path::new("vendor/mycrypto/aes.c")), Selection::Included);
+        assert_eq!(filter.select(Path::new("vendor/mycrypto/aes_test.go")), Selection::Excluded);
+        assert_eq!(filter.select(Path::new("tests/fixtures/keys.py")), Selection::Excluded);
+        assert_eq!(filter.select(Path::new("vendor/other/lib.c")), Selection::Unmatched);
+        assert_eq!(PathFilter::default().select(Path::new("src/main.rs")), Selection::Unmatched);
+    }
+
+    #[test]
+    fn test_invalid_glob_rejected() {
+        assert!(parse_glob("src/[a-").is_err());
+        assert!(PathFilter::from_patterns(&["src/**"], &["src/[a-"]).is_err());
+    }
+}
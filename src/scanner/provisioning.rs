@@ -0,0 +1,216 @@
+use crate::scanner::secrets::is_template_reference;
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// cloud-init user-data file names
+const CLOUD_INIT_FILE_NAMES: &[&str] = &[
+    "user-data", "user-data.yml", "user-data.yaml", "vendor-data",
+    "cloud-init.yml", "cloud-init.yaml", "cloud-config.yml", "cloud-config.yaml",
+];
+
+lazy_static! {
+    /// Provisioning-specific credential patterns: (pattern, name, description, severity)
+    static ref PROVISIONING_PATTERNS: Vec<(Regex, &'static str, &'static str, Severity)> = vec![
+        (
+            Regex::new(r#"(?i)["']?\b\w*(?:password|passwd|secret|token|access_key|api_key)\w*["']?\s*[:=]\s*["']([^"'\s]{4,})["']"#).unwrap(),
+            "Provisioning Credential",
+            "Credential hardcoded in a provisioning template",
+            Severity::High,
+        ),
+        (
+            Regex::new(r#"(?i)^\s*-?\s*(?:password|plain_text_passwd|passwd|hashed_passwd)\s*:\s*([^\s"'#]{4,})\s*$"#).unwrap(),
+            "cloud-init Password",
+            "User password set in cloud-init user-data",
+            Severity::High,
+        ),
+        (
+            Regex::new(r"-----BEGIN (?:[A-Z]+ )?PRIVATE KEY-----").unwrap(),
+            "Private Key",
+            "Private key written onto provisioned machines",
+            Severity::Critical,
+        ),
+        (
+            Regex::new(r"((?:ssh-(?:rsa|ed25519|dss)|ecdsa-sha2-nistp\d+) AAAA[0-9A-Za-z+/=]{20,})").unwrap(),
+            "SSH Authorized Key",
+            "SSH public key injected into provisioned machines; whoever holds the matching private key gets access",
+            Severity::Medium,
+        ),
+    ];
+}
+
+/// Check whether a file is a Vagrant, Packer or cloud-init file by name
+pub fn is_provisioning_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|f| f.to_str()) else {
+        return false;
+    };
+    let name = name.to_lowercase();
+    name == "vagrantfile"
+        || name.ends_with(".pkr.hcl")
+        || name.ends_with(".pkr.json")
+        || CLOUD_INIT_FILE_NAMES.contains(&name.as_str())
+}
+
+/// Check whether already-loaded YAML content starts with a `#cloud-config` header
+pub fn starts_with_cloud_config(content: &str) -> bool {
+    content
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| line.trim_start().starts_with("#cloud-config"))
+}
+
+/// Check whether a YAML file is cloud-init user-data by its `#cloud-config` header
+pub fn has_cloud_config_header(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .find(|line| !line.trim().is_empty())
+        .map(|line| line.trim_start().starts_with("#cloud-config"))
+        .unwrap_or(false)
+}
+
+fn provisioning_language(path: &Path) -> &'static str {
+    let name = path
+        .file_name()
+        .and_then(|f| f.to_str())
+        .map(|n| n.to_lowercase())
+        .unwrap_or_default();
+    if name == "vagrantfile" {
+        "Ruby"
+    } else if name.ends_with(".pkr.hcl") {
+        "HCL"
+    } else if name.ends_with(".json") {
+        "JSON"
+    } else {
+        "YAML"
+    }
+}
+
+/// Scans a provisioning file for secrets, keys and credentials injected into machines
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded provisioning content
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    let language = provisioning_language(path);
+
+    let mut findings: Vec<Finding> = crate::scanner::secrets::scan_content(path, content)
+        .into_iter()
+        .map(|mut finding| {
+            finding.language = language.to_string();
+            finding.source = "provisioning".to_string();
+            finding.severity.get_or_insert(Severity::High);
+            finding
+        })
+        .collect();
+
+    for (line_num, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with('#') || line.trim_start().starts_with("//") {
+            continue;
+        }
+        // The generic secret patterns already reported this line
+        if findings.iter().any(|f| f.line_number == line_num + 1) {
+            continue;
+        }
+
+        for (regex, name, description, severity) in PROVISIONING_PATTERNS.iter() {
+            let Some(caps) = regex.captures(line) else {
+                continue;
+            };
+            if caps.get(1).is_some_and(|value| is_template_reference(value.as_str())) {
+                continue;
+            }
+
+            findings.push(Finding {
+                file: path.display().to_string(),
+                line_number: line_num + 1,
+                end_line_number: None,
+                column_start: None,
+                column_end: None,
+                line_content: line.to_string(),
+                match_type: "secret".to_string(),
+                keyword: name.to_string(),
+                context: description.to_string(),
+                version: None,
+                language: language.to_string(),
+                source: "provisioning".to_string(),
+                category: "secret".to_string(),
+                severity: Some(*severity),
+                file_hash: None,
+                cwe: None,
+                remediation: None,
+            });
+            break;
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_provisioning_file_recognition() {
+        assert!(is_provisioning_file(&PathBuf::from("infra/Vagrantfile")));
+        assert!(is_provisioning_file(&PathBuf::from("images/ubuntu.pkr.hcl")));
+        assert!(is_provisioning_file(&PathBuf::from("seed/user-data")));
+        assert!(!is_provisioning_file(&PathBuf::from("docker-compose.yml")));
+    }
+
+    #[test]
+    fn test_ssh_key_in_cloud_init_user_data() {
+        let content = "#cloud-config\n\
+                       users:\n\
+                       \x20 - name: deploy\n\
+                       \x20   ssh_authorized_keys:\n\
+                       \x20     - ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGq3fJ5k8xYzQw7bKx2PqL0mN9sR4tU6vW8yZ1aB3cD5 deploy@ci\n\
+                       \x20   passwd: $6$rounds=4096$saltsalt$hashedhashedhashed\n\
+                       chpasswd:\n\
+                       \x20 expire: false\n\
+                       \x20 password: S3cureBootstrap!\n";
+        let findings = scan_content(Path::new("user-data"), content);
+
+        assert!(findings.iter().all(|f| f.source == "provisioning"));
+        let key = findings.iter().find(|f| f.keyword == "SSH Authorized Key").expect("ssh key finding");
+        assert_eq!(key.line_number, 5);
+        assert_eq!(key.severity, Some(Severity::Medium));
+        assert!(findings.iter().any(|f| f.keyword == "cloud-init Password" && f.line_number == 9));
+    }
+
+    #[test]
+    fn test_packer_credentials_and_references() {
+        let content = r#"
+source "amazon-ebs" "ubuntu" {
+  ssh_password  = "packer-Build-2024"
+  winrm_password = "${var.winrm_password}"
+  access_key    = "{{ user `aws_access_key` }}"
+}
+"#;
+        let findings = scan_content(Path::new("ubuntu.pkr.hcl"), content);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line_number, 3);
+        assert_eq!(findings[0].language, "HCL");
+    }
+
+    #[test]
+    fn test_vagrant_password() {
+        let content = "Vagrant.configure(\"2\") do |config|\n  config.ssh.password = \"vagrant-pass\"\nend\n";
+        let findings = scan_content(Path::new("Vagrantfile"), content);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].language, "Ruby");
+    }
+}
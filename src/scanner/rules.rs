@@ -0,0 +1,94 @@
+//! Compilation of user-supplied detection patterns.
+//!
+//! Built-in patterns are reviewed with the code; patterns from rule files are not,
+//! so they are compiled under explicit `regex` size limits and rejected at load
+//! time if they would produce an oversized automaton.
+
+use crate::config::Config;
+use crate::error::{config_error, ScanResult};
+use regex::{Regex, RegexBuilder};
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::Path;
+
+/// Default cap on a compiled user pattern (`--regex-size-limit`)
+pub const DEFAULT_REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Default cap on the lazy DFA cache of a user pattern (`--regex-dfa-size-limit`)
+pub const DEFAULT_REGEX_DFA_SIZE_LIMIT: usize = 2 << 20;
+
+/// Size limits applied when compiling user-supplied patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegexLimits {
+    /// Bytes the compiled program may use
+    pub size_limit: usize,
+    /// Bytes the lazy DFA may cache while matching
+    pub dfa_size_limit: usize,
+}
+
+impl Default for RegexLimits {
+    fn default() -> Self {
+        RegexLimits { size_limit: DEFAULT_REGEX_SIZE_LIMIT, dfa_size_limit: DEFAULT_REGEX_DFA_SIZE_LIMIT }
+    }
+}
+
+impl From<&Config> for RegexLimits {
+    fn from(config: &Config) -> Self {
+        RegexLimits { size_limit: config.regex_size_limit, dfa_size_limit: config.regex_dfa_size_limit }
+    }
+}
+
+/// Compile a pattern loaded from a rule file; `name` identifies the rule in errors
+pub fn compile_user_pattern(name: &str, pattern: &str, limits: RegexLimits) -> ScanResult<Regex> {
+    RegexBuilder::new(pattern)
+        .size_limit(limits.size_limit)
+        .dfa_size_limit(limits.dfa_size_limit)
+        .build()
+        .map_err(|e| match e {
+            regex::Error::CompiledTooBig(limit) => config_error(&format!(
+                "Pattern for rule '{}' compiles to more than {} bytes (--regex-size-limit); simplify it or raise the limit",
+                name, limit
+            )),
+            other => config_error(&format!("Invalid pattern for rule '{}': {}", name, other)),
+        })
+}
+
+/// Read a rule file: YAML for `.yaml`/`.yml`, TOML otherwise
+pub fn load_rule_file<T: DeserializeOwned>(path: &Path) -> ScanResult<T> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| config_error(&format!("Cannot read rule file {}: {}", path.display(), e)))?;
+    let is_yaml = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"));
+    let parsed = if is_yaml {
+        serde_yaml::from_str(&content).map_err(|e| e.to_string())
+    } else {
+        toml::from_str(&content).map_err(|e| e.to_string())
+    };
+    parsed.map_err(|e| config_error(&format!("Invalid rule file {}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oversized_pattern_rejected() {
+        let limits = RegexLimits::default();
+        assert!(compile_user_pattern("internal-token", r"lt_live_[A-Za-z0-9]{32}", limits).is_ok());
+        assert!(compile_user_pattern("wide", r"[A-Za-z0-9]{64}", limits).is_ok());
+
+        let err = compile_user_pattern("huge", r"\w{1000}\w{1000}", limits).unwrap_err().to_string();
+        assert!(err.contains("rule 'huge'") && err.contains("--regex-size-limit"), "{}", err);
+
+        let tight = RegexLimits { size_limit: 4096, ..limits };
+        assert!(compile_user_pattern("wide", r"[A-Za-z0-9]{64}", tight).is_err());
+    }
+
+    #[test]
+    fn test_invalid_pattern_names_rule() {
+        let err = compile_user_pattern("broken", r"(unclosed", RegexLimits::default()).unwrap_err().to_string();
+        assert!(err.contains("Invalid pattern for rule 'broken'"), "{}", err);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::utils::report::{Finding, Severity};
+
+/// A `--severity-clamp` entry: findings whose category or scanner source is `scope`
+/// are kept within `[floor, ceiling]`. Either bound may be left open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeverityClamp {
+    pub scope: String,
+    pub floor: Option<Severity>,
+    pub ceiling: Option<Severity>,
+}
+
+/// Parse `SCOPE=FLOOR..CEILING`, e.g. `secret=high..`, `weak-crypto=..medium` or `pcap=low..high`
+pub fn parse_severity_clamp(spec: &str) -> Result<SeverityClamp, String> {
+    let (scope, range) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("expected SCOPE=FLOOR..CEILING, got '{}'", spec))?;
+    let (floor, ceiling) = range
+        .split_once("..")
+        .ok_or_else(|| format!("expected a FLOOR..CEILING range after '{}=', got '{}'", scope, range))?;
+
+    let bound = |level: &str| -> Result<Option<Severity>, String> {
+        if level.trim().is_empty() {
+            Ok(None)
+        } else {
+            level.parse().map(Some)
+        }
+    };
+    let clamp = SeverityClamp { scope: scope.trim().to_string(), floor: bound(floor)?, ceiling: bound(ceiling)? };
+
+    if clamp.scope.is_empty() {
+        return Err(format!("missing category or scanner before '=' in '{}'", spec));
+    }
+    if clamp.floor.is_none() && clamp.ceiling.is_none() {
+        return Err(format!("'{}' sets neither a floor nor a ceiling", spec));
+    }
+    if let (Some(floor), Some(ceiling)) = (clamp.floor, clamp.ceiling) {
+        if floor > ceiling {
+            return Err(format!("floor {} is above ceiling {} in '{}'", floor, ceiling, spec));
+        }
+    }
+    Ok(clamp)
+}
+
+impl SeverityClamp {
+    fn applies_to(&self, finding: &Finding) -> bool {
+        finding.category == self.scope || finding.source == self.scope
+    }
+
+    /// Clamp a finding into range. A floor also rates unrated findings, a ceiling leaves them unrated.
+    fn apply(&self, finding: &mut Finding) {
+        let original = finding.severity;
+        let mut severity = original;
+        if let Some(floor) = self.floor {
+            severity = Some(severity.map_or(floor, |s| s.max(floor)));
+        }
+        if let Some(ceiling) = self.ceiling {
+            severity = severity.map(|s| s.min(ceiling));
+        }
+
+        if severity != original {
+            finding.severity = severity;
+            if let Some(severity) = severity {
+                finding.context.push_str(&format!(" [severity clamped to {}: {} policy]", severity, self.scope));
+            }
+        }
+    }
+}
+
+/// Organization-wide severity bounds per category or scanner, applied after path-based adjustment
+#[derive(Debug, Clone, Default)]
+pub struct SeverityPolicy {
+    clamps: Vec<SeverityClamp>,
+}
+
+impl SeverityPolicy {
+    pub fn new(clamps: &[SeverityClamp]) -> Self {
+        SeverityPolicy { clamps: clamps.to_vec() }
+    }
+
+    /// Apply every matching clamp, in the order they were given
+    pub fn apply(&self, finding: &mut Finding) {
+        for clamp in &self.clamps {
+            if clamp.applies_to(finding) {
+                clamp.apply(finding);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_severity_clamp() {
+        assert_eq!(
+            parse_severity_clamp("secret=high..").unwrap(),
+            SeverityClamp { scope: "secret".to_string(), floor: Some(Severity::High), ceiling: None }
+        );
+        assert_eq!(parse_severity_clamp("weak-crypto=..Medium").unwrap().ceiling, Some(Severity::Medium));
+        assert!(parse_severity_clamp("secret=high").is_err());
+        assert!(parse_severity_clamp("secret=..").is_err());
+        assert!(parse_severity_clamp("secret=high..low").is_err());
+        assert!(parse_severity_clamp("secret=severe..").is_err());
+        assert!(parse_severity_clamp("=low..high").is_err());
+    }
+
+    #[test]
+    fn test_clamping_mixed_findings() {
+        let clamps = [
+            parse_severity_clamp("secret=high..").unwrap(),
+            parse_severity_clamp("weak-crypto=..medium").unwrap(),
+            parse_severity_clamp("pcap=low..high").unwrap(),
+        ];
+        let policy = SeverityPolicy::new(&clamps);
+
+        let mut findings = vec![
+            Finding {
+                source: "hardcoded".to_string(),
+                severity: Some(Severity::Low),
+                ..Finding::for_test("src/app.py", 1, "Test", "secret")
+            },
+            Finding { source: "hardcoded".to_string(), ..Finding::for_test("src/app.py", 1, "Test", "secret") },
+            Finding {
+                source: "hardcoded".to_string(),
+                severity: Some(Severity::Critical),
+                ..Finding::for_test("src/app.py", 1, "Test", "secret")
+            },
+            Finding {
+                source: "weak-crypto".to_string(),
+                severity: Some(Severity::High),
+                ..Finding::for_test("src/app.py", 1, "Test", "weak-crypto")
+            },
+            Finding {
+                source: "weak-crypto".to_string(),
+                severity: Some(Severity::Info),
+                ..Finding::for_test("src/app.py", 1, "Test", "weak-crypto")
+            },
+            Finding {
+                source: "pcap".to_string(),
+                severity: Some(Severity::Critical),
+                ..Finding::for_test("src/app.py", 1, "Test", "plaintext-credential")
+            },
+            Finding { source: "import".to_string(), ..Finding::for_test("src/app.py", 1, "Test", "library") },
+        ];
+        for finding in &mut findings {
+            policy.apply(finding);
+        }
+
+        let severities: Vec<Option<Severity>> = findings.iter().map(|f| f.severity).collect();
+        assert_eq!(severities, vec![
+            Some(Severity::High),
+            Some(Severity::High),
+            Some(Severity::Critical),
+            Some(Severity::Medium),
+            Some(Severity::Info),
+            Some(Severity::High),
+            None,
+        ]);
+        assert!(findings[0].context.ends_with("[severity clamped to high: secret policy]"));
+        assert!(findings[2].context.is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+use crate::scanner::secrets::is_comment_line;
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::lang_ident::detect_language;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+/// Hardhat/Anvil account #0; published in their docs, so only a risk if reused on a live network
+const WELL_KNOWN_DEV_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Directories that hold deployment scripts in Hardhat, Foundry and Truffle projects
+const DEPLOYMENT_DIRS: &[&str] = &["scripts", "script", "deploy", "deployments", "migrations", "ignition"];
+
+/// Framework config files that carry deployer accounts
+const DEPLOYMENT_CONFIG_NAMES: &[&str] = &[
+    "hardhat.config.js", "hardhat.config.ts", "hardhat.config.cjs", "hardhat.config.mjs",
+    "truffle-config.js", "truffle.js",
+];
+
+lazy_static! {
+    /// Anti-patterns in Solidity/Vyper source: (pattern, name, description, severity)
+    static ref CONTRACT_PATTERNS: Vec<(Regex, &'static str, &'static str, Severity)> = vec![
+        (
+            Regex::new(r"\b(?:block\.)?blockhash\s*\(|\bblock\.(?:difficulty|prevrandao)\b").unwrap(),
+            "Block Data Randomness",
+            "Block hashes and prevrandao are known to (or chosen by) validators; use a VRF or commit-reveal for randomness",
+            Severity::High,
+        ),
+        (
+            Regex::new(r"(?i)\bblock\.timestamp\b.*(?:%|\b(?:rand|seed|lottery|winner|lucky))|(?:\b(?:keccak256|sha256|sha3)\s*\(|\b(?:rand|seed|lottery|winner|lucky)\w*\s*=).*\bblock\.timestamp\b").unwrap(),
+            "Timestamp Randomness",
+            "block.timestamp used as a randomness source; validators can shift it to pick the outcome",
+            Severity::High,
+        ),
+        (
+            Regex::new(r"\btx\.origin\s*[!=]=|[!=]=\s*tx\.origin\b").unwrap(),
+            "tx.origin Authorization",
+            "tx.origin used for authorization; any contract the owner calls can act on their behalf (use msg.sender)",
+            Severity::High,
+        ),
+        (
+            Regex::new(r"(?i)\bkeccak256\s*\([^;]*\)\s*\^|\^\s*keccak256\s*\(|\b\w*(?:encrypt|cipher|obfuscat)\w*\b.*\bkeccak256\s*\(").unwrap(),
+            "Hash-Based Encryption",
+            "keccak256 used to \"encrypt\" data; all contract storage and calldata is public and XOR with a hash is reversible",
+            Severity::Medium,
+        ),
+    ];
+
+    /// A 64-hex-digit private key literal assigned to a key/account name
+    static ref DEPLOYMENT_KEY: Regex = Regex::new(
+        r#"(?i)\b\w*(?:private_?key|priv_?key|deployer_?key|signer_?key|accounts)\w*\b.*?\b(?:0x)?([0-9a-f]{64})\b"#
+    ).unwrap();
+}
+
+/// Check whether a file is Solidity or Vyper source
+pub fn is_smart_contract_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| matches!(ext.to_lowercase().as_str(), "sol" | "vy"))
+}
+
+/// Check whether a file is a Hardhat/Foundry/Truffle deployment script or framework config
+pub fn is_deployment_script(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|f| f.to_str()).map(|f| f.to_lowercase()) else {
+        return false;
+    };
+    if DEPLOYMENT_CONFIG_NAMES.contains(&name.as_str()) || name.ends_with(".s.sol") {
+        return true;
+    }
+
+    let is_script = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| matches!(ext.to_lowercase().as_str(), "js" | "ts" | "mjs" | "cjs" | "sol" | "py"));
+    let in_deployment_dir = path.parent().is_some_and(|dir| {
+        dir.components().any(|c| DEPLOYMENT_DIRS.contains(&c.as_os_str().to_string_lossy().to_lowercase().as_str()))
+    });
+    is_script && in_deployment_dir
+}
+
+/// Scans a smart contract or deployment script for crypto anti-patterns
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded contract source or deployment script content
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let language = detect_language(path);
+    let is_contract = is_smart_contract_file(path);
+    let is_deployment = is_deployment_script(path);
+
+    let finding = |line_num: usize, line: &str, keyword: &str, context: String, severity: Severity| Finding {
+        file: path.display().to_string(),
+        line_number: line_num + 1,
+        end_line_number: None,
+        column_start: None,
+        column_end: None,
+        line_content: line.to_string(),
+        match_type: "smart-contract".to_string(),
+        keyword: keyword.to_string(),
+        context,
+        version: None,
+        language: language.clone(),
+        source: "smart-contract".to_string(),
+        category: "smart-contract-crypto".to_string(),
+        severity: Some(severity),
+        file_hash: None,
+        cwe: None,
+        remediation: None,
+    };
+
+    for (line_num, line) in content.lines().enumerate() {
+        if is_comment_line(line) {
+            continue;
+        }
+
+        if is_contract {
+            for (regex, name, description, severity) in CONTRACT_PATTERNS.iter() {
+                // `tx.origin == msg.sender` only rejects contract callers; it doesn't authorize anyone
+                if name.starts_with("tx.origin") && line.contains("msg.sender") {
+                    continue;
+                }
+                if regex.is_match(line) {
+                    findings.push(finding(line_num, line, name, description.to_string(), *severity));
+                }
+            }
+        }
+
+        if is_deployment {
+            if let Some(key) = DEPLOYMENT_KEY.captures(line).and_then(|c| c.get(1)) {
+                let (context, severity) = if key.as_str().eq_ignore_ascii_case(WELL_KNOWN_DEV_KEY) {
+                    ("Well-known Hardhat/Anvil development key; never fund it or use it on a live network", Severity::Low)
+                } else {
+                    ("Deployer private key hardcoded in a deployment script; load it from the environment or a keystore", Severity::Critical)
+                };
+                findings.push(finding(line_num, line, "Hardcoded Deployer Key", context.to_string(), severity));
+            }
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn keywords(findings: &[Finding]) -> Vec<(usize, &str)> {
+        findings.iter().map(|f| (f.line_number, f.keyword.as_str())).collect()
+    }
+
+    #[test]
+    fn test_file_recognition() {
+        assert!(is_smart_contract_file(&PathBuf::from("contracts/Lottery.sol")));
+        assert!(is_smart_contract_file(&PathBuf::from("contracts/vault.vy")));
+        assert!(is_deployment_script(&PathBuf::from("hardhat.config.ts")));
+        assert!(is_deployment_script(&PathBuf::from("script/Deploy.s.sol")));
+        assert!(is_deployment_script(&PathBuf::from("scripts/deploy.js")));
+        assert!(!is_deployment_script(&PathBuf::from("src/index.js")));
+        assert!(!is_deployment_script(&PathBuf::from("contracts/Token.sol")));
+    }
+
+    #[test]
+    fn test_block_randomness() {
+        let content = r#"contract Lottery {
+    function draw() external {
+        uint256 winnerIndex = uint256(keccak256(abi.encodePacked(block.timestamp, msg.sender))) % players.length;
+        uint256 seed = uint256(blockhash(block.number - 1));
+        uint256 r = block.prevrandao;
+        require(block.timestamp >= deadline, "too early");
+        // uint256 old = uint256(blockhash(block.number - 1));
+    }
+}"#;
+        let findings = scan_content(Path::new("contracts/Lottery.sol"), content);
+
+        assert_eq!(keywords(&findings), vec![
+            (3, "Timestamp Randomness"),
+            (4, "Block Data Randomness"),
+            (5, "Block Data Randomness"),
+        ]);
+        assert!(findings.iter().all(|f| f.category == "smart-contract-crypto" && f.severity == Some(Severity::High)));
+        assert_eq!(findings[0].language, "Solidity");
+    }
+
+    #[test]
+    fn test_tx_origin_authorization() {
+        let solidity = r#"function withdraw() public {
+    require(tx.origin == owner, "not owner");
+    require(tx.origin == msg.sender, "no contracts");
+    emit Called(tx.origin);
+}"#;
+        let findings = scan_content(Path::new("Wallet.sol"), solidity);
+        assert_eq!(keywords(&findings), vec![(2, "tx.origin Authorization")]);
+
+        let vyper = "@external\ndef withdraw():\n    assert tx.origin == self.owner\n";
+        let findings = scan_content(Path::new("wallet.vy"), vyper);
+        assert_eq!(keywords(&findings), vec![(3, "tx.origin Authorization")]);
+        assert_eq!(findings[0].language, "Vyper");
+    }
+
+    #[test]
+    fn test_hash_based_encryption() {
+        let content = "bytes32 encrypted = keccak256(abi.encodePacked(secret)) ^ data;\nbytes32 id = keccak256(abi.encodePacked(name));\n";
+        let findings = scan_content(Path::new("Vault.sol"), content);
+        assert_eq!(keywords(&findings), vec![(1, "Hash-Based Encryption")]);
+        assert_eq!(findings[0].severity, Some(Severity::Medium));
+    }
+
+    #[test]
+    fn test_deployment_keys() {
+        let content = r#"module.exports = {
+  networks: {
+    mainnet: { url: RPC_URL, accounts: ["0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"] },
+    local: { accounts: ["0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"] },
+    sepolia: { accounts: [process.env.PRIVATE_KEY] },
+  },
+};"#;
+        let findings = scan_content(Path::new("hardhat.config.js"), content);
+
+        assert_eq!(keywords(&findings), vec![(3, "Hardcoded Deployer Key"), (4, "Hardcoded Deployer Key")]);
+        assert_eq!(findings[0].severity, Some(Severity::Critical));
+        assert_eq!(findings[1].severity, Some(Severity::Low));
+
+        // The same line outside a deployment script is left to the generic secret scan
+        assert!(scan_content(Path::new("src/app.js"), content).is_empty());
+    }
+}
@@ -0,0 +1,284 @@
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::report::{Finding, Severity};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::path::Path;
+
+/// OpenSSH key lists, matched by file name
+const AUTHORIZED_KEYS_NAMES: &[&str] = &["authorized_keys", "authorized_keys2"];
+const KNOWN_HOSTS_NAMES: &[&str] = &["known_hosts", "known_hosts2", "ssh_known_hosts"];
+
+/// RSA moduli below this many bits are reported as weak
+const MIN_RSA_BITS: usize = 2048;
+
+/// One parsed `authorized_keys` or `known_hosts` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshKeyEntry {
+    /// `authorized_keys` options (`from="...",no-pty`) or `known_hosts` marker and host patterns
+    pub prefix: Option<String>,
+    /// Key type, e.g. `ssh-ed25519` or `ecdsa-sha2-nistp256`
+    pub key_type: String,
+    /// RSA modulus size in bits, when the key blob could be decoded
+    pub bits: Option<usize>,
+    /// Free-form comment after the key, usually `user@host`
+    pub comment: Option<String>,
+}
+
+impl SshKeyEntry {
+    /// Why this key is considered weak, if it is
+    pub fn weakness(&self) -> Option<String> {
+        if self.key_type.starts_with("ssh-dss") {
+            return Some("DSA keys are limited to 1024 bits and disabled by default since OpenSSH 7.0".to_string());
+        }
+        match self.bits {
+            Some(bits) if self.key_type.starts_with("ssh-rsa") && bits < MIN_RSA_BITS => {
+                Some(format!("{}-bit RSA key is below the {}-bit minimum", bits, MIN_RSA_BITS))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Check whether a file is an `authorized_keys` or `known_hosts` list by name
+pub fn is_ssh_key_list(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|f| f.to_str())
+        .is_some_and(|name| AUTHORIZED_KEYS_NAMES.contains(&name) || KNOWN_HOSTS_NAMES.contains(&name))
+}
+
+fn is_known_hosts(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|f| f.to_str())
+        .is_some_and(|name| KNOWN_HOSTS_NAMES.contains(&name))
+}
+
+/// Split a line on whitespace outside double quotes, returning each token's byte range
+fn tokens(line: &str) -> Vec<(usize, usize)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        if start.is_none() {
+            if c.is_whitespace() {
+                continue;
+            }
+            start = Some(i);
+        }
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c.is_whitespace() && !in_quotes {
+            tokens.push((start.take().unwrap_or(i), i));
+        }
+    }
+    if let Some(start) = start {
+        tokens.push((start, line.len()));
+    }
+    tokens
+}
+
+/// Whether a token names an SSH public key algorithm
+fn is_key_type(token: &str) -> bool {
+    token.starts_with("ssh-") || token.starts_with("ecdsa-sha2-") || token.starts_with("sk-")
+}
+
+/// Read one length-prefixed SSH wire string, returning it and the remainder
+fn read_string(blob: &[u8]) -> Option<(&[u8], &[u8])> {
+    let len = u32::from_be_bytes(blob.get(..4)?.try_into().ok()?) as usize;
+    let rest = &blob[4..];
+    (rest.len() >= len).then(|| rest.split_at(len))
+}
+
+/// Modulus size of a base64 `ssh-rsa` key blob
+fn rsa_modulus_bits(encoded: &str) -> Option<usize> {
+    let blob = STANDARD.decode(encoded).ok()?;
+    let (name, rest) = read_string(&blob)?;
+    if name != b"ssh-rsa" {
+        return None;
+    }
+    let (_exponent, rest) = read_string(rest)?;
+    let (modulus, _) = read_string(rest)?;
+
+    let significant = modulus.iter().position(|&b| b != 0).map(|i| &modulus[i..])?;
+    Some(significant.len() * 8 - significant[0].leading_zeros() as usize)
+}
+
+/// Parse an `[options|hosts] key-type base64 [comment]` line.
+///
+/// The key type is located by scanning for the first token that names a key algorithm and is
+/// followed by a base64 blob, so quoted options containing spaces don't shift the fields.
+pub fn parse_entry(line: &str) -> Option<SshKeyEntry> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+
+    let tokens = tokens(trimmed);
+    let key_index = tokens.iter().enumerate().position(|(i, &(start, end))| {
+        is_key_type(&trimmed[start..end])
+            && tokens.get(i + 1).is_some_and(|&(s, e)| trimmed[s..e].starts_with("AAAA"))
+    })?;
+
+    let (type_start, type_end) = tokens[key_index];
+    let (blob_start, blob_end) = tokens[key_index + 1];
+    let key_type = trimmed[type_start..type_end].to_string();
+
+    let prefix = trimmed[..type_start].trim();
+    let comment = trimmed[blob_end..].trim();
+    let bits = if key_type == "ssh-rsa" {
+        rsa_modulus_bits(&trimmed[blob_start..blob_end])
+    } else {
+        None
+    };
+
+    Some(SshKeyEntry {
+        prefix: (!prefix.is_empty()).then(|| prefix.to_string()),
+        key_type,
+        bits,
+        comment: (!comment.is_empty()).then(|| comment.to_string()),
+    })
+}
+
+/// Scans an `authorized_keys` or `known_hosts` file for key inventory and weak keys
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded key list content; every entry is reported, weak ones at High severity
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    let (match_type, prefix_label) = if is_known_hosts(path) {
+        ("known-host", "hosts")
+    } else {
+        ("authorized-key", "options")
+    };
+
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(line_num, line)| parse_entry(line).map(|entry| (line_num, line, entry)))
+        .map(|(line_num, line, entry)| {
+            let mut details = vec![match entry.bits {
+                Some(bits) => format!("{} {}-bit key", entry.key_type, bits),
+                None => format!("{} key", entry.key_type),
+            }];
+            if let Some(comment) = &entry.comment {
+                details.push(format!("comment: {}", comment));
+            }
+            if let Some(prefix) = &entry.prefix {
+                details.push(format!("{}: {}", prefix_label, prefix));
+            }
+
+            let weakness = entry.weakness();
+            let context = match &weakness {
+                Some(reason) => format!("Weak SSH key: {}; {}", reason, details.join("; ")),
+                None => details.join("; "),
+            };
+
+            Finding {
+                file: path.display().to_string(),
+                line_number: line_num + 1,
+                end_line_number: None,
+                column_start: None,
+                column_end: None,
+                line_content: line.to_string(),
+                match_type: match_type.to_string(),
+                keyword: entry.key_type,
+                context,
+                version: entry.bits.map(|bits| bits.to_string()),
+                language: "SSH".to_string(),
+                source: "ssh-key-list".to_string(),
+                category: "ssh-key-material".to_string(),
+                severity: Some(if weakness.is_some() { Severity::High } else { Severity::Info }),
+                file_hash: None,
+                cwe: None,
+                remediation: None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Base64 `ssh-rsa` blob with a modulus of exactly `bits` bits
+    fn rsa_blob(bits: usize) -> String {
+        let mut blob = Vec::new();
+        let mut push = |bytes: &[u8]| {
+            blob.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+            blob.extend_from_slice(bytes);
+        };
+        let mut modulus = vec![0u8; bits / 8 + 1];
+        modulus[1] = 0x80;
+        push(b"ssh-rsa");
+        push(&[0x01, 0x00, 0x01]);
+        push(&modulus);
+        STANDARD.encode(blob)
+    }
+
+    #[test]
+    fn test_key_list_recognition() {
+        assert!(is_ssh_key_list(&PathBuf::from("home/alice/.ssh/authorized_keys")));
+        assert!(is_ssh_key_list(&PathBuf::from(".ssh/known_hosts")));
+        assert!(!is_ssh_key_list(&PathBuf::from(".ssh/id_rsa.pub")));
+    }
+
+    #[test]
+    fn test_mixed_authorized_keys() {
+        let content = format!(
+            "# deploy keys\n\
+             ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIGq1 alice@laptop\n\
+             command=\"/usr/bin/rsync --server -e.Lsfx . /backup\",no-pty,from=\"10.0.0.0/8\" ssh-rsa {} backup bot\n\
+             ssh-dss AAAAB3NzaC1kc3MAAACBAP\n\
+             ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTY=\n\
+             ssh-rsa {} ci@build\n",
+            rsa_blob(4096),
+            rsa_blob(1024),
+        );
+        let findings = scan_content(Path::new(".ssh/authorized_keys"), &content);
+        let summary: Vec<(usize, &str, Option<Severity>)> = findings
+            .iter()
+            .map(|f| (f.line_number, f.keyword.as_str(), f.severity))
+            .collect();
+
+        assert_eq!(summary, vec![
+            (2, "ssh-ed25519", Some(Severity::Info)),
+            (3, "ssh-rsa", Some(Severity::Info)),
+            (4, "ssh-dss", Some(Severity::High)),
+            (5, "ecdsa-sha2-nistp256", Some(Severity::Info)),
+            (6, "ssh-rsa", Some(Severity::High)),
+        ]);
+        assert!(findings.iter().all(|f| f.category == "ssh-key-material" && f.match_type == "authorized-key"));
+
+        let restricted = parse_entry(content.lines().nth(2).unwrap()).unwrap();
+        assert_eq!(
+            restricted.prefix.as_deref(),
+            Some("command=\"/usr/bin/rsync --server -e.Lsfx . /backup\",no-pty,from=\"10.0.0.0/8\"")
+        );
+        assert_eq!(restricted.bits, Some(4096));
+        assert_eq!(restricted.comment.as_deref(), Some("backup bot"));
+        assert!(findings[4].context.contains("1024-bit RSA key"));
+    }
+
+    #[test]
+    fn test_known_hosts_entries() {
+        let content = "|1|JfKTdBh7rNbXkVAQCRp4OQoPfmI=|USECr3SWf1JUPsms5AqfD5QfxkM= ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIOMq\n\
+                       @revoked bastion.example.com,10.0.0.5 ssh-dss AAAAB3NzaC1kc3MAAACBAP\n";
+        let findings = scan_content(Path::new("known_hosts"), content);
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].match_type, "known-host");
+        assert!(findings[0].context.contains("hosts: |1|"));
+        assert!(findings[1].context.contains("hosts: @revoked bastion.example.com,10.0.0.5"));
+        assert_eq!(findings[1].severity, Some(Severity::High));
+    }
+}
@@ -0,0 +1,106 @@
+//! `.cryptoscanignore`: repo-level suppressions of known-safe findings.
+//!
+//! Each non-empty line that isn't a `#` comment is `file:line` or `file:rule`, with `file`
+//! relative to the scan root and `rule` a rule id (`secret/github-token`) or a whole
+//! category (`secret`).
+
+use crate::error::{config_error, ScanResult};
+use crate::utils::report::Finding;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File name looked for at the scan root
+pub const IGNORE_FILE: &str = ".cryptoscanignore";
+
+/// Suppressions loaded from a `.cryptoscanignore`
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    root: PathBuf,
+    lines: HashSet<(String, usize)>,
+    rules: HashSet<(String, String)>,
+}
+
+/// Normalize a relative path for comparison: forward slashes, no leading `./`
+pub(crate) fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.trim_start_matches("./").to_string()
+}
+
+impl Suppressions {
+    /// Load `.cryptoscanignore` from `root`; no file means no suppressions
+    pub fn load(root: &Path) -> ScanResult<Self> {
+        let path = root.join(IGNORE_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(root, &content)
+                .map_err(|e| config_error(&format!("Invalid {}: {}", path.display(), e))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Suppressions::default()),
+            Err(e) => Err(config_error(&format!("Cannot read {}: {}", path.display(), e))),
+        }
+    }
+
+    /// Parse `.cryptoscanignore` content for a scan of `root`
+    pub fn parse(root: &Path, content: &str) -> Result<Self, String> {
+        let mut suppressions = Suppressions { root: root.to_path_buf(), ..Default::default() };
+        for (i, entry) in content.lines().enumerate() {
+            let entry = entry.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+            let Some((file, target)) = entry.rsplit_once(':').filter(|(f, t)| !f.trim().is_empty() && !t.trim().is_empty()) else {
+                return Err(format!("line {}: expected `file:line` or `file:rule`, got `{}`", i + 1, entry));
+            };
+            let (file, target) = (normalize(file.trim()), target.trim());
+            match target.parse::<usize>() {
+                Ok(line) => suppressions.lines.insert((file, line)),
+                Err(_) => suppressions.rules.insert((file, target.to_lowercase())),
+            };
+        }
+        Ok(suppressions)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty() && self.rules.is_empty()
+    }
+
+    /// Whether an entry covers this finding
+    pub fn is_suppressed(&self, finding: &Finding) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let path = Path::new(&finding.file);
+        let relative = normalize(&path.strip_prefix(&self.root).unwrap_or(path).to_string_lossy());
+        self.lines.contains(&(relative.clone(), finding.line_number))
+            || self.rules.contains(&(relative.clone(), finding.rule_id()))
+            || self.rules.contains(&(relative, finding.category.to_lowercase()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_rule_and_category_entries() {
+        let suppressions = Suppressions::parse(Path::new("/repo"), r#"
+# Test fixtures
+tests/fixtures/keys.py:12
+./config/settings.py:secret/github-token
+docs/examples.py:weak-crypto
+"#).unwrap();
+
+        assert!(suppressions.is_suppressed(&Finding::for_test("/repo/tests/fixtures/keys.py", 12, "API Key", "secret")));
+        assert!(!suppressions.is_suppressed(&Finding::for_test("/repo/tests/fixtures/keys.py", 13, "API Key", "secret")));
+        assert!(suppressions.is_suppressed(&Finding::for_test("/repo/config/settings.py", 3, "GitHub Token", "secret")));
+        assert!(!suppressions.is_suppressed(&Finding::for_test("/repo/config/settings.py", 4, "AWS Access Key", "secret")));
+        assert!(suppressions.is_suppressed(&Finding::for_test("/repo/docs/examples.py", 7, "MD5", "weak-crypto")));
+        assert!(!suppressions.is_suppressed(&Finding::for_test("/repo/src/examples.py", 7, "MD5", "weak-crypto")));
+    }
+
+    #[test]
+    fn test_malformed_entry_rejected() {
+        let err = Suppressions::parse(Path::new("/repo"), "src/app.py:3\nsrc/app.py\n").unwrap_err();
+        assert!(err.contains("line 2"), "{}", err);
+    }
+}
@@ -0,0 +1,76 @@
+//! Where a scan's time goes: the walk, the parallel scan as a whole, and the time spent
+//! inside each group of scanners. Scanner time is summed over every worker thread, so on
+//! a multi-core scan it can add up to more than the wall-clock scan time.
+
+use crate::utils::summary::ScanTimings;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// A group of scanners whose time is reported together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScannerGroup {
+    /// Libraries, algorithm usage and the other crypto-in-code checks
+    Code,
+    /// Secret patterns and the secret scanners for config, Dockerfiles, lockfiles and history
+    Secrets,
+    /// Keystores, SSH key lists, packet captures and binaries
+    Artefacts,
+}
+
+/// Scanner time accumulated across the threads of one scan
+#[derive(Debug, Default)]
+pub struct ScannerTimes {
+    code_ns: AtomicU64,
+    secrets_ns: AtomicU64,
+    artefacts_ns: AtomicU64,
+}
+
+impl ScannerTimes {
+    /// Run `scan`, counting its time towards `group`
+    pub fn time<T>(&self, group: ScannerGroup, scan: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = scan();
+        self.add(group, started.elapsed());
+        result
+    }
+
+    pub fn add(&self, group: ScannerGroup, elapsed: Duration) {
+        let counter = match group {
+            ScannerGroup::Code => &self.code_ns,
+            ScannerGroup::Secrets => &self.secrets_ns,
+            ScannerGroup::Artefacts => &self.artefacts_ns,
+        };
+        counter.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// The totals so far, with the walk and wall-clock scan times measured by the caller
+    pub fn timings(&self, walk: Duration, scan: Duration) -> ScanTimings {
+        let ms = |ns: &AtomicU64| ns.load(Ordering::Relaxed) / 1_000_000;
+        ScanTimings {
+            walk_ms: walk.as_millis() as u64,
+            scan_ms: scan.as_millis() as u64,
+            code_ms: ms(&self.code_ns),
+            secrets_ms: ms(&self.secrets_ns),
+            artefacts_ms: ms(&self.artefacts_ns),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_accumulate_separately() {
+        let times = ScannerTimes::default();
+        assert_eq!(times.time(ScannerGroup::Secrets, || 7), 7);
+        times.add(ScannerGroup::Code, Duration::from_millis(1500));
+        times.add(ScannerGroup::Code, Duration::from_millis(500));
+        times.add(ScannerGroup::Artefacts, Duration::from_micros(900));
+
+        let timings = times.timings(Duration::from_millis(40), Duration::from_secs(3));
+        assert_eq!((timings.walk_ms, timings.scan_ms), (40, 3000));
+        assert_eq!((timings.code_ms, timings.artefacts_ms), (2000, 0));
+        assert!(timings.secrets_ms < 1000);
+    }
+}
@@ -0,0 +1,420 @@
+//! `--check-vulnerable-deps`: crypto dependencies pinned to versions with known advisories.
+//!
+//! Exact versions are read from lockfiles and pinned manifests (npm, PyPI, Cargo, Conan)
+//! and compared against a small built-in advisory list. `--vuln-db` adds advisories from
+//! a JSON file and `--vuln-db-url` fetches more with `curl`; nothing is fetched otherwise.
+
+use crate::error::{config_error, ScanResult};
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// A known-vulnerable version range of one package.
+/// Affected versions are `>= introduced` and `< fixed` (or `<= last_affected` when there is no fix).
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct Advisory {
+    /// `npm`, `PyPI`, `crates.io` or `ConanCenter`
+    pub ecosystem: String,
+    pub package: String,
+    #[serde(default)]
+    pub introduced: Option<String>,
+    #[serde(default)]
+    pub fixed: Option<String>,
+    #[serde(default)]
+    pub last_affected: Option<String>,
+    /// CVE (or other advisory) identifier
+    pub id: String,
+    pub summary: String,
+    pub severity: Severity,
+}
+
+/// (ecosystem, package, introduced, fixed, last_affected, id, summary, severity)
+type EmbeddedAdvisory = (
+    &'static str,
+    &'static str,
+    Option<&'static str>,
+    Option<&'static str>,
+    Option<&'static str>,
+    &'static str,
+    &'static str,
+    Severity,
+);
+
+/// Built-in advisories for widely used crypto libraries, available offline
+const EMBEDDED_ADVISORIES: &[EmbeddedAdvisory] = &[
+    ("npm", "jsonwebtoken", None, Some("9.0.0"), None, "CVE-2022-23540",
+     "jwt.verify() accepts unsigned tokens through an insecure default algorithm", Severity::High),
+    ("npm", "node-forge", None, Some("1.3.0"), None, "CVE-2022-24771",
+     "RSA PKCS#1 v1.5 signature verification is lenient about the digest structure", Severity::High),
+    ("npm", "elliptic", None, Some("6.5.4"), None, "CVE-2020-28498",
+     "ECDH with invalid public keys can leak the secp256k1 private key", Severity::Medium),
+    ("PyPI", "cryptography", Some("3.1"), Some("3.3.2"), None, "CVE-2020-36242",
+     "Integer and buffer overflow when symmetrically encrypting multi-gigabyte values", Severity::Critical),
+    ("PyPI", "pycrypto", None, None, Some("2.6.1"), "CVE-2013-7459",
+     "Heap buffer overflow in AES.new with a crafted IV; the package is unmaintained", Severity::Critical),
+    ("PyPI", "pyjwt", Some("1.5.0"), Some("2.4.0"), None, "CVE-2022-29217",
+     "Algorithm confusion lets attackers choose the verification algorithm", Severity::High),
+    ("crates.io", "rsa", None, None, None, "CVE-2023-49092",
+     "Marvin attack: decryption timing leaks enough to recover the private key", Severity::Medium),
+    ("ConanCenter", "openssl", Some("1.0.1"), Some("1.0.1g"), None, "CVE-2014-0160",
+     "Heartbleed: TLS heartbeat over-read discloses process memory, including private keys", Severity::High),
+];
+
+/// Advisories to check dependencies against
+#[derive(Debug, Clone, Default)]
+pub struct AdvisoryDb {
+    advisories: Vec<Advisory>,
+}
+
+impl AdvisoryDb {
+    /// The built-in advisories only
+    pub fn embedded() -> Self {
+        let advisories = EMBEDDED_ADVISORIES
+            .iter()
+            .map(|&(ecosystem, package, introduced, fixed, last_affected, id, summary, severity)| Advisory {
+                ecosystem: ecosystem.to_string(),
+                package: package.to_string(),
+                introduced: introduced.map(String::from),
+                fixed: fixed.map(String::from),
+                last_affected: last_affected.map(String::from),
+                id: id.to_string(),
+                summary: summary.to_string(),
+                severity,
+            })
+            .collect();
+        AdvisoryDb { advisories }
+    }
+
+    /// Built-in advisories plus those in the `--vuln-db` file and at `--vuln-db-url`
+    pub fn load(file: Option<&str>, url: Option<&str>) -> ScanResult<Self> {
+        let mut db = AdvisoryDb::embedded();
+        if let Some(file) = file {
+            let json = fs::read_to_string(file)
+                .map_err(|e| config_error(&format!("Cannot read --vuln-db {}: {}", file, e)))?;
+            db.extend_from_json(&json, file)?;
+        }
+        if let Some(url) = url {
+            db.extend_from_json(&fetch(url)?, url)?;
+        }
+        Ok(db)
+    }
+
+    /// Add advisories from a JSON array; `origin` names the source in errors
+    fn extend_from_json(&mut self, json: &str, origin: &str) -> ScanResult<()> {
+        let advisories: Vec<Advisory> = serde_json::from_str(json)
+            .map_err(|e| config_error(&format!("Invalid advisory list {}: {}", origin, e)))?;
+        self.advisories.extend(advisories);
+        Ok(())
+    }
+
+    /// Advisories affecting `package` at `version`
+    fn matching<'a>(&'a self, dependency: &'a Dependency) -> impl Iterator<Item = &'a Advisory> {
+        self.advisories.iter().filter(move |advisory| {
+            advisory.ecosystem.eq_ignore_ascii_case(dependency.ecosystem)
+                && normalize_package(&advisory.package) == dependency.name
+                && advisory.affects(&dependency.version)
+        })
+    }
+}
+
+impl Advisory {
+    fn affects(&self, version: &str) -> bool {
+        let at_least = |bound: &Option<String>| bound.as_deref().is_none_or(|b| compare_versions(version, b) != Ordering::Less);
+        let below = |bound: &Option<String>| bound.as_deref().is_none_or(|b| compare_versions(version, b) == Ordering::Less);
+        let at_most = |bound: &Option<String>| bound.as_deref().is_none_or(|b| compare_versions(version, b) != Ordering::Greater);
+        at_least(&self.introduced) && below(&self.fixed) && at_most(&self.last_affected)
+    }
+}
+
+/// Download an advisory list. `curl` handles HTTPS, which the built-in report upload does not.
+fn fetch(url: &str) -> ScanResult<String> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location", "--max-time", "30", "--"])
+        .arg(url)
+        .output()
+        .map_err(|e| config_error(&format!("Cannot run curl to fetch --vuln-db-url {}: {}", url, e)))?;
+    if !output.status.success() {
+        return Err(config_error(&format!(
+            "Fetching --vuln-db-url {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Compare dotted versions numerically, with letter suffixes (`1.0.1f`) ordered after the bare number.
+/// Pre-release and build suffixes (`-rc1`, `+meta`) are ignored.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn parts(version: &str) -> Vec<(u64, String)> {
+        let release = version.trim().trim_start_matches(['v', '=']).split(['-', '+']).next().unwrap_or("");
+        release
+            .split('.')
+            .map(|part| {
+                let digits = part.len() - part.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+                (part[..digits].parse().unwrap_or(0), part[digits..].to_lowercase())
+            })
+            .collect()
+    }
+
+    let (a, b) = (parts(a), parts(b));
+    let zero = (0, String::new());
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&zero).cmp(b.get(i).unwrap_or(&zero)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// PyPI treats `_`, `.` and `-` alike and ignores case
+pub(crate) fn normalize_package(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
+/// A dependency pinned to an exact version
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Dependency {
+    ecosystem: &'static str,
+    name: String,
+    version: String,
+    /// Zero-based line the dependency is declared on
+    line: usize,
+}
+
+lazy_static! {
+    static ref REQUIREMENT_PIN: Regex =
+        Regex::new(r"^\s*([A-Za-z0-9][A-Za-z0-9_.\-]*)(?:\[[^\]]*\])?\s*===?\s*([A-Za-z0-9_.\-+!]+)").unwrap();
+    static ref TOML_NAME: Regex = Regex::new(r#"^name\s*=\s*"([^"]+)""#).unwrap();
+    static ref TOML_VERSION: Regex = Regex::new(r#"^version\s*=\s*"([^"]+)""#).unwrap();
+    static ref JSON_OBJECT_KEY: Regex = Regex::new(r#"^\s*"([^"]+)":\s*\{"#).unwrap();
+    static ref JSON_VERSION: Regex = Regex::new(r#"^\s*"version":\s*"=*([^"]+)""#).unwrap();
+    static ref YARN_ENTRY: Regex = Regex::new(r#"^"?(@?[^@\s"]+)@"#).unwrap();
+    static ref YARN_VERSION: Regex = Regex::new(r#"^\s+version:?\s+"?([^"\s]+)"?"#).unwrap();
+    static ref CONAN_REFERENCE: Regex = Regex::new(r"^\s*([A-Za-z0-9_.\-]+)/([A-Za-z0-9_.\-]+)").unwrap();
+}
+
+/// Files dependency versions are read from
+pub fn is_dependency_file(path: &Path) -> bool {
+    dependency_format(path).is_some()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Requirements,
+    PoetryLock,
+    PipfileLock,
+    CargoLock,
+    PackageLock,
+    YarnLock,
+    Conanfile,
+}
+
+fn dependency_format(path: &Path) -> Option<Format> {
+    let name = path.file_name()?.to_str()?.to_lowercase();
+    match name.as_str() {
+        "poetry.lock" => Some(Format::PoetryLock),
+        "pipfile.lock" => Some(Format::PipfileLock),
+        "cargo.lock" => Some(Format::CargoLock),
+        "package-lock.json" | "npm-shrinkwrap.json" => Some(Format::PackageLock),
+        "yarn.lock" => Some(Format::YarnLock),
+        "conanfile.txt" => Some(Format::Conanfile),
+        _ if name.starts_with("requirements") && name.ends_with(".txt") => Some(Format::Requirements),
+        _ => None,
+    }
+}
+
+/// Entries whose name is on an `anchor` line and whose version follows within a few lines,
+/// before the next entry starts
+fn anchored_dependencies(lines: &[&str], ecosystem: &'static str, anchor: &Regex, version: &Regex) -> Vec<Dependency> {
+    const LOOKAHEAD: usize = 8;
+    let mut dependencies = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let Some(name) = anchor.captures(line).map(|c| c[1].to_string()) else {
+            continue;
+        };
+        let found = lines
+            .iter()
+            .skip(index + 1)
+            .take(LOOKAHEAD)
+            .take_while(|next| !anchor.is_match(next))
+            .find_map(|next| version.captures(next).map(|c| c[1].to_string()));
+        if let Some(found) = found {
+            dependencies.push(Dependency { ecosystem, name, version: found, line: index });
+        }
+    }
+    dependencies
+}
+
+fn dependencies(format: Format, content: &str) -> Vec<Dependency> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut dependencies = match format {
+        Format::Requirements => lines
+            .iter()
+            .enumerate()
+            .filter_map(|(line, text)| {
+                let pin = REQUIREMENT_PIN.captures(text)?;
+                Some(Dependency { ecosystem: "PyPI", name: pin[1].to_string(), version: pin[2].to_string(), line })
+            })
+            .collect(),
+        Format::PoetryLock => anchored_dependencies(&lines, "PyPI", &TOML_NAME, &TOML_VERSION),
+        Format::CargoLock => anchored_dependencies(&lines, "crates.io", &TOML_NAME, &TOML_VERSION),
+        Format::PipfileLock => anchored_dependencies(&lines, "PyPI", &JSON_OBJECT_KEY, &JSON_VERSION),
+        Format::PackageLock => {
+            let mut found = anchored_dependencies(&lines, "npm", &JSON_OBJECT_KEY, &JSON_VERSION);
+            // lockfileVersion 2/3 keys are install paths: node_modules/a/node_modules/b
+            for dependency in &mut found {
+                if let Some((_, name)) = dependency.name.rsplit_once("node_modules/") {
+                    dependency.name = name.to_string();
+                }
+            }
+            found
+        }
+        Format::YarnLock => anchored_dependencies(&lines, "npm", &YARN_ENTRY, &YARN_VERSION),
+        Format::Conanfile => lines
+            .iter()
+            .enumerate()
+            .filter_map(|(line, text)| {
+                let reference = CONAN_REFERENCE.captures(text)?;
+                Some(Dependency {
+                    ecosystem: "ConanCenter",
+                    name: reference[1].to_string(),
+                    version: reference[2].to_string(),
+                    line,
+                })
+            })
+            .collect(),
+    };
+    for dependency in &mut dependencies {
+        dependency.name = normalize_package(&dependency.name);
+    }
+    dependencies
+}
+
+/// `(ecosystem, normalized name, version)` of each dependency pinned in a lockfile or pinned manifest
+pub(crate) fn pinned_versions(path: &Path, content: &str) -> Vec<(&'static str, String, String)> {
+    let Some(format) = dependency_format(path) else {
+        return Vec::new();
+    };
+    dependencies(format, content).into_iter().map(|d| (d.ecosystem, d.name, d.version)).collect()
+}
+
+fn language(ecosystem: &str) -> &'static str {
+    match ecosystem {
+        "npm" => "JavaScript",
+        "PyPI" => "Python",
+        "crates.io" => "Rust",
+        "ConanCenter" => "C/C++",
+        _ => "Unknown",
+    }
+}
+
+/// Report dependencies in `content` whose pinned versions are covered by an advisory in `db`
+pub fn scan_content(path: &Path, content: &str, db: &AdvisoryDb) -> Vec<Finding> {
+    let Some(format) = dependency_format(path) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = content.lines().collect();
+
+    let mut findings = Vec::new();
+    for dependency in dependencies(format, content) {
+        for advisory in db.matching(&dependency) {
+            let fix = match &advisory.fixed {
+                Some(fixed) => format!("fixed in {}", fixed),
+                None => "no fixed version".to_string(),
+            };
+            findings.push(Finding {
+                file: path.display().to_string(),
+                line_number: dependency.line + 1,
+                end_line_number: None,
+                column_start: None,
+                column_end: None,
+                line_content: lines.get(dependency.line).map_or_else(String::new, |line| line.to_string()),
+                match_type: "dependency".to_string(),
+                keyword: dependency.name.clone(),
+                context: format!("{}: {} ({} {}, {})", advisory.id, advisory.summary, dependency.name, dependency.version, fix),
+                version: Some(dependency.version.clone()),
+                language: language(dependency.ecosystem).to_string(),
+                source: "advisory".to_string(),
+                category: "vulnerable-crypto-dependency".to_string(),
+                severity: Some(advisory.severity),
+                file_hash: None,
+                cwe: None,
+                remediation: None,
+            });
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("8.5.1", "9.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("3.3", "3.3.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.10.0", "1.9.9"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.1f", "1.0.1g"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.1", "1.0.1a"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.2", "1.0.1g"), Ordering::Greater);
+        assert_eq!(compare_versions("v2.4.0-rc1", "2.4.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_vulnerable_versions_in_lockfiles() {
+        let db = AdvisoryDb::embedded();
+
+        let package_lock = "{\n  \"packages\": {\n    \"\": {\n      \"name\": \"app\",\n      \"version\": \"1.0.0\"\n    },\n\
+                            \"node_modules/jsonwebtoken\": {\n      \"version\": \"8.5.1\"\n    },\n\
+                            \"node_modules/node-forge\": {\n      \"version\": \"1.3.1\"\n    }\n  }\n}\n";
+        let findings = scan_content(Path::new("web/package-lock.json"), package_lock, &db);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].keyword, "jsonwebtoken");
+        assert_eq!(findings[0].line_number, 7);
+        assert_eq!(findings[0].category, "vulnerable-crypto-dependency");
+        assert!(findings[0].context.starts_with("CVE-2022-23540"), "{}", findings[0].context);
+
+        let requirements = "requests==2.31.0\ncryptography==3.2.1\nPyJWT[crypto]==2.4.0\npycrypto==2.6.1\n";
+        let ids: Vec<String> = scan_content(Path::new("requirements.txt"), requirements, &db)
+            .iter()
+            .map(|f| f.context.split(':').next().unwrap().to_string())
+            .collect();
+        assert_eq!(ids, vec!["CVE-2020-36242", "CVE-2013-7459"]);
+
+        let cargo_lock = "[[package]]\nname = \"rsa\"\nversion = \"0.9.6\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n";
+        let findings = scan_content(Path::new("Cargo.lock"), cargo_lock, &db);
+        assert!(findings[0].context.contains("no fixed version"));
+
+        let conanfile = "[requires]\nopenssl/1.0.1e\nzlib/1.2.11\n";
+        let findings = scan_content(Path::new("conanfile.txt"), conanfile, &db);
+        assert_eq!(findings[0].severity, Some(Severity::High));
+        assert!(findings[0].context.starts_with("CVE-2014-0160"));
+        assert!(scan_content(Path::new("conanfile.txt"), "[requires]\nopenssl/1.0.1g\n", &db).is_empty());
+    }
+
+    #[test]
+    fn test_extra_advisories_from_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("advisories.json");
+        fs::write(
+            &file,
+            r#"[{"ecosystem": "npm", "package": "jsrsasign", "fixed": "11.0.0", "id": "CVE-2024-21484",
+                 "summary": "Marvin attack on RSA PKCS#1 v1.5 decryption", "severity": "high"}]"#,
+        )
+        .unwrap();
+        let db = AdvisoryDb::load(file.to_str(), None).unwrap();
+
+        let yarn_lock = "jsrsasign@^10.5.0:\n  version \"10.9.0\"\n  resolved \"https://registry.yarnpkg.com/jsrsasign/-/jsrsasign-10.9.0.tgz\"\n";
+        let findings = scan_content(Path::new("yarn.lock"), yarn_lock, &db);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].version.as_deref(), Some("10.9.0"));
+
+        fs::write(&file, "{\"not\": \"a list\"}").unwrap();
+        assert!(AdvisoryDb::load(file.to_str(), None).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+use crate::scanner::secrets::is_comment_line;
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::lang_ident::detect_language;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::Path;
+
+/// How many lines either side of a weak hash call are inspected for context
+const CONTEXT_LINES: usize = 3;
+
+/// Call shapes for a weak hash across languages; `{alg}` is replaced with the algorithm pattern
+const WEAK_HASH_CALL: &str = r#"(?i)(hashlib\.{alg}\b|hashlib\.new\(\s*['"]{alg}['"]|messagedigest\.getinstance\(\s*"{alg}"|create(?:hash|hmac)\(\s*['"]{alg}['"]|\b{alg}(?:\.|::)(?:new|sum|compute|digest|create)\b|digestutils\.{alg}|\b{alg}\s*\(|hmac_?{alg}\b)"#;
+
+lazy_static! {
+    /// Weak hash algorithms: (call pattern, display name)
+    static ref WEAK_HASHES: Vec<(Regex, &'static str)> = [("md5", "MD5"), ("sha-?1", "SHA-1")]
+        .iter()
+        .map(|(alg, name)| (Regex::new(&WEAK_HASH_CALL.replace("{alg}", alg)).unwrap(), *name))
+        .collect();
+
+    /// Explicit opt-out of security use, e.g. Python's `usedforsecurity=False`
+    static ref NOT_FOR_SECURITY: Regex = Regex::new(r"(?i)usedforsecurity\s*=\s*false").unwrap();
+
+    /// Weak ciphers, ECB mode and constant IVs, as library calls or cipher names across languages:
+    /// (pattern, keyword, match type, severity, description)
+    static ref WEAK_CIPHERS: Vec<(Regex, &'static str, &'static str, Severity, &'static str)> = vec![
+        (
+            Regex::new(r#"\bDES\.new\(|\bdes\.NewCipher\(|\bEVP_des_(?:ecb|cbc|cfb\d*|ofb)\b|\bDES_(?:n?cbc|ecb)_encrypt\b|\bDESCryptoServiceProvider\b|\bDES\.Create\(|\bDes::new|(?i:getinstance\(\s*"des(?:/[^"]*)?"|['"]des(?:-(?:cbc|ecb|cfb|ofb))?['"])"#).unwrap(),
+            "DES",
+            "cipher",
+            Severity::High,
+            "DES has a 56-bit key that can be brute-forced; use AES-GCM or ChaCha20-Poly1305",
+        ),
+        (
+            Regex::new(r#"\bDES3\.new\(|\bdes\.NewTripleDESCipher\(|\bEVP_des_ede3?\w*|\bTripleDES(?:CryptoServiceProvider\b|\.Create\()|\balgorithms\.TripleDES\(|\bTdesEde[23]|(?i:['"](?:desede|tripledes|3des|des-ede3?(?:-(?:cbc|ecb|cfb|ofb))?)(?:/[^'"]*)?['"])"#).unwrap(),
+            "3DES",
+            "cipher",
+            Severity::Medium,
+            "3DES is deprecated (NIST SP 800-131A) and its 64-bit block is open to Sweet32 collisions; use AES-GCM",
+        ),
+        (
+            Regex::new(r#"\bARC4\.new\(|\brc4\.NewCipher\(|\balgorithms\.ARC4\(|\bEVP_rc4\b|\bRC4_set_key\b|\bRc4::new|(?i:['"](?:rc4|arcfour)(?:-\d+)?(?:/[^'"]*)?['"])"#).unwrap(),
+            "RC4",
+            "cipher",
+            Severity::High,
+            "RC4 keystreams are biased and RC4 is prohibited in TLS (RFC 7465); use AES-GCM or ChaCha20-Poly1305",
+        ),
+        (
+            Regex::new(r#"\bMODE_ECB\b|\bmodes\.ECB\(|\bCipherMode\.ECB\b|\bEVP_aes_\d+_ecb\b|\becb::(?:En|De)cryptor|\bEcb<|(?i:['"]aes(?:-\d{3})?-ecb['"]|['"][a-z0-9]+/ecb/|getinstance\(\s*"aes"\s*\))"#).unwrap(),
+            "ECB Mode",
+            "mode",
+            Severity::High,
+            "ECB mode encrypts identical blocks identically and leaks plaintext patterns (Java's plain \"AES\" defaults to it); use GCM",
+        ),
+        (
+            Regex::new(r#"(?i:ivparameterspec\(\s*(?:"[^"]*"\.getbytes|new\s+byte\s*\[)|createcipheriv\(\s*[^,]+,\s*[^,]+,\s*(?:['"]|buffer\.alloc\(|buffer\.from\(\s*['"])|mode_(?:cbc|cfb|ofb)\s*,\s*(?:iv\s*=\s*)?b?['"]|modes\.(?:cbc|cfb|ofb)\(\s*(?:b?['"]|bytes\(\s*\d+)|cipher\.newcbc(?:en|de)crypter\(\s*\w+\s*,\s*\[\]byte\(\s*"|\.iv\s*=\s*(?:encoding\.\w+\.getbytes\(\s*"|new\s+byte\s*\[))"#).unwrap(),
+            "Hardcoded IV",
+            "iv",
+            Severity::Medium,
+            "IV is a literal or zero-filled buffer, so equal plaintexts encrypt alike; generate a random IV for every message",
+        ),
+    ];
+}
+
+/// Identifier words (prefix match) showing the digest protects authenticity or secrets
+const SECURITY_WORDS: &[&str] = &[
+    "sign", "hmac", "passw", "token", "secret", "credential", "auth", "verif",
+    "integrity", "cert", "session", "deriv",
+];
+
+/// Short identifier words that must match exactly to avoid hits like "machine"
+const SECURITY_EXACT_WORDS: &[&str] = &["mac", "pwd", "otp", "kdf", "pin"];
+
+/// Identifier words (prefix match) showing the digest is a non-security checksum or lookup key
+const BENIGN_WORDS: &[&str] = &[
+    "cache", "etag", "checksum", "fingerprint", "dedup", "bucket", "shard",
+    "partition", "lookup", "gravatar", "filename",
+];
+
+/// How the surrounding code uses a weak digest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HashContext {
+    Security,
+    Benign,
+    Unknown,
+}
+
+/// Split code into lowercase identifier words, breaking on snake_case and camelCase boundaries
+fn identifier_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+
+    for c in text.chars() {
+        if !c.is_ascii_alphanumeric() {
+            prev_lower = false;
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Classify a piece of code by the identifiers it uses; security signals win over benign ones
+fn classify(text: &str) -> HashContext {
+    if NOT_FOR_SECURITY.is_match(text) {
+        return HashContext::Benign;
+    }
+
+    let words = identifier_words(text);
+    let is_security = words.iter().any(|w| {
+        SECURITY_WORDS.iter().any(|p| w.starts_with(p)) || SECURITY_EXACT_WORDS.contains(&w.as_str())
+    });
+    if is_security {
+        return HashContext::Security;
+    }
+
+    let is_benign = words.iter().any(|w| BENIGN_WORDS.iter().any(|p| w.starts_with(p)))
+        || words.windows(2).any(|pair| matches!(pair[0].as_str(), "content" | "file") && pair[1] == "hash");
+    if is_benign {
+        HashContext::Benign
+    } else {
+        HashContext::Unknown
+    }
+}
+
+/// Classify a weak hash call, preferring the call's own line over its neighbours
+fn classify_usage(lines: &[&str], index: usize) -> HashContext {
+    match classify(lines[index]) {
+        HashContext::Unknown => {}
+        context => return context,
+    }
+
+    let start = index.saturating_sub(CONTEXT_LINES);
+    let end = (index + CONTEXT_LINES + 1).min(lines.len());
+    classify(&lines[start..end].join("\n"))
+}
+
+/// Scans a source file for MD5/SHA-1 usage, rating severity by how the digest is used, and for
+/// DES, 3DES, RC4, ECB mode and hardcoded IVs
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded file content for weak hash, cipher, mode and IV usage
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    // (line index, match type, keyword, severity, description)
+    let mut hits: Vec<(usize, &str, &str, Severity, String)> = Vec::new();
+    let lines: Vec<&str> = content.lines().collect();
+    let language = detect_language(path);
+
+    for (i, line) in lines.iter().enumerate() {
+        if is_comment_line(line) {
+            continue;
+        }
+
+        for (regex, keyword, match_type, severity, description) in WEAK_CIPHERS.iter() {
+            if regex.is_match(line) {
+                hits.push((i, *match_type, *keyword, *severity, description.to_string()));
+            }
+        }
+
+        let Some((_, name)) = WEAK_HASHES.iter().find(|(regex, _)| regex.is_match(line)) else {
+            continue;
+        };
+
+        let (severity, description) = match classify_usage(&lines, i) {
+            HashContext::Security => (
+                Severity::High,
+                format!("{} used for a signature, MAC, password or token; it is not collision/preimage resistant enough for security use", name),
+            ),
+            HashContext::Benign => (
+                Severity::Info,
+                format!("{} used as a checksum or cache key; acceptable for non-security purposes", name),
+            ),
+            HashContext::Unknown => (
+                Severity::Medium,
+                format!("{} is a weak hash; confirm it is not used for signatures, passwords or integrity protection", name),
+            ),
+        };
+
+        hits.push((i, "hash", *name, severity, description));
+    }
+
+    hits.into_iter()
+        .map(|(i, match_type, keyword, severity, description)| Finding {
+            file: path.display().to_string(),
+            line_number: i + 1,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: lines[i].to_string(),
+            match_type: match_type.to_string(),
+            keyword: keyword.to_string(),
+            context: description,
+            version: None,
+            language: language.clone(),
+            source: "heuristic".to_string(),
+            category: "weak-crypto".to_string(),
+            severity: Some(severity),
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(file: &str, content: &str) -> Vec<Finding> {
+        scan_content(Path::new(file), content)
+    }
+
+    #[test]
+    fn test_md5_cache_key_is_informational() {
+        let findings = scan("cache.py", r#"
+def get_cached(url):
+    cache_key = hashlib.md5(url.encode()).hexdigest()
+    return cache.get(cache_key)
+"#);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].keyword, "MD5");
+        assert_eq!(findings[0].category, "weak-crypto");
+        assert_eq!(findings[0].severity, Some(Severity::Info));
+    }
+
+    #[test]
+    fn test_md5_password_hash_is_high() {
+        let findings = scan("auth.py", r#"
+def store_user(name, password):
+    hashed = hashlib.md5(password.encode()).hexdigest()
+    db.save(name, hashed)
+"#);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Some(Severity::High));
+        assert_eq!(findings[0].line_number, 3);
+    }
+
+    #[test]
+    fn test_sha1_signature_from_neighbouring_lines() {
+        let findings = scan("Signer.java", r#"
+public byte[] signPayload(byte[] payload) throws Exception {
+    MessageDigest md = MessageDigest.getInstance("SHA-1");
+    return md.digest(payload);
+}
+"#);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].keyword, "SHA-1");
+        assert_eq!(findings[0].severity, Some(Severity::High));
+    }
+
+    #[test]
+    fn test_etag_and_used_for_security_false_are_benign() {
+        let findings = scan("server.js", r#"
+const etag = crypto.createHash('sha1').update(body).digest('hex');
+"#);
+        assert_eq!(findings[0].severity, Some(Severity::Info));
+
+        let findings = scan("dedup.py", "digest = hashlib.md5(data, usedforsecurity=False)\n");
+        assert_eq!(findings[0].severity, Some(Severity::Info));
+    }
+
+    #[test]
+    fn test_hmac_md5_is_high() {
+        let findings = scan("api.go", "mac := hmac.New(md5.New, apiKey)\n");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Some(Severity::High));
+    }
+
+    #[test]
+    fn test_unclear_usage_is_medium_and_strong_hashes_ignored() {
+        let findings = scan("util.py", r#"
+h = hashlib.sha1(data).hexdigest()
+s = hashlib.sha256(data).hexdigest()
+"#);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Some(Severity::Medium));
+    }
+
+    fn keywords(file: &str, content: &str) -> Vec<(usize, String)> {
+        scan(file, content).into_iter().map(|f| (f.line_number, f.keyword)).collect()
+    }
+
+    #[test]
+    fn test_weak_ciphers_and_modes_across_languages() {
+        let java = keywords("Legacy.java", r#"
+Cipher des = Cipher.getInstance("DES/CBC/PKCS5Padding");
+Cipher tdes = Cipher.getInstance("DESede/ECB/PKCS5Padding");
+Cipher plain = Cipher.getInstance("AES");
+Cipher gcm = Cipher.getInstance("AES/GCM/NoPadding");
+"#);
+        assert_eq!(java, vec![
+            (2, "DES".to_string()),
+            (3, "3DES".to_string()),
+            (3, "ECB Mode".to_string()),
+            (4, "ECB Mode".to_string()),
+        ]);
+
+        assert_eq!(keywords("legacy.py", "cipher = ARC4.new(key)
+c = Cipher(algorithms.AES(key), modes.ECB())
+"),
+            vec![(1, "RC4".to_string()), (2, "ECB Mode".to_string())]);
+        assert_eq!(keywords("legacy.js", "const c = crypto.createCipheriv('des-ede3-cbc', key, iv);
+"),
+            vec![(1, "3DES".to_string())]);
+        assert_eq!(keywords("legacy.go", "block, err := des.NewCipher(key)
+c, _ := rc4.NewCipher(key)
+"),
+            vec![(1, "DES".to_string()), (2, "RC4".to_string())]);
+
+        let findings = scan("legacy.c", "EVP_EncryptInit_ex(ctx, EVP_des_ede3_cbc(), NULL, key, iv);
+");
+        assert_eq!(findings[0].keyword, "3DES");
+        assert_eq!(findings[0].match_type, "cipher");
+        assert_eq!(findings[0].category, "weak-crypto");
+        assert_eq!(findings[0].severity, Some(Severity::Medium));
+    }
+
+    #[test]
+    fn test_hardcoded_ivs() {
+        let findings = keywords("crypto.js", r#"
+const a = crypto.createCipheriv('aes-256-cbc', key, '1234567890abcdef');
+const b = crypto.createCipheriv('aes-256-cbc', key, Buffer.alloc(16, 0));
+const c = crypto.createCipheriv('aes-256-cbc', key, crypto.randomBytes(16));
+"#);
+        assert_eq!(findings, vec![(2, "Hardcoded IV".to_string()), (3, "Hardcoded IV".to_string())]);
+
+        assert_eq!(scan("Enc.java", "IvParameterSpec iv = new IvParameterSpec(new byte[16]);
+").len(), 1);
+        assert_eq!(scan("enc.py", "cipher = AES.new(key, AES.MODE_CBC, b'0000000000000000')
+")[0].keyword, "Hardcoded IV");
+        assert!(scan("enc.py", "cipher = AES.new(key, AES.MODE_CBC, iv)
+").is_empty());
+    }
+
+    #[test]
+    fn test_weak_cipher_names_in_comments_and_words_ignored() {
+        let findings = scan("notes.py", r#"
+# Never use DES.new(key) or "rc4" here
+label = "description"
+mode = "desktop"
+"#);
+        assert!(findings.is_empty(), "{:?}", findings);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::scanner::secrets::is_comment_line;
+use crate::utils::file_utils::read_file_lossy;
+use crate::utils::lang_ident::detect_language;
+use crate::utils::report::{Finding, Severity};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Generated tokens and passwords should carry at least this much entropy
+const MIN_ENTROPY_BITS: f64 = 64.0;
+
+/// Alphabets this small are weak regardless of the length they are used with
+const RESTRICTED_ALPHABET_SIZE: usize = 10;
+
+/// Size of the alphanumeric alphabet assumed when a generator doesn't show its charset
+const DEFAULT_ALPHABET_SIZE: usize = 62;
+
+/// What a generator's length argument counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LengthUnit {
+    Bytes,
+    Chars,
+}
+
+lazy_static! {
+    /// Generation idioms: (pattern, unit, needs a secret-named identifier on the line).
+    /// `len` holds a literal length; `args` holds an argument list to search for one.
+    static ref GENERATORS: Vec<(Regex, LengthUnit, bool)> = vec![
+        // Python secrets module is always secret generation
+        (Regex::new(r"\btoken_(?:hex|bytes|urlsafe)\(\s*(?P<len>\d+)\s*\)").unwrap(), LengthUnit::Bytes, false),
+        // Raw random bytes: os.urandom, crypto.randomBytes, SecureRandom.hex, random_bytes
+        (Regex::new(r"(?:\burandom|\brandomBytes|SecureRandom\.(?:hex|base64|urlsafe_base64|random_bytes)|\brandom_bytes)\(\s*(?P<len>\d+)").unwrap(), LengthUnit::Bytes, true),
+        // Random strings: Django get_random_string, RandomStringUtils, SecureRandom.alphanumeric, nanoid
+        (Regex::new(r"(?:\bget_random_string|\brandom(?:Alphanumeric|Alphabetic|Ascii|Numeric|Graph|Print)|SecureRandom\.alphanumeric|\bnanoid)\(\s*(?P<len>\d+)").unwrap(), LengthUnit::Chars, true),
+        (Regex::new(r#"\bcustomAlphabet\(\s*["'][^"']*["']\s*,\s*(?P<len>\d+)"#).unwrap(), LengthUnit::Chars, true),
+        // Python: random.choices(alphabet, k=N) and ''.join(choice(alphabet) for _ in range(N))
+        (Regex::new(r"\bchoices\([^)]*\bk\s*=\s*(?P<len>\d+)").unwrap(), LengthUnit::Chars, true),
+        (Regex::new(r"\bchoice\([^)]*\)\s*for\s+\w+\s+in\s+range\(\s*(?P<len>\d+)\s*\)").unwrap(), LengthUnit::Chars, true),
+        // Rust: Alphanumeric.sample_string(&mut rng, N), rng.sample_iter(&Alphanumeric).take(N)
+        (Regex::new(r"\bsample_string\([^,]+,\s*(?P<len>\d+)\s*\)").unwrap(), LengthUnit::Chars, true),
+        (Regex::new(r"\bsample_iter\([^)]*\)[^;]*?\.take\(\s*(?P<len>\d+)\s*\)").unwrap(), LengthUnit::Chars, true),
+        // Project helpers: generate_token(length=4), makePassword(6), create_api_key(size=8)
+        (Regex::new(r"(?i)\b\w*(?:generate|gen|make|create|random|new)_?\w*(?:token|password|passwd|secret|key|salt)\w*\((?P<args>[^)]*)\)").unwrap(), LengthUnit::Chars, false),
+    ];
+
+    /// Length passed by keyword or as the first positional argument
+    static ref LENGTH_ARGUMENT: Regex = Regex::new(
+        r"(?i)(?:\b(?:length|len|size|n|nbytes|num_?chars|chars|k)\s*[=:]\s*(\d+)|^\s*(\d+)\s*(?:,|$))"
+    ).unwrap();
+
+    /// Identifiers that mark the generated value as a secret
+    static ref SECRET_CONTEXT: Regex = Regex::new(
+        r"(?i)(token|passw|pwd|secret|api_?key|session|salt|nonce|credential|(?:^|[^a-z])key(?:[^a-z]|$))"
+    ).unwrap();
+
+    /// Quoted alphabet literal handed to a chooser
+    static ref ALPHABET_LITERAL: Regex = Regex::new(
+        r#"(?:choices?|customAlphabet|sample)\(\s*["']([^"']+)["']"#
+    ).unwrap();
+
+    /// Named restricted charsets: (pattern, alphabet size)
+    static ref NAMED_ALPHABETS: Vec<(Regex, usize)> = vec![
+        (Regex::new(r"string\.digits|\brandomNumeric\b|\bNumeric\b").unwrap(), 10),
+        (Regex::new(r"string\.hexdigits|\bhexdigits\b").unwrap(), 16),
+        (Regex::new(r"string\.ascii_(?:lowercase|uppercase)|\brandomAlphabetic\b").unwrap(), 26),
+    ];
+
+    /// A line that picks characters from an alphabet, even without a literal length
+    static ref ALPHABET_CHOOSER: Regex = Regex::new(r"\b(?:choices?|sample|randomNumeric|customAlphabet)\(").unwrap();
+}
+
+/// The literal length in a helper's argument list, if any
+fn length_argument(args: &str) -> Option<usize> {
+    let caps = LENGTH_ARGUMENT.captures(args)?;
+    caps.get(1).or_else(|| caps.get(2))?.as_str().parse().ok()
+}
+
+/// The alphabet size the line visibly draws from, if it shows one
+fn alphabet_size(line: &str) -> Option<usize> {
+    if let Some(literal) = ALPHABET_LITERAL.captures(line).and_then(|c| c.get(1)) {
+        return Some(literal.as_str().chars().collect::<HashSet<_>>().len());
+    }
+    NAMED_ALPHABETS
+        .iter()
+        .find(|(regex, _)| regex.is_match(line))
+        .map(|(_, size)| *size)
+}
+
+/// Scans a source file for tokens/passwords generated with too little entropy
+pub fn scan_file(path: &Path) -> Vec<Finding> {
+    match read_file_lossy(path) {
+        Ok(content) => scan_content(path, &content),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Scans already-loaded content for weak secret generation
+pub fn scan_content(path: &Path, content: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let language = detect_language(path);
+
+    for (line_num, line) in content.lines().enumerate() {
+        if is_comment_line(line) {
+            continue;
+        }
+        let secret_context = SECRET_CONTEXT.is_match(line);
+        let alphabet = alphabet_size(line);
+
+        let generation = GENERATORS.iter().find_map(|(regex, unit, needs_context)| {
+            if *needs_context && !secret_context {
+                return None;
+            }
+            let caps = regex.captures(line)?;
+            let length = match (caps.name("len"), caps.name("args")) {
+                (Some(len), _) => len.as_str().parse().ok(),
+                (None, Some(args)) => length_argument(args.as_str()),
+                (None, None) => None,
+            }?;
+            Some((caps.get(0)?.as_str().to_string(), *unit, length))
+        });
+
+        let context = match generation {
+            Some((call, unit, length)) => {
+                let symbols = match unit {
+                    LengthUnit::Bytes => 256,
+                    LengthUnit::Chars => alphabet.unwrap_or(DEFAULT_ALPHABET_SIZE),
+                };
+                let bits = length as f64 * (symbols as f64).log2();
+                if bits >= MIN_ENTROPY_BITS {
+                    continue;
+                }
+                let unit_name = match unit {
+                    LengthUnit::Bytes => "bytes",
+                    LengthUnit::Chars => "chars",
+                };
+                format!(
+                    "{} yields ~{:.0} bits of entropy ({} {} from a {}-symbol alphabet); generate at least {:.0} bits, e.g. 32 random bytes",
+                    call, bits, length, unit_name, symbols, MIN_ENTROPY_BITS
+                )
+            }
+            // No literal length, but the secret is drawn from a tiny alphabet
+            None => match alphabet {
+                Some(size) if size <= RESTRICTED_ALPHABET_SIZE && secret_context && ALPHABET_CHOOSER.is_match(line) => format!(
+                    "Secret generated from a restricted {}-symbol alphabet; use a full alphanumeric alphabet or random bytes",
+                    size
+                ),
+                _ => continue,
+            },
+        };
+
+        findings.push(Finding {
+            file: path.display().to_string(),
+            line_number: line_num + 1,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: line.to_string(),
+            match_type: "generation".to_string(),
+            keyword: "Weak Generated Secret".to_string(),
+            context,
+            version: None,
+            language: language.clone(),
+            source: "heuristic".to_string(),
+            category: "weak-generated-secret".to_string(),
+            severity: Some(Severity::Medium),
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        });
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_four_character_token_generator() {
+        let content = "def invite_code():\n    return generate_token(length=4)\n";
+        let findings = scan_content(Path::new("invites.py"), content);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line_number, 2);
+        assert_eq!(findings[0].category, "weak-generated-secret");
+        assert_eq!(findings[0].severity, Some(Severity::Medium));
+        assert!(findings[0].context.contains("~24 bits"));
+    }
+
+    #[test]
+    fn test_byte_lengths() {
+        let findings = scan_content(Path::new("auth.py"), "reset = secrets.token_hex(4)\nok = secrets.token_urlsafe(32)\n");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line_number, 1);
+
+        let content = "const sessionId = crypto.randomBytes(6).toString('hex');\nconst sessionKey = crypto.randomBytes(32);\n";
+        let findings = scan_content(Path::new("session.js"), content);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line_number, 1);
+    }
+
+    #[test]
+    fn test_restricted_alphabets() {
+        let content = "reset_token = ''.join(random.choice(string.digits) for _ in range(12))\n\
+                       api_key = ''.join(secrets.choice(ALPHABET) for _ in range(40))\n\
+                       password = ''.join(random.choices(\"01\", k=length))\n";
+        let findings = scan_content(Path::new("tokens.py"), content);
+        let lines: Vec<usize> = findings.iter().map(|f| f.line_number).collect();
+        assert_eq!(lines, vec![1, 3]);
+        assert!(findings[1].context.contains("restricted 2-symbol alphabet"));
+    }
+
+    #[test]
+    fn test_java_and_rust_idioms() {
+        let java = "String password = RandomStringUtils.randomAlphanumeric(6);\n";
+        assert_eq!(scan_content(Path::new("Gen.java"), java).len(), 1);
+
+        let rust = "let token: String = Alphanumeric.sample_string(&mut rand::thread_rng(), 8);\n\
+                    let api_key: String = Alphanumeric.sample_string(&mut rand::thread_rng(), 32);\n";
+        assert_eq!(scan_content(Path::new("gen.rs"), rust).len(), 1);
+    }
+
+    #[test]
+    fn test_non_secret_randomness_ignored() {
+        let content = "request_id = os.urandom(4)\nsuffix = get_random_string(6)\ntoken = generate_token()\n";
+        assert!(scan_content(Path::new("util.py"), content).is_empty());
+    }
+}
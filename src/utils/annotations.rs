@@ -0,0 +1,160 @@
+//! Decisions recorded by `--review`. They are exported next to the findings report as
+//! `<stem>.review.json`, and rejected findings become `.cryptoscanignore` entries.
+
+use crate::error::{config_error, ScanResult};
+use crate::scanner::suppressions::{normalize, IGNORE_FILE};
+use crate::utils::report::Finding;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// What the reviewer made of a finding
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Decision {
+    /// A real issue that should stay in reports
+    Accept,
+    /// A false positive, suppressed from later scans
+    Reject,
+    /// Looked at but left undecided
+    Skip,
+}
+
+/// One reviewed finding in the exported annotations
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub file: String,
+    pub line_number: usize,
+    pub rule_id: String,
+    pub line_content: String,
+    pub decision: Decision,
+}
+
+impl Annotation {
+    pub fn new(finding: &Finding, decision: Decision) -> Self {
+        Annotation {
+            file: finding.file.clone(),
+            line_number: finding.line_number,
+            rule_id: finding.rule_id(),
+            line_content: finding.line_content.clone(),
+            decision,
+        }
+    }
+
+    /// Whether this annotation was made for `finding`, so a later review can resume
+    pub fn is_for(&self, finding: &Finding) -> bool {
+        self.file == finding.file && self.line_number == finding.line_number && self.rule_id == finding.rule_id()
+    }
+}
+
+/// `<stem>.review.json` next to the findings report
+pub fn annotations_path(findings: &Path) -> PathBuf {
+    let stem = findings.file_stem().unwrap_or_default().to_string_lossy();
+    findings.with_file_name(format!("{}.review.json", stem))
+}
+
+/// Annotations from an earlier review; no file means none
+pub fn load_annotations(path: &Path) -> ScanResult<Vec<Annotation>> {
+    match fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json)
+            .map_err(|e| config_error(&format!("Invalid review annotations {}: {}", path.display(), e))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(config_error(&format!("Cannot read {}: {}", path.display(), e))),
+    }
+}
+
+pub fn write_annotations(path: &Path, annotations: &[Annotation]) -> io::Result<()> {
+    fs::write(path, serde_json::to_string_pretty(annotations)?)
+}
+
+/// `.cryptoscanignore` entry suppressing `finding` in scans of `root`: its line, or its rule
+/// in the file for findings about a whole file (line 0)
+pub fn ignore_entry(root: &Path, finding: &Finding) -> String {
+    let path = Path::new(&finding.file);
+    let relative = normalize(&path.strip_prefix(root).unwrap_or(path).to_string_lossy());
+    if finding.line_number == 0 {
+        format!("{}:{}", relative, finding.rule_id())
+    } else {
+        format!("{}:{}", relative, finding.line_number)
+    }
+}
+
+/// Append the `entries` not already listed to `root/.cryptoscanignore`, returning how many were added
+pub fn append_ignore_entries(root: &Path, entries: &[String]) -> io::Result<usize> {
+    let path = root.join(IGNORE_FILE);
+    let existing = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    let mut listed: HashSet<&str> = existing.lines().map(str::trim).collect();
+    let new: Vec<&String> = entries.iter().filter(|entry| listed.insert(entry.as_str())).collect();
+    if new.is_empty() {
+        return Ok(0);
+    }
+
+    let mut out = String::new();
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str("# Rejected in cryptoscan --review\n");
+    for entry in &new {
+        out.push_str(entry);
+        out.push('\n');
+    }
+    OpenOptions::new().create(true).append(true).open(&path)?.write_all(out.as_bytes())?;
+    Ok(new.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::suppressions::Suppressions;
+
+    #[test]
+    fn test_rejected_entries_suppress_findings() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join(IGNORE_FILE), "docs/example.py:4").unwrap();
+
+        let line = Finding::for_test(&root.join("app/settings.py").to_string_lossy(), 12, "API Key", "secret");
+        let keystore = Finding::for_test(&root.join("certs/server.p12").to_string_lossy(), 0, "PKCS12", "keystore");
+        let entries = vec![
+            ignore_entry(root, &line),
+            ignore_entry(root, &keystore),
+            "docs/example.py:4".to_string(),
+        ];
+        assert_eq!(entries[0], "app/settings.py:12");
+        assert_eq!(entries[1], "certs/server.p12:keystore/pkcs12");
+
+        assert_eq!(append_ignore_entries(root, &entries).unwrap(), 2);
+        assert_eq!(append_ignore_entries(root, &entries).unwrap(), 0);
+        let content = fs::read_to_string(root.join(IGNORE_FILE)).unwrap();
+        assert!(content.starts_with("docs/example.py:4\n# Rejected"), "{}", content);
+
+        let suppressions = Suppressions::load(root).unwrap();
+        assert!(suppressions.is_suppressed(&line));
+        assert!(suppressions.is_suppressed(&keystore));
+    }
+
+    #[test]
+    fn test_annotations_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let report = dir.path().join("findings.json");
+        let path = annotations_path(&report);
+        assert_eq!(path, dir.path().join("findings.review.json"));
+        assert!(load_annotations(&path).unwrap().is_empty());
+
+        let reviewed = Finding::for_test("src/app.py", 3, "API Key", "secret");
+        write_annotations(&path, &[Annotation::new(&reviewed, Decision::Reject)]).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json[0]["decision"], "reject");
+        assert_eq!(json[0]["rule_id"], "secret/api-key");
+
+        let annotations = load_annotations(&path).unwrap();
+        assert!(annotations[0].is_for(&reviewed));
+        assert!(!annotations[0].is_for(&Finding::for_test("src/app.py", 4, "API Key", "secret")));
+    }
+}
@@ -0,0 +1,246 @@
+//! `--baseline`/`--write-baseline`: suppress findings that are already known.
+//!
+//! A baseline is a findings JSON file (the default report, or one written with
+//! `--write-baseline`). Findings are matched by file, trimmed line content, keyword
+//! and category, so they stay suppressed when surrounding edits shift line numbers.
+//! Each baseline entry suppresses one finding: a known secret pasted into a second
+//! place in the same file is still reported as new. Findings about a whole file
+//! (keystores, binaries) carry a `file_hash`: they only match an entry with the same hash,
+//! so a file whose content changed is reported again, and they also match such an entry
+//! at another path, so a moved or renamed but unchanged file stays suppressed.
+
+use crate::error::{config_error, ScanResult};
+use crate::utils::report::{write_report_to_json, Finding};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// The finding fields a baseline is matched on; anything else in the file is ignored
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash)]
+struct BaselineKey {
+    file: String,
+    line_content: String,
+    keyword: String,
+    category: String,
+}
+
+impl BaselineKey {
+    fn new(file: &str, line_content: &str, keyword: &str, category: &str) -> Self {
+        BaselineKey {
+            file: file.to_string(),
+            line_content: line_content.trim().to_string(),
+            keyword: keyword.to_string(),
+            category: category.to_string(),
+        }
+    }
+
+    fn of(finding: &Finding) -> Self {
+        BaselineKey::new(&finding.file, &finding.line_content, &finding.keyword, &finding.category)
+    }
+}
+
+/// A baseline entry: its key plus the content hash of findings about a whole file
+#[derive(Debug, Deserialize)]
+struct BaselineEntry {
+    #[serde(flatten)]
+    key: BaselineKey,
+    #[serde(default)]
+    file_hash: Option<String>,
+}
+
+/// Content hash, keyword and category: what a moved file's finding still has in common
+type MovedKey = (String, String, String);
+
+fn moved_key(file_hash: &str, keyword: &str, category: &str) -> MovedKey {
+    (file_hash.to_string(), keyword.to_string(), category.to_string())
+}
+
+/// Whether a baseline entry can stand for a finding: both hashes must agree when both are known
+fn same_content(entry_hash: &Option<String>, file_hash: Option<&String>) -> bool {
+    match (entry_hash, file_hash) {
+        (Some(entry_hash), Some(file_hash)) => entry_hash == file_hash,
+        _ => true,
+    }
+}
+
+/// Known findings, one per entry, so duplicates are only suppressed as often as they were recorded
+#[derive(Debug, Default)]
+pub struct Baseline {
+    /// The `file_hash` of each entry with this key
+    known: HashMap<BaselineKey, Vec<Option<String>>>,
+    /// Keys of the entries with a `file_hash`, by what they match after a move
+    hashed: HashMap<MovedKey, Vec<BaselineKey>>,
+}
+
+impl Baseline {
+    /// Load a findings JSON file as a baseline
+    pub fn load(path: &Path) -> ScanResult<Self> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| config_error(&format!("Cannot read baseline {}: {}", path.display(), e)))?;
+        let entries: Vec<BaselineEntry> = serde_json::from_str(&json)
+            .map_err(|e| config_error(&format!("Invalid baseline {}: {}", path.display(), e)))?;
+
+        let mut baseline = Baseline::default();
+        for BaselineEntry { key, file_hash } in entries {
+            let key = BaselineKey::new(&key.file, &key.line_content, &key.keyword, &key.category);
+            if let Some(file_hash) = &file_hash {
+                let moved = moved_key(file_hash, &key.keyword, &key.category);
+                let keys = baseline.hashed.entry(moved).or_default();
+                if !keys.contains(&key) {
+                    keys.push(key.clone());
+                }
+            }
+            baseline.known.entry(key).or_default().push(file_hash);
+        }
+        Ok(baseline)
+    }
+
+    pub fn len(&self) -> usize {
+        self.known.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.known.is_empty()
+    }
+
+    /// Findings not covered by the baseline, in their original order. Entries are used up by
+    /// findings at their recorded path first, and only then by moved copies of the file.
+    pub fn new_findings(&self, findings: Vec<Finding>) -> Vec<Finding> {
+        let mut used: HashMap<BaselineKey, Vec<bool>> = HashMap::new();
+        let mut take = |key: &BaselineKey, file_hash: Option<&String>| {
+            let Some(entries) = self.known.get(key) else {
+                return false;
+            };
+            let taken = used.entry(key.clone()).or_insert_with(|| vec![false; entries.len()]);
+            let available = entries.iter().zip(taken.iter()).position(|(entry_hash, &taken)| {
+                !taken && same_content(entry_hash, file_hash)
+            });
+            if let Some(index) = available {
+                taken[index] = true;
+            }
+            available.is_some()
+        };
+
+        let known: Vec<bool> =
+            findings.iter().map(|finding| take(&BaselineKey::of(finding), finding.file_hash.as_ref())).collect();
+        let moved: Vec<bool> = findings
+            .iter()
+            .zip(&known)
+            .map(|(finding, &known)| {
+                if known {
+                    return false;
+                }
+                let Some(file_hash) = &finding.file_hash else {
+                    return false;
+                };
+                let moved = moved_key(file_hash, &finding.keyword, &finding.category);
+                self.hashed.get(&moved).is_some_and(|keys| keys.iter().any(|key| take(key, Some(file_hash))))
+            })
+            .collect();
+
+        findings
+            .into_iter()
+            .zip(known.into_iter().zip(moved))
+            .filter(|(_, (known, moved))| !known && !moved)
+            .map(|(finding, _)| finding)
+            .collect()
+    }
+}
+
+/// Write findings in the format `--baseline` reads, creating parent directories as needed
+pub fn write_baseline(findings: &[Finding], path: &Path) -> ScanResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_report_to_json(findings, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_findings_suppressed_despite_line_shifts() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("baseline").join("findings.json");
+        let recorded = [
+            Finding {
+                line_content: "API_KEY = \"abc\"".to_string(),
+                ..Finding::for_test("src/config.py", 10, "API Key", "secret")
+            },
+            Finding {
+                line_content: "pw = \"hunter22\"".to_string(),
+                ..Finding::for_test("src/config.py", 20, "Password", "secret")
+            },
+        ];
+        write_baseline(&recorded, &path).unwrap();
+        let baseline = Baseline::load(&path).unwrap();
+        assert_eq!(baseline.len(), 2);
+
+        let current = vec![
+            // Moved down and re-indented: still the known finding
+            Finding {
+                line_content: "    API_KEY = \"abc\"".to_string(),
+                ..Finding::for_test("src/config.py", 14, "API Key", "secret")
+            },
+            Finding {
+                line_content: "TOKEN = \"ghp_x\"".to_string(),
+                ..Finding::for_test("src/config.py", 30, "GitHub Token", "secret")
+            },
+            // Same line content, different rule: new
+            Finding {
+                line_content: "pw = \"hunter22\"".to_string(),
+                ..Finding::for_test("src/config.py", 24, "Plaintext Password", "secret")
+            },
+        ];
+        let new: Vec<usize> = baseline.new_findings(current).iter().map(|f| f.line_number).collect();
+        assert_eq!(new, vec![30, 24]);
+    }
+
+    #[test]
+    fn test_duplicates_beyond_baseline_count_are_new() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("findings.json");
+        let key = Finding { line_content: "KEY = \"abc\"".to_string(), ..Finding::for_test("src/config.py", 5, "API Key", "secret") };
+        write_baseline(std::slice::from_ref(&key), &path).unwrap();
+
+        let current = vec![key.clone(), Finding { line_number: 40, ..key }];
+        let new = Baseline::load(&path).unwrap().new_findings(current);
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].line_number, 40);
+    }
+
+    #[test]
+    fn test_moved_file_matched_by_hash() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("findings.json");
+        let keystore = |file: &str, file_hash: &str| Finding {
+            match_type: "keystore".to_string(),
+            file_hash: Some(file_hash.to_string()),
+            ..Finding::for_test(file, 0, "jks", "keystore")
+        };
+        write_baseline(&[keystore("certs/server.jks", "aa11")], &path).unwrap();
+        let baseline = Baseline::load(&path).unwrap();
+
+        // Renamed but unchanged: known
+        assert!(baseline.new_findings(vec![keystore("tls/server.jks", "aa11")]).is_empty());
+        // Same name, new content: new
+        assert_eq!(baseline.new_findings(vec![keystore("certs/server.jks", "bb22")]).len(), 1);
+        // The entry covers the file at its recorded path before any copy of it
+        let new = baseline.new_findings(vec![keystore("copy/server.jks", "aa11"), keystore("certs/server.jks", "aa11")]);
+        assert_eq!(new.len(), 1);
+        assert_eq!(new[0].file, "copy/server.jks");
+    }
+
+    #[test]
+    fn test_invalid_baseline_rejected() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("findings.json");
+        fs::write(&path, "[{\"file\": \"a.py\"}]").unwrap();
+
+        assert!(Baseline::load(&path).is_err());
+        assert!(Baseline::load(&dir.path().join("missing.json")).is_err());
+    }
+}
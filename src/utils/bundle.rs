@@ -0,0 +1,177 @@
+//! Report bundle written by `--report-bundle <dir>`.
+//!
+//! Layout:
+//!
+//! ```text
+//! <dir>/
+//! ├── index.html      # Landing page linking everything below
+//! ├── findings.json   # Raw findings (same schema as web/data/findings.json)
+//! ├── cbom.json       # CycloneDX CBOM generated from the findings
+//! ├── report.html     # Static, human-readable findings report
+//! └── metadata.json   # Scan metadata: tool version, target, timing, counts
+//! ```
+
+use crate::cbom::{CbomGenerator, ComponentTypeMap};
+use crate::error::{scanner_error, ScanResult};
+use crate::utils::html::{escape_html, write_report_to_html};
+use crate::utils::report::{write_report_to_json, Finding};
+use crate::utils::sink::ReportSink;
+use crate::utils::summary::FindingCounts;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+pub const FINDINGS_FILE: &str = "findings.json";
+pub const CBOM_FILE: &str = "cbom.json";
+pub const HTML_REPORT_FILE: &str = "report.html";
+pub const METADATA_FILE: &str = "metadata.json";
+pub const INDEX_FILE: &str = "index.html";
+
+/// Scan metadata stored alongside the bundle's reports
+#[derive(Serialize, Debug)]
+pub struct ScanMetadata {
+    pub tool: String,
+    pub version: String,
+    pub scanned_path: String,
+    pub app_name: Option<String>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub total_findings: usize,
+    pub findings_by_category: BTreeMap<String, usize>,
+    pub findings_by_severity: BTreeMap<String, usize>,
+    /// Caveats such as checks skipped on this platform
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+/// Writes every report format into a single directory
+pub struct BundleSink {
+    pub dir: PathBuf,
+    pub scanned_path: String,
+    pub app_name: Option<String>,
+    pub component_types: ComponentTypeMap,
+    pub start_time: DateTime<Utc>,
+    pub notes: Vec<String>,
+    /// Mask secret values in the HTML report
+    pub redact: bool,
+}
+
+impl BundleSink {
+    fn metadata(&self, findings: &[Finding]) -> ScanMetadata {
+        let counts = FindingCounts::of(findings);
+        ScanMetadata {
+            tool: "CryptoScanner".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            scanned_path: self.scanned_path.clone(),
+            app_name: self.app_name.clone(),
+            start_time: self.start_time,
+            end_time: Utc::now(),
+            total_findings: findings.len(),
+            findings_by_category: counts.by_category,
+            findings_by_severity: counts.by_severity,
+            notes: self.notes.clone(),
+        }
+    }
+
+    fn render_index(&self, metadata: &ScanMetadata) -> String {
+        let links = [
+            (HTML_REPORT_FILE, "Findings report (HTML)"),
+            (FINDINGS_FILE, "Findings (JSON)"),
+            (CBOM_FILE, "Cryptography Bill of Materials (CycloneDX JSON)"),
+            (METADATA_FILE, "Scan metadata"),
+        ];
+
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>CryptoScanner report bundle</title>\n</head>\n<body>\n\
+             <h1>CryptoScanner report bundle</h1>\n<p>Scanned <code>{}</code> with CryptoScanner {} on {} &mdash; {} findings.</p>\n<ul>\n",
+            escape_html(&metadata.scanned_path),
+            escape_html(&metadata.version),
+            metadata.end_time.format("%Y-%m-%d %H:%M:%S UTC"),
+            metadata.total_findings
+        );
+        for (file, label) in links {
+            html.push_str(&format!("<li><a href=\"{}\">{}</a></li>\n", file, label));
+        }
+        html.push_str("</ul>\n</body>\n</html>\n");
+        html
+    }
+}
+
+impl ReportSink for BundleSink {
+    fn write(&mut self, findings: &[Finding]) -> ScanResult<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        write_report_to_json(findings, self.dir.join(FINDINGS_FILE))?;
+        write_report_to_html(findings, self.dir.join(HTML_REPORT_FILE), None, self.redact)?;
+
+        let cbom = CbomGenerator::generate_cbom_with_types(findings, self.app_name.clone(), &self.component_types)
+            .and_then(|cbom| CbomGenerator::export_json(&cbom))
+            .map_err(|e| scanner_error(&format!("Failed to generate CBOM: {}", e)))?;
+        fs::write(self.dir.join(CBOM_FILE), cbom)?;
+
+        let metadata = self.metadata(findings);
+        fs::write(self.dir.join(METADATA_FILE), serde_json::to_string_pretty(&metadata)?)?;
+        fs::write(self.dir.join(INDEX_FILE), self.render_index(&metadata))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::report::Severity;
+
+    #[test]
+    fn test_bundle_layout() {
+        let dir = std::env::temp_dir().join(format!("cryptoscan-bundle-{}", uuid::Uuid::new_v4()));
+        let findings = vec![Finding {
+            file: "src/main.py".to_string(),
+            line_number: 1,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: "import hashlib".to_string(),
+            match_type: "import".to_string(),
+            keyword: "hashlib".to_string(),
+            context: "import".to_string(),
+            version: None,
+            language: "Python".to_string(),
+            source: "import".to_string(),
+            category: "library".to_string(),
+            severity: Some(Severity::Low),
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        }];
+
+        let mut sink = BundleSink {
+            dir: dir.clone(),
+            scanned_path: "./src".to_string(),
+            app_name: Some("demo".to_string()),
+            component_types: ComponentTypeMap::default(),
+            start_time: Utc::now(),
+            notes: Vec::new(),
+            redact: true,
+        };
+        sink.write(&findings).unwrap();
+
+        for file in [FINDINGS_FILE, CBOM_FILE, HTML_REPORT_FILE, METADATA_FILE, INDEX_FILE] {
+            assert!(dir.join(file).is_file(), "{} missing from bundle", file);
+        }
+
+        let index = fs::read_to_string(dir.join(INDEX_FILE)).unwrap();
+        for file in [FINDINGS_FILE, CBOM_FILE, HTML_REPORT_FILE, METADATA_FILE] {
+            assert!(index.contains(&format!("href=\"{}\"", file)));
+        }
+
+        let metadata: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join(METADATA_FILE)).unwrap()).unwrap();
+        assert_eq!(metadata["total_findings"], 1);
+        assert_eq!(metadata["findings_by_category"]["library"], 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
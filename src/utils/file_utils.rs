@@ -1,16 +1,178 @@
-use std::fs::{self, File};
-use std::io::{self, Read};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
 use std::path::Path;
 
-/// Reads the full file content into a string
+/// Default `--max-file-size`: larger files are skipped rather than read into memory
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Default `--stream-above`: larger text files are scanned a line at a time
+pub const DEFAULT_STREAM_ABOVE: u64 = 1024 * 1024;
+
+/// Reads the full file content into a string, up to [`DEFAULT_MAX_FILE_SIZE`]
 pub fn read_file_to_string(path: &Path) -> io::Result<String> {
-    fs::read_to_string(path)
+    String::from_utf8(read_file_limited(path, DEFAULT_MAX_FILE_SIZE)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Reads a file of at most `limit` bytes (0 for no limit). A larger file fails with
+/// `ErrorKind::InvalidData` before anything is read; one that grows past the limit
+/// meanwhile fails once the read goes beyond it.
+pub fn read_file_limited(path: &Path, limit: u64) -> io::Result<Vec<u8>> {
+    let file = File::open(path)?;
+    let size = file.metadata()?.len();
+    if limit == 0 {
+        let mut bytes = Vec::with_capacity(size as usize);
+        (&file).read_to_end(&mut bytes)?;
+        return Ok(bytes);
+    }
+    if size > limit {
+        return Err(too_large(size, limit));
+    }
+    let mut bytes = Vec::with_capacity(size as usize);
+    file.take(limit + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > limit {
+        return Err(too_large(bytes.len() as u64, limit));
+    }
+    Ok(bytes)
+}
+
+/// Reads a stream such as stdin to its end, failing with `ErrorKind::InvalidData` once it
+/// goes beyond `limit` bytes (0 for no limit)
+pub fn read_limited<R: Read>(mut reader: R, limit: u64) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    if limit == 0 {
+        reader.read_to_end(&mut bytes)?;
+        return Ok(bytes);
+    }
+    reader.take(limit + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > limit {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("more than {} bytes", limit)));
+    }
+    Ok(bytes)
+}
+
+/// Calls `each` with every line of a file, without its `\n` or `\r\n`, until it returns `false`.
+/// Lines are read into one reused buffer, so memory stays at the longest line however large
+/// the file. Like [`read_file_limited`], fails with `ErrorKind::InvalidData` once more than
+/// `limit` bytes (0 for no limit) have been read.
+pub fn for_each_line(path: &Path, limit: u64, mut each: impl FnMut(&[u8]) -> bool) -> io::Result<()> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut line = Vec::new();
+    let mut total = 0u64;
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)?;
+        if read == 0 {
+            return Ok(());
+        }
+        total += read as u64;
+        if limit > 0 && total > limit {
+            return Err(too_large(total, limit));
+        }
+        if line.ends_with(b"\n") {
+            line.pop();
+            if line.ends_with(b"\r") {
+                line.pop();
+            }
+        }
+        if !each(&line) {
+            return Ok(());
+        }
+    }
 }
 
+fn too_large(size: u64, limit: u64) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{} bytes, limit {}", size, limit))
+}
+
+/// Reads a file as text, replacing invalid UTF-8 with U+FFFD so files with latin-1 bytes
+/// or stray binary are still scanned rather than failing to read. Files over
+/// [`DEFAULT_MAX_FILE_SIZE`] are not read.
+pub fn read_file_lossy(path: &Path) -> io::Result<String> {
+    let bytes = read_file_limited(path, DEFAULT_MAX_FILE_SIZE)?;
+    Ok(match String::from_utf8(bytes) {
+        Ok(content) => content,
+        Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    })
+}
+
+/// Size and hex SHA-256 of a file, hashed as it streams
+pub fn sha256_file(path: &Path) -> io::Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let size = io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok((size, hex(&hasher.finalize())))
+}
+
+/// Hex SHA-256 of already-read content
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Number of leading bytes inspected for MIME detection
+const MIME_PREFIX_LEN: usize = 512;
+
 /// Detects the MIME type using the first few bytes of the file
 pub fn detect_mime_type(path: &Path) -> Option<String> {
-    let mut buf = [0u8; 512];
+    let mut buf = [0u8; MIME_PREFIX_LEN];
     let mut file = File::open(path).ok()?;
     let n = file.read(&mut buf).ok()?;
-    infer::get(&buf[..n]).map(|kind| kind.mime_type().to_string())
+    detect_mime_type_from_bytes(&buf[..n])
+}
+
+/// Detects the MIME type of already-read content from its leading bytes
+pub fn detect_mime_type_from_bytes(content: &[u8]) -> Option<String> {
+    let prefix = &content[..content.len().min(MIME_PREFIX_LEN)];
+    infer::get(prefix).map(|kind| kind.mime_type().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_file_limited() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("blob.txt");
+        std::fs::write(&path, "0123456789").unwrap();
+
+        assert_eq!(read_file_limited(&path, 10).unwrap(), b"0123456789");
+        assert_eq!(read_file_limited(&path, 0).unwrap().len(), 10);
+        let err = read_file_limited(&path, 9).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "10 bytes, limit 9");
+
+        assert_eq!(read_limited(&b"0123456789"[..], 10).unwrap().len(), 10);
+        assert_eq!(read_limited(&b"0123456789"[..], 0).unwrap().len(), 10);
+        assert_eq!(read_limited(&b"0123456789"[..], 9).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_for_each_line() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("lines.txt");
+        std::fs::write(&path, "one\r\ntwo\n\nthree").unwrap();
+
+        let mut lines = Vec::new();
+        for_each_line(&path, 0, |line| {
+            lines.push(String::from_utf8_lossy(line).into_owned());
+            true
+        })
+        .unwrap();
+        assert_eq!(lines, "one\r\ntwo\n\nthree".lines().collect::<Vec<_>>());
+
+        let mut count = 0;
+        for_each_line(&path, 0, |_| {
+            count += 1;
+            count < 2
+        })
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(for_each_line(&path, 8, |_| true).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
 }
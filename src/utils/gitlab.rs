@@ -0,0 +1,253 @@
+use crate::error::ScanResult;
+use crate::utils::report::{Finding, Severity};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use uuid::Uuid;
+
+/// GitLab security report schema version the output conforms to
+const SCHEMA_VERSION: &str = "15.0.7";
+
+/// Timestamp format required by the GitLab schema (no timezone suffix)
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Top-level gl-sast-report.json document
+#[derive(Serialize, Debug)]
+pub struct GitlabSastReport {
+    pub version: String,
+    pub vulnerabilities: Vec<GitlabVulnerability>,
+    pub scan: GitlabScan,
+}
+
+/// A single vulnerability entry
+#[derive(Serialize, Debug)]
+pub struct GitlabVulnerability {
+    pub id: String,
+    pub category: String,
+    pub name: String,
+    pub description: String,
+    pub severity: String,
+    pub location: GitlabLocation,
+    pub identifiers: Vec<GitlabIdentifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solution: Option<String>,
+}
+
+/// Source location of a vulnerability
+#[derive(Serialize, Debug)]
+pub struct GitlabLocation {
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+}
+
+/// Rule or weakness identifier attached to a vulnerability
+#[derive(Serialize, Debug)]
+pub struct GitlabIdentifier {
+    #[serde(rename = "type")]
+    pub identifier_type: String,
+    pub name: String,
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Scan metadata block
+#[derive(Serialize, Debug)]
+pub struct GitlabScan {
+    pub analyzer: GitlabTool,
+    pub scanner: GitlabTool,
+    #[serde(rename = "type")]
+    pub scan_type: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub status: String,
+}
+
+/// Analyzer/scanner description
+#[derive(Serialize, Debug)]
+pub struct GitlabTool {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub vendor: GitlabVendor,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GitlabVendor {
+    pub name: String,
+}
+
+/// Map a finding severity onto GitLab's severity vocabulary
+fn gitlab_severity(severity: Option<Severity>) -> &'static str {
+    match severity {
+        Some(Severity::Info) => "Info",
+        Some(Severity::Low) => "Low",
+        Some(Severity::Medium) => "Medium",
+        Some(Severity::High) => "High",
+        Some(Severity::Critical) => "Critical",
+        None => "Unknown",
+    }
+}
+
+/// Best-effort CWE for a finding category
+pub(crate) fn cwe_for_category(category: &str) -> Option<u32> {
+    match category {
+        "secret" | "insecure-default-secret" => Some(798),
+        "nonce-reuse" => Some(323),
+        "weak-crypto" => Some(328),
+        "weak-algorithm-in-list" => Some(327),
+        "insecure-password-compare" => Some(208),
+        "weak-generated-secret" => Some(331),
+        "keystore" => Some(321),
+        _ => None,
+    }
+}
+
+/// The finding's own CWE (`CWE-798`) if its rule names one, otherwise its category's
+pub(crate) fn cwe_for_finding(finding: &Finding) -> Option<u32> {
+    finding
+        .cwe
+        .as_deref()
+        .and_then(|cwe| cwe.strip_prefix("CWE-"))
+        .and_then(|id| id.parse().ok())
+        .or_else(|| cwe_for_category(&finding.category))
+}
+
+fn tool() -> GitlabTool {
+    GitlabTool {
+        id: "cryptoscan".to_string(),
+        name: "CryptoScanner".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        vendor: GitlabVendor { name: "Link2Trust".to_string() },
+    }
+}
+
+/// Build a GitLab SAST report from scan findings
+pub fn build_gitlab_sast_report(findings: &[Finding], start_time: DateTime<Utc>, end_time: DateTime<Utc>) -> GitlabSastReport {
+    let vulnerabilities = findings
+        .iter()
+        .map(|finding| {
+            let rule_id = finding.rule_id();
+            let mut identifiers = vec![GitlabIdentifier {
+                identifier_type: "cryptoscan_rule_id".to_string(),
+                name: format!("CryptoScanner {}", rule_id),
+                value: rule_id,
+                url: None,
+            }];
+            if let Some(cwe) = cwe_for_finding(finding) {
+                identifiers.push(GitlabIdentifier {
+                    identifier_type: "cwe".to_string(),
+                    name: format!("CWE-{}", cwe),
+                    value: cwe.to_string(),
+                    url: Some(format!("https://cwe.mitre.org/data/definitions/{}.html", cwe)),
+                });
+            }
+
+            GitlabVulnerability {
+                id: Uuid::new_v4().to_string(),
+                category: "sast".to_string(),
+                name: finding.keyword.clone(),
+                description: finding.context.clone(),
+                severity: gitlab_severity(finding.severity).to_string(),
+                location: GitlabLocation {
+                    file: finding.file.clone(),
+                    // Binary findings carry a byte offset, not a line
+                    start_line: (finding.line_number > 0 && finding.category != "binary").then_some(finding.line_number),
+                    end_line: finding.end_line_number,
+                },
+                identifiers,
+                solution: finding.remediation.as_ref().map(|url| format!("See {}", url)),
+            }
+        })
+        .collect();
+
+    GitlabSastReport {
+        version: SCHEMA_VERSION.to_string(),
+        vulnerabilities,
+        scan: GitlabScan {
+            analyzer: tool(),
+            scanner: tool(),
+            scan_type: "sast".to_string(),
+            start_time: start_time.format(TIME_FORMAT).to_string(),
+            end_time: end_time.format(TIME_FORMAT).to_string(),
+            status: "success".to_string(),
+        },
+    }
+}
+
+/// Write findings as a GitLab `gl-sast-report.json` document
+pub fn write_report_to_gitlab_sast<P: AsRef<Path>>(
+    findings: &[Finding],
+    output_path: P,
+    start_time: DateTime<Utc>,
+) -> ScanResult<()> {
+    let report = build_gitlab_sast_report(findings, start_time, Utc::now());
+    let json = serde_json::to_string_pretty(&report)?;
+    let mut file = File::create(output_path)?;
+    file.write_all(json.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_top_level_fields() {
+        let findings = vec![Finding {
+            severity: Some(Severity::High),
+            ..Finding::for_test("src/config.py", 12, "GitHub Token", "secret")
+        }];
+        let report = build_gitlab_sast_report(&findings, Utc::now(), Utc::now());
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["version"], SCHEMA_VERSION);
+        assert!(json["vulnerabilities"].is_array());
+        for field in ["analyzer", "scanner", "type", "start_time", "end_time", "status"] {
+            assert!(!json["scan"][field].is_null(), "scan.{} missing", field);
+        }
+        assert_eq!(json["scan"]["type"], "sast");
+        assert!(json["scan"]["analyzer"]["vendor"]["name"].is_string());
+
+        let vuln = &json["vulnerabilities"][0];
+        assert!(vuln["id"].is_string());
+        assert_eq!(vuln["severity"], "High");
+        assert_eq!(vuln["location"]["file"], "src/config.py");
+        assert_eq!(vuln["location"]["start_line"], 12);
+        assert_eq!(vuln["identifiers"][0]["value"], "secret/github-token");
+        assert_eq!(vuln["identifiers"][1]["name"], "CWE-798");
+        assert!(vuln.get("solution").is_none());
+    }
+
+    #[test]
+    fn test_rule_metadata_overrides_category_cwe() {
+        let mut key = Finding {
+            severity: Some(Severity::Critical),
+            ..Finding::for_test("src/config.py", 3, "GitHub Token", "secret")
+        };
+        key.cwe = Some("CWE-321".to_string());
+        key.remediation = Some("https://example.com/rotate".to_string());
+        let report = build_gitlab_sast_report(&[key], Utc::now(), Utc::now());
+        let json = serde_json::to_value(&report).unwrap();
+
+        let vuln = &json["vulnerabilities"][0];
+        assert_eq!(vuln["identifiers"][1]["value"], "321");
+        assert_eq!(vuln["solution"], "See https://example.com/rotate");
+    }
+
+    #[test]
+    fn test_file_level_findings_omit_line() {
+        let findings = vec![Finding::for_test("src/config.py", 0, "GitHub Token", "keystore")];
+        let report = build_gitlab_sast_report(&findings, Utc::now(), Utc::now());
+        let json = serde_json::to_value(&report).unwrap();
+
+        let vuln = &json["vulnerabilities"][0];
+        assert!(vuln["location"].get("start_line").is_none());
+        assert_eq!(vuln["severity"], "Unknown");
+    }
+}
@@ -0,0 +1,143 @@
+//! `--format jsonl`: one compact JSON finding per line (JSON Lines / ndjson).
+//!
+//! For a file or stdout report the findings are written while the scan runs: rayon
+//! workers hand each file's findings to a bounded channel and a single writer thread
+//! serializes them, so neither the report nor (unless something else needs them) the
+//! findings are held in memory. Lines are in no particular file order.
+
+use crate::error::{scanner_error, ScanResult};
+use crate::utils::report::{finding_to_value, FieldSelection, Finding};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::thread::{self, JoinHandle};
+
+/// Batches of findings (one per scanned file) queued before workers wait for the writer
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Append `finding` as one compact JSON line
+fn push_line(out: &mut String, finding: &Finding, fields: Option<&FieldSelection>) -> serde_json::Result<()> {
+    out.push_str(&finding_to_value(finding, fields)?.to_string());
+    out.push('\n');
+    Ok(())
+}
+
+/// Render findings as JSON Lines, keeping only `fields` when given
+pub fn render_jsonl(findings: &[Finding], fields: Option<&FieldSelection>) -> serde_json::Result<String> {
+    let mut out = String::new();
+    for finding in findings {
+        push_line(&mut out, finding, fields)?;
+    }
+    Ok(out)
+}
+
+/// Write findings as JSON Lines, keeping only `fields` when given
+pub fn write_report_to_jsonl<P: AsRef<Path>>(findings: &[Finding], output_path: P, fields: Option<&FieldSelection>) -> ScanResult<()> {
+    let mut file = BufWriter::new(File::create(output_path)?);
+    for finding in findings {
+        let mut line = String::new();
+        push_line(&mut line, finding, fields)?;
+        file.write_all(line.as_bytes())?;
+    }
+    file.flush()?;
+    Ok(())
+}
+
+/// Streams findings to a file or stdout from a dedicated writer thread
+pub struct JsonlWriter {
+    sender: SyncSender<Vec<Finding>>,
+    handle: JoinHandle<io::Result<usize>>,
+}
+
+impl JsonlWriter {
+    /// Stream to `path`, creating parent directories as needed
+    pub fn to_file(path: &Path, fields: Option<FieldSelection>) -> ScanResult<Self> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(Self::spawn(File::create(path)?, fields))
+    }
+
+    /// Stream to standard output
+    pub fn to_stdout(fields: Option<FieldSelection>) -> Self {
+        Self::spawn(io::stdout(), fields)
+    }
+
+    fn spawn<W: Write + Send + 'static>(out: W, fields: Option<FieldSelection>) -> Self {
+        let (sender, receiver) = sync_channel::<Vec<Finding>>(CHANNEL_CAPACITY);
+        let handle = thread::spawn(move || {
+            let mut out = BufWriter::new(out);
+            let mut written = 0;
+            for batch in receiver {
+                let mut lines = String::new();
+                for finding in &batch {
+                    push_line(&mut lines, finding, fields.as_ref())?;
+                }
+                out.write_all(lines.as_bytes())?;
+                written += batch.len();
+            }
+            out.flush()?;
+            Ok(written)
+        });
+        JsonlWriter { sender, handle }
+    }
+
+    /// Queue the findings of one scanned file, waiting while the writer is behind
+    pub fn send(&self, findings: &[Finding]) {
+        if findings.is_empty() {
+            return;
+        }
+        // A failed writer has dropped its receiver; the error surfaces in `finish`
+        let _ = self.sender.send(findings.to_vec());
+    }
+
+    /// Flush everything queued and return the number of findings written
+    pub fn finish(self) -> ScanResult<usize> {
+        drop(self.sender);
+        match self.handle.join() {
+            Ok(result) => Ok(result?),
+            Err(_) => Err(scanner_error("JSON Lines writer thread panicked")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::report::parse_output_fields;
+
+    #[test]
+    fn test_render_jsonl() {
+        let fields = parse_output_fields("file,line_number").unwrap();
+        let findings = [Finding::for_test("a.py", 3, "API Key", "secret"), Finding::for_test("b.py", 7, "API Key", "secret")];
+        let rendered = render_jsonl(&findings, Some(&fields)).unwrap();
+        assert_eq!(rendered, "{\"file\":\"a.py\",\"line_number\":3}\n{\"file\":\"b.py\",\"line_number\":7}\n");
+        assert_eq!(render_jsonl(&[], None).unwrap(), "");
+    }
+
+    #[test]
+    fn test_writer_streams_batches_from_many_threads() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("out/findings.jsonl");
+        let writer = JsonlWriter::to_file(&path, None).unwrap();
+        thread::scope(|scope| {
+            for t in 0..4 {
+                let writer = &writer;
+                scope.spawn(move || {
+                    for i in 0..50 {
+                        let file = format!("t{}.py", t);
+                        writer.send(&[Finding::for_test(&file, i, "API Key", "secret"), Finding::for_test(&file, i + 100, "API Key", "secret")]);
+                    }
+                });
+            }
+        });
+        writer.send(&[]);
+        assert_eq!(writer.finish().unwrap(), 400);
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<Finding> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 400);
+        assert!(lines.iter().any(|f| f.file == "t3.py" && f.line_number == 149));
+    }
+}
@@ -42,6 +42,8 @@ pub fn detect_language(path: &Path) -> String {
             "mm" => "Objective-C++",
             "dart" => "Dart",
             "lua" => "Lua",
+            "sol" => "Solidity",
+            "vy" => "Vyper",
             "vim" => "Vim Script",
             "asm" | "s" => "Assembly",
             _ => "Unknown"
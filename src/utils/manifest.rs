@@ -0,0 +1,86 @@
+//! `--manifest`: an auditable record of every file a scan covered.
+//!
+//! Each scanned file is listed with its path relative to the scan root, size, SHA-256
+//! and detected language, whether or not it produced findings. Entries are sorted by
+//! path so manifests of the same tree can be diffed.
+
+use crate::error::{file_error, ScanResult};
+use crate::utils::file_utils::sha256_file;
+use crate::utils::lang_ident::detect_language;
+use log::warn;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// One scanned file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Path relative to the scan root (as given when the file lies outside it)
+    pub path: String,
+    pub size: u64,
+    /// Hex SHA-256 of the content; `null` if the file could not be read again
+    pub sha256: Option<String>,
+    pub language: String,
+}
+
+/// Describe `files` relative to `root`. Unreadable files are an error when `strict` is set
+/// and are listed without a hash otherwise.
+pub fn build_manifest(files: &[PathBuf], root: &Path, strict: bool) -> ScanResult<Vec<ManifestEntry>> {
+    let mut entries = files
+        .par_iter()
+        .map(|file| {
+            let path = file.strip_prefix(root).unwrap_or(file).display().to_string();
+            let (size, sha256) = match sha256_file(file) {
+                Ok((size, sha256)) => (size, Some(sha256)),
+                Err(e) if strict => {
+                    return Err(file_error(&format!("Cannot hash {} for the manifest: {}", file.display(), e)));
+                }
+                Err(e) => {
+                    warn!("Listing {} in the manifest without a hash: {}", file.display(), e);
+                    (fs::metadata(file).map(|m| m.len()).unwrap_or(0), None)
+                }
+            };
+            Ok(ManifestEntry { path, size, sha256, language: detect_language(file) })
+        })
+        .collect::<ScanResult<Vec<_>>>()?;
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+/// Write the manifest as a JSON array, creating parent directories as needed
+pub fn write_manifest(entries: &[ManifestEntry], path: &Path) -> ScanResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = File::create(path)?;
+    file.write_all(serde_json::to_string_pretty(entries)?.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_entries() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.py"), "print('hi')\n").unwrap();
+        fs::write(root.join("empty.rs"), "").unwrap();
+
+        let files = vec![root.join("src/main.py"), root.join("empty.rs"), root.join("gone.py")];
+        let entries = build_manifest(&files, root, false).unwrap();
+
+        let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["empty.rs", "gone.py", "src/main.py"]);
+        assert_eq!(entries[0].sha256.as_deref(), Some("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"));
+        assert_eq!(entries[0].language, "Rust");
+        assert_eq!(entries[1].sha256, None);
+        assert_eq!(entries[2].size, 12);
+
+        assert!(build_manifest(&files, root, true).is_err());
+    }
+}
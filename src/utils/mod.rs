@@ -1,3 +1,15 @@
+pub mod annotations;
+pub mod baseline;
+pub mod bundle;
 pub mod file_utils;
+pub mod gitlab;
+pub mod html;
+pub mod jsonl;
 pub mod lang_ident;
+pub mod manifest;
 pub mod report;
+pub mod sarif;
+pub mod sink;
+pub mod skipped;
+pub mod socket;
+pub mod summary;
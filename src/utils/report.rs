@@ -1,12 +1,148 @@
-use serde::{Deserialize, Serialize};
+use crate::error::ScanResult;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashSet;
+use std::fmt;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Severity of a finding, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = String;
+
+    fn from_str(level: &str) -> Result<Self, Self::Err> {
+        match level.trim().to_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "low" => Ok(Severity::Low),
+            "medium" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            "critical" => Ok(Severity::Critical),
+            other => Err(format!("unknown severity '{}' (expected info, low, medium, high or critical)", other)),
+        }
+    }
+}
+
+impl Severity {
+    /// Map a pattern-table level (0 = info .. 4 = critical) onto a severity
+    pub fn from_level(level: u8) -> Self {
+        match level {
+            0 => Severity::Info,
+            1 => Severity::Low,
+            2 => Severity::Medium,
+            3 => Severity::High,
+            _ => Severity::Critical,
+        }
+    }
+
+    /// One level more severe, saturating at Critical
+    pub fn raised(self) -> Self {
+        match self {
+            Severity::Info => Severity::Low,
+            Severity::Low => Severity::Medium,
+            Severity::Medium => Severity::High,
+            Severity::High | Severity::Critical => Severity::Critical,
+        }
+    }
+
+    /// One level less severe, saturating at Info
+    pub fn lowered(self) -> Self {
+        match self {
+            Severity::Info | Severity::Low => Severity::Info,
+            Severity::Medium => Severity::Low,
+            Severity::High => Severity::Medium,
+            Severity::Critical => Severity::High,
+        }
+    }
+}
+
+/// Format used when writing the findings report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Pretty-printed JSON array of findings (consumed by the web dashboard)
+    Json,
+    /// GitLab Security Dashboard SAST report (gl-sast-report.json)
+    GitlabSast,
+    /// SARIF 2.1.0 log for GitHub code scanning and other SARIF consumers
+    Sarif,
+    /// RFC 4180 CSV, one row per finding, for spreadsheet triage
+    Csv,
+    /// JSON Lines: one compact finding per line, streamed while the scan runs
+    Jsonl,
+    /// Standalone HTML page for sharing, with secret values masked unless `--no-redact`
+    Html,
+}
+
+/// Order of findings in a written report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum FindingOrder {
+    /// By file, line and rule, so identical scans produce identical reports
+    #[default]
+    File,
+    /// In the order the scan produced them, which varies with the walk and thread scheduling
+    None,
+}
+
+impl OutputFormat {
+    /// Default report location for this format
+    pub fn default_output_path(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "web/data/findings.json",
+            OutputFormat::GitlabSast => "gl-sast-report.json",
+            OutputFormat::Sarif => "cryptoscan.sarif",
+            OutputFormat::Csv => "findings.csv",
+            OutputFormat::Jsonl => "findings.jsonl",
+            OutputFormat::Html => "findings.html",
+        }
+    }
+
+    /// MIME type of the rendered report
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            OutputFormat::Csv => "text/csv",
+            OutputFormat::Jsonl => "application/x-ndjson",
+            OutputFormat::Html => "text/html",
+            OutputFormat::Json | OutputFormat::GitlabSast | OutputFormat::Sarif => "application/json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Finding {
     pub file: String,
     pub line_number: usize,
+    /// Last line of a finding that spans several lines, e.g. a PEM block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line_number: Option<usize>,
+    /// 1-based character column where the matched value starts on `line_number`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column_start: Option<usize>,
+    /// 1-based character column just past the matched value (exclusive, as in SARIF)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column_end: Option<usize>,
     pub line_content: String,
     pub match_type: String,
     pub keyword: String,
@@ -15,11 +151,373 @@ pub struct Finding {
     pub language: String,
     pub source: String,
     pub category: String, // ✅ NEW: library, keystore, command, etc.
+    pub severity: Option<Severity>,
+    /// Hex SHA-256 of the whole file, for findings about a file rather than a line of it
+    /// (keystores, binaries), so copies and moves of the file can be recognized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_hash: Option<String>,
+    /// Weakness the finding is evidence of, e.g. `CWE-798`; reports fall back to a per-category CWE without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwe: Option<String>,
+    /// Link to guidance on fixing the finding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+}
+
+impl Finding {
+    /// Stable rule identifier derived from the category and keyword, e.g. `secret/github-token`
+    pub fn rule_id(&self) -> String {
+        let keyword: String = self.keyword
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let keyword = keyword
+            .split('-')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        format!("{}/{}", self.category, keyword)
+    }
+
+    /// A hardcoded Python finding for tests: `keyword` of `category` at `file:line_number`,
+    /// with every other field empty; override them with `..` struct update
+    #[cfg(test)]
+    pub(crate) fn for_test(file: &str, line_number: usize, keyword: &str, category: &str) -> Self {
+        Finding {
+            file: file.to_string(),
+            line_number,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: String::new(),
+            match_type: "secret".to_string(),
+            keyword: keyword.to_string(),
+            context: String::new(),
+            version: None,
+            language: "Python".to_string(),
+            source: "hardcoded".to_string(),
+            category: category.to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        }
+    }
+}
+
+/// Finding fields that `--output-fields` can select; `rule_id` is derived, the rest are struct fields
+pub const FINDING_FIELDS: &[&str] = &[
+    "file", "line_number", "end_line_number", "column_start", "column_end", "line_content", "match_type", "keyword", "context", "version",
+    "language", "source", "category", "severity", "file_hash", "cwe", "remediation", "rule_id",
+];
+
+/// The subset of finding fields written to JSON reports, in the requested order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSelection {
+    fields: Vec<&'static str>,
+}
+
+/// Parse a comma-separated `--output-fields` list, rejecting unknown field names
+pub fn parse_output_fields(list: &str) -> Result<FieldSelection, String> {
+    let mut fields = Vec::new();
+    for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let field = FINDING_FIELDS
+            .iter()
+            .find(|known| **known == name)
+            .ok_or_else(|| format!("Unknown finding field '{}' (expected one of: {})", name, FINDING_FIELDS.join(", ")))?;
+        if !fields.contains(field) {
+            fields.push(*field);
+        }
+    }
+    if fields.is_empty() {
+        return Err("Expected at least one field name".to_string());
+    }
+    Ok(FieldSelection { fields })
+}
+
+/// A finding serialized with only the selected fields
+struct ProjectedFinding<'a> {
+    finding: &'a Finding,
+    selection: &'a FieldSelection,
+}
+
+impl Serialize for ProjectedFinding<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let f = self.finding;
+        let mut map = serializer.serialize_map(Some(self.selection.fields.len()))?;
+        for &field in &self.selection.fields {
+            match field {
+                "file" => map.serialize_entry(field, &f.file)?,
+                "line_number" => map.serialize_entry(field, &f.line_number)?,
+                "end_line_number" => map.serialize_entry(field, &f.end_line_number)?,
+                "column_start" => map.serialize_entry(field, &f.column_start)?,
+                "column_end" => map.serialize_entry(field, &f.column_end)?,
+                "line_content" => map.serialize_entry(field, &f.line_content)?,
+                "match_type" => map.serialize_entry(field, &f.match_type)?,
+                "keyword" => map.serialize_entry(field, &f.keyword)?,
+                "context" => map.serialize_entry(field, &f.context)?,
+                "version" => map.serialize_entry(field, &f.version)?,
+                "language" => map.serialize_entry(field, &f.language)?,
+                "source" => map.serialize_entry(field, &f.source)?,
+                "category" => map.serialize_entry(field, &f.category)?,
+                "severity" => map.serialize_entry(field, &f.severity)?,
+                "file_hash" => map.serialize_entry(field, &f.file_hash)?,
+                "cwe" => map.serialize_entry(field, &f.cwe)?,
+                "remediation" => map.serialize_entry(field, &f.remediation)?,
+                "rule_id" => map.serialize_entry(field, &f.rule_id())?,
+                _ => unreachable!("field names are validated by parse_output_fields"),
+            }
+        }
+        map.end()
+    }
+}
+
+/// A single finding as a JSON value, limited to the selected fields when given
+pub fn finding_to_value(finding: &Finding, fields: Option<&FieldSelection>) -> serde_json::Result<serde_json::Value> {
+    match fields {
+        Some(selection) => serde_json::to_value(ProjectedFinding { finding, selection }),
+        None => serde_json::to_value(finding),
+    }
+}
+
+/// Render findings as pretty-printed JSON, keeping only `fields` when given
+pub fn render_json(findings: &[Finding], fields: Option<&FieldSelection>) -> serde_json::Result<String> {
+    match fields {
+        Some(selection) => {
+            let projected: Vec<ProjectedFinding> = findings
+                .iter()
+                .map(|finding| ProjectedFinding { finding, selection })
+                .collect();
+            serde_json::to_string_pretty(&projected)
+        }
+        None => serde_json::to_string_pretty(findings),
+    }
+}
+
+/// Sort findings by file, position and rule so identical scans produce identical reports
+pub fn sort_findings(findings: &mut [Finding]) {
+    findings.sort_by(|a, b| {
+        (&a.file, a.line_number, a.end_line_number, &a.keyword, &a.category, &a.line_content)
+            .cmp(&(&b.file, b.line_number, b.end_line_number, &b.keyword, &b.category, &b.line_content))
+    });
+}
+
+/// Number of findings at or above `level`, for `--fail-on`; findings without a severity don't count
+pub fn count_at_or_above(findings: &[Finding], level: Severity) -> usize {
+    findings.iter().filter(|f| f.severity.is_some_and(|severity| severity >= level)).count()
+}
+
+/// Drop findings identical in file, line, keyword, category and start column to an earlier one,
+/// as when several scanners or overlapping patterns report the same secret. Keeps the first
+/// occurrence and the order of the rest.
+pub fn dedup_findings(findings: &mut Vec<Finding>) {
+    let mut seen = HashSet::new();
+    findings.retain(|f| {
+        seen.insert((f.file.clone(), f.line_number, f.keyword.clone(), f.category.clone(), f.column_start))
+    });
+}
+
+pub fn write_report_to_json<P: AsRef<Path>>(findings: &[Finding], output_path: P) -> ScanResult<()> {
+    write_report_to_json_fields(findings, output_path, None)
 }
 
-pub fn write_report_to_json<P: AsRef<Path>>(findings: &[Finding], output_path: P) -> std::io::Result<()> {
-    let json = serde_json::to_string_pretty(findings)?;
+/// Write findings as JSON, keeping only `fields` when given
+pub fn write_report_to_json_fields<P: AsRef<Path>>(findings: &[Finding], output_path: P, fields: Option<&FieldSelection>) -> ScanResult<()> {
+    let json = render_json(findings, fields)?;
     let mut file = File::create(output_path)?;
     file.write_all(json.as_bytes())?;
     Ok(())
 }
+
+/// Column headers of the CSV report
+const CSV_HEADER: &[&str] = &["file", "line_number", "category", "keyword", "context", "language", "source", "line_content"];
+
+/// Quote a CSV field per RFC 4180 when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render findings as CSV; the header row is always present, even with no findings
+pub fn render_csv(findings: &[Finding]) -> String {
+    let mut csv = CSV_HEADER.join(",");
+    csv.push_str("\r\n");
+    for finding in findings {
+        let line_number = finding.line_number.to_string();
+        let row = [
+            finding.file.as_str(),
+            line_number.as_str(),
+            finding.category.as_str(),
+            finding.keyword.as_str(),
+            finding.context.as_str(),
+            finding.language.as_str(),
+            finding.source.as_str(),
+            finding.line_content.as_str(),
+        ];
+        csv.push_str(&row.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+pub fn write_report_to_csv<P: AsRef<Path>>(findings: &[Finding], output_path: P) -> std::io::Result<()> {
+    let mut file = File::create(output_path)?;
+    file.write_all(render_csv(findings).as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_field_projection() {
+        let finding = Finding {
+            file: "src/keys.py".to_string(),
+            line_number: 3,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: "token = \"ghp_secret\"".to_string(),
+            match_type: "secret".to_string(),
+            keyword: "GitHub Token".to_string(),
+            context: "GitHub personal access token".to_string(),
+            version: None,
+            language: "Python".to_string(),
+            source: "hardcoded".to_string(),
+            category: "secret".to_string(),
+            severity: Some(Severity::High),
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        };
+        let fields = parse_output_fields("file, line_number,rule_id,severity").unwrap();
+        let json = render_json(&[finding], Some(&fields)).unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value, serde_json::json!([{
+            "file": "src/keys.py",
+            "line_number": 3,
+            "rule_id": "secret/github-token",
+            "severity": "high",
+        }]));
+        assert!(!json.contains("ghp_secret"));
+        // Fields keep the requested order
+        assert!(json.find("rule_id").unwrap() < json.find("severity").unwrap());
+    }
+
+    #[test]
+    fn test_unknown_output_field_rejected() {
+        let err = parse_output_fields("file,secret_value").unwrap_err();
+        assert!(err.contains("secret_value"));
+        assert!(parse_output_fields(" , ").is_err());
+    }
+
+    #[test]
+    fn test_dedup_keeps_first_occurrence() {
+        let finding = |line_number: usize, keyword: &str, source: &str, column_start: Option<usize>| Finding {
+            file: "src/app.py".to_string(),
+            line_number,
+            end_line_number: None,
+            column_start,
+            column_end: None,
+            line_content: "key = \"...\"".to_string(),
+            match_type: "secret".to_string(),
+            keyword: keyword.to_string(),
+            context: String::new(),
+            version: None,
+            language: "Python".to_string(),
+            source: source.to_string(),
+            category: "secret".to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        };
+        let mut findings = vec![
+            finding(4, "API Key", "hardcoded", Some(7)),
+            finding(2, "API Key", "hardcoded", Some(7)),
+            finding(4, "API Key", "entropy", Some(7)),
+            finding(4, "API Key", "hardcoded", Some(20)),
+            finding(4, "Password", "hardcoded", Some(7)),
+        ];
+        dedup_findings(&mut findings);
+
+        let kept: Vec<(usize, &str, &str, Option<usize>)> = findings
+            .iter()
+            .map(|f| (f.line_number, f.keyword.as_str(), f.source.as_str(), f.column_start))
+            .collect();
+        assert_eq!(kept, vec![
+            (4, "API Key", "hardcoded", Some(7)),
+            (2, "API Key", "hardcoded", Some(7)),
+            (4, "API Key", "hardcoded", Some(20)),
+            (4, "Password", "hardcoded", Some(7)),
+        ]);
+    }
+
+    #[test]
+    fn test_count_at_or_above() {
+        let finding = |severity: Option<Severity>| Finding {
+            file: "src/app.py".to_string(),
+            line_number: 1,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: String::new(),
+            match_type: "secret".to_string(),
+            keyword: "API Key".to_string(),
+            context: String::new(),
+            version: None,
+            language: "Python".to_string(),
+            source: "hardcoded".to_string(),
+            category: "secret".to_string(),
+            severity,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        };
+        let findings = vec![finding(None), finding(Some(Severity::Low)), finding(Some(Severity::High)), finding(Some(Severity::Critical))];
+        assert_eq!(count_at_or_above(&findings, Severity::Info), 3);
+        assert_eq!(count_at_or_above(&findings, Severity::High), 2);
+        assert_eq!(count_at_or_above(&findings[..2], Severity::Medium), 0);
+    }
+
+    #[test]
+    fn test_csv_header_without_findings() {
+        assert_eq!(render_csv(&[]), "file,line_number,category,keyword,context,language,source,line_content\r\n");
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let finding = Finding {
+            file: "src/app, v2/config.py".to_string(),
+            line_number: 7,
+            end_line_number: None,
+            column_start: None,
+            column_end: None,
+            line_content: "token = \"abc,def\"\nnext".to_string(),
+            match_type: "secret".to_string(),
+            keyword: "API Key".to_string(),
+            context: "Generic API key pattern".to_string(),
+            version: None,
+            language: "Python".to_string(),
+            source: "hardcoded".to_string(),
+            category: "secret".to_string(),
+            severity: None,
+            file_hash: None,
+            cwe: None,
+            remediation: None,
+        };
+        let csv = render_csv(&[finding]);
+        let row = csv.split_once("\r\n").unwrap().1;
+
+        assert_eq!(
+            row,
+            "\"src/app, v2/config.py\",7,secret,API Key,Generic API key pattern,Python,hardcoded,\"token = \"\"abc,def\"\"\nnext\"\r\n"
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use crate::error::ScanResult;
+use crate::utils::gitlab::cwe_for_finding;
+use crate::utils::report::{Finding, Severity};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Base id that result URIs are relative to
+const SRCROOT: &str = "%SRCROOT%";
+
+/// Top-level SARIF log
+#[derive(Serialize, Debug)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub original_uri_base_ids: BTreeMap<String, SarifArtifactLocation>,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    pub name: String,
+    pub version: String,
+    pub information_uri: String,
+    pub rules: Vec<SarifRule>,
+}
+
+/// A rule, shared by every result with the same `ruleId`
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub name: String,
+    pub short_description: SarifMessage,
+    /// The rule's CWE and remediation guidance, when its pattern names them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<SarifMultiformatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help_uri: Option<String>,
+    pub default_configuration: SarifConfiguration,
+    pub properties: SarifRuleProperties,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SarifConfiguration {
+    pub level: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SarifRuleProperties {
+    pub tags: Vec<String>,
+    /// Numeric score GitHub uses to bucket security alerts
+    #[serde(rename = "security-severity", skip_serializing_if = "Option::is_none")]
+    pub security_severity: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: String,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SarifMultiformatMessage {
+    pub text: String,
+    pub markdown: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<SarifRegion>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri_base_id: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+    /// Byte offset of a match in a binary file, which has no lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub byte_offset: Option<usize>,
+}
+
+/// Map a finding severity onto SARIF's level vocabulary
+fn sarif_level(severity: Option<Severity>) -> &'static str {
+    match severity {
+        Some(Severity::Critical) | Some(Severity::High) => "error",
+        Some(Severity::Medium) | None => "warning",
+        Some(Severity::Low) | Some(Severity::Info) => "note",
+    }
+}
+
+/// GitHub's `security-severity` score for a finding severity
+fn security_severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "9.5",
+        Severity::High => "8.0",
+        Severity::Medium => "5.5",
+        Severity::Low => "3.0",
+        Severity::Info => "0.0",
+    }
+}
+
+/// `rule.help` for a finding: its CWE and where to read about fixing it
+fn rule_help(finding: &Finding) -> Option<SarifMultiformatMessage> {
+    let cwe = finding.cwe.as_deref();
+    let remediation = finding.remediation.as_deref();
+    let (text, markdown) = match (cwe, remediation) {
+        (None, None) => return None,
+        (Some(cwe), None) => (cwe.to_string(), cwe.to_string()),
+        (None, Some(url)) => (format!("Remediation: {}", url), format!("[Remediation]({})", url)),
+        (Some(cwe), Some(url)) => (format!("{}. Remediation: {}", cwe, url), format!("{}. [Remediation]({})", cwe, url)),
+    };
+    Some(SarifMultiformatMessage { text, markdown })
+}
+
+/// Drop `.` components so `./src/a.rs` and `src/a.rs` compare equal
+fn normalize(path: &Path) -> PathBuf {
+    path.components().filter(|c| !matches!(c, Component::CurDir)).collect()
+}
+
+/// The directory result URIs are relative to; a single scanned file counts as its parent
+fn base_dir(scan_root: &Path) -> PathBuf {
+    if scan_root.is_file() {
+        scan_root.parent().map(Path::to_path_buf).unwrap_or_default()
+    } else {
+        scan_root.to_path_buf()
+    }
+}
+
+/// Relative, `/`-separated URI for a finding's file
+fn relative_uri(file: &str, base: &Path) -> String {
+    let file = normalize(Path::new(file));
+    let base = normalize(base);
+    let relative = file.strip_prefix(&base).unwrap_or(&file);
+
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+        .replace('%', "%25")
+        .replace(' ', "%20")
+        .replace('#', "%23")
+}
+
+/// Absolute `file://` URI of the scan root, used to resolve relative result URIs
+fn root_uri(base: &Path) -> Option<String> {
+    let absolute = base.canonicalize().ok()?;
+    let mut uri = format!("file://{}", absolute.to_string_lossy().replace('\\', "/"));
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    Some(uri)
+}
+
+/// Build a SARIF 2.1.0 log from scan findings, with locations relative to `scan_root`
+pub fn build_sarif_report(findings: &[Finding], scan_root: &Path) -> SarifLog {
+    let base = base_dir(scan_root);
+    let mut rules: Vec<SarifRule> = Vec::new();
+    let mut rule_indices: BTreeMap<String, usize> = BTreeMap::new();
+
+    let results = findings
+        .iter()
+        .map(|finding| {
+            let rule_id = finding.rule_id();
+            let rule_index = *rule_indices.entry(rule_id.clone()).or_insert_with(|| {
+                let mut tags = vec!["security".to_string(), finding.category.clone()];
+                if let Some(cwe) = cwe_for_finding(finding) {
+                    tags.push(format!("external/cwe/cwe-{}", cwe));
+                }
+                rules.push(SarifRule {
+                    id: rule_id.clone(),
+                    name: finding.keyword.clone(),
+                    short_description: SarifMessage { text: finding.keyword.clone() },
+                    help: rule_help(finding),
+                    help_uri: finding.remediation.clone(),
+                    default_configuration: SarifConfiguration { level: sarif_level(finding.severity).to_string() },
+                    properties: SarifRuleProperties {
+                        tags,
+                        security_severity: finding.severity.map(|s| security_severity(s).to_string()),
+                    },
+                });
+                rules.len() - 1
+            });
+
+            let message = if finding.context.is_empty() {
+                finding.keyword.clone()
+            } else {
+                format!("{}: {}", finding.keyword, finding.context)
+            };
+
+            SarifResult {
+                rule_id,
+                rule_index,
+                level: sarif_level(finding.severity).to_string(),
+                message: SarifMessage { text: message },
+                locations: vec![SarifLocation {
+                    physical_location: SarifPhysicalLocation {
+                        artifact_location: SarifArtifactLocation {
+                            uri: relative_uri(&finding.file, &base),
+                            uri_base_id: Some(SRCROOT.to_string()),
+                        },
+                        // Whole-file findings (keystores) have no line to point at
+                        region: if finding.category == "binary" {
+                            Some(SarifRegion {
+                                start_line: None,
+                                end_line: None,
+                                start_column: None,
+                                end_column: None,
+                                byte_offset: Some(finding.line_number),
+                            })
+                        } else {
+                            (finding.line_number > 0).then_some(SarifRegion {
+                                start_line: Some(finding.line_number),
+                                end_line: finding.end_line_number,
+                                start_column: finding.column_start,
+                                end_column: finding.column_end,
+                                byte_offset: None,
+                            })
+                        },
+                    },
+                }],
+            }
+        })
+        .collect();
+
+    let mut original_uri_base_ids = BTreeMap::new();
+    if let Some(uri) = root_uri(&base) {
+        original_uri_base_ids.insert(SRCROOT.to_string(), SarifArtifactLocation { uri, uri_base_id: None });
+    }
+
+    SarifLog {
+        schema: SARIF_SCHEMA.to_string(),
+        version: SARIF_VERSION.to_string(),
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "CryptoScanner".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    information_uri: "https://github.com/Link2Trust/crypscan".to_string(),
+                    rules,
+                },
+            },
+            original_uri_base_ids,
+            results,
+        }],
+    }
+}
+
+/// Write findings as a SARIF 2.1.0 log
+pub fn write_report_to_sarif<P: AsRef<Path>>(findings: &[Finding], output_path: P, scan_root: &Path) -> ScanResult<()> {
+    let report = build_sarif_report(findings, scan_root);
+    let json = serde_json::to_string_pretty(&report)?;
+    let mut file = File::create(output_path)?;
+    file.write_all(json.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_results_and_deduplicated_rules() {
+        let mut key_block = Finding {
+            severity: Some(Severity::High),
+            ..Finding::for_test("./repo/src/a.py", 3, "GitHub Token", "secret")
+        };
+        key_block.end_line_number = Some(5);
+        key_block.cwe = Some("CWE-321".to_string());
+        key_block.remediation = Some("https://example.com/keys".to_string());
+        let mut token = Finding {
+            severity: Some(Severity::High),
+            ..Finding::for_test("./repo/src/b.py", 9, "GitHub Token", "secret")
+        };
+        (token.column_start, token.column_end) = (Some(9), Some(49));
+        let findings = vec![
+            key_block,
+            token,
+            Finding {
+                severity: Some(Severity::Low),
+                ..Finding::for_test("./repo/src/c.py", 1, "MD5 Hash", "weak-crypto")
+            },
+        ];
+        let report = serde_json::to_value(build_sarif_report(&findings, Path::new("./repo"))).unwrap();
+
+        assert_eq!(report["version"], "2.1.0");
+        let run = &report["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "secret/github-token");
+        assert!(rules[0]["properties"]["tags"].as_array().unwrap().contains(&"external/cwe/cwe-321".into()));
+        assert_eq!(rules[0]["helpUri"], "https://example.com/keys");
+        assert_eq!(rules[0]["help"]["markdown"], "CWE-321. [Remediation](https://example.com/keys)");
+        assert!(rules[1].get("help").is_none());
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results[1]["ruleIndex"], 0);
+        assert_eq!(results[2]["ruleIndex"], 1);
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[2]["level"], "note");
+
+        let location = &results[1]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/b.py");
+        assert_eq!(location["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+        assert_eq!(location["region"]["startLine"], 9);
+        assert!(location["region"].get("endLine").is_none());
+        assert_eq!(location["region"]["startColumn"], 9);
+        assert_eq!(location["region"]["endColumn"], 49);
+        assert!(results[0]["locations"][0]["physicalLocation"]["region"].get("startColumn").is_none());
+        assert_eq!(results[0]["locations"][0]["physicalLocation"]["region"]["endLine"], 5);
+    }
+
+    #[test]
+    fn test_whole_file_finding_has_no_region() {
+        let findings = vec![Finding::for_test("/work/repo/certs/server key.pem", 0, "PEM Private Key", "keystore")];
+        let report = serde_json::to_value(build_sarif_report(&findings, Path::new("/work/repo"))).unwrap();
+
+        let location = &report["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "certs/server%20key.pem");
+        assert!(location.get("region").is_none());
+        assert_eq!(report["runs"][0]["results"][0]["level"], "warning");
+    }
+
+    #[test]
+    fn test_binary_finding_region_is_byte_offset() {
+        let findings = vec![Finding {
+            severity: Some(Severity::Info),
+            ..Finding::for_test("/work/repo/bin/server", 4096, "AES", "binary")
+        }];
+        let report = serde_json::to_value(build_sarif_report(&findings, Path::new("/work/repo"))).unwrap();
+
+        let region = &report["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region, &serde_json::json!({ "byteOffset": 4096 }));
+    }
+}
@@ -0,0 +1,466 @@
+use crate::config::Config;
+use crate::error::{config_error, report_write_error, scanner_error, ScanResult};
+use crate::utils::gitlab::{build_gitlab_sast_report, write_report_to_gitlab_sast};
+use crate::utils::html::{render_html_report, write_report_to_html, HTML_REPORT_TITLE};
+use crate::utils::jsonl::{render_jsonl, write_report_to_jsonl};
+use crate::utils::report::{
+    render_csv, render_json, write_report_to_csv, write_report_to_json_fields, FieldSelection, Finding, OutputFormat,
+};
+use crate::utils::sarif::{build_sarif_report, write_report_to_sarif};
+use crate::utils::summary::ScanSummary;
+use chrono::{DateTime, Utc};
+use log::warn;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Destination for a finished scan report
+pub trait ReportSink {
+    fn write(&mut self, findings: &[Finding]) -> ScanResult<()>;
+}
+
+/// Scan details some report formats need besides the findings themselves
+#[derive(Debug, Clone)]
+pub struct ReportContext {
+    pub start_time: DateTime<Utc>,
+    /// Path the scan was run against; SARIF locations are made relative to it
+    pub scan_root: PathBuf,
+    /// Finding fields kept in JSON reports; all of them when `None`
+    pub output_fields: Option<FieldSelection>,
+    /// Totals shown at the top of HTML reports, for scans that count their files
+    pub summary: Option<ScanSummary>,
+    /// Mask secret values in HTML reports
+    pub redact: bool,
+}
+
+impl ReportContext {
+    pub fn new(scan_root: impl Into<PathBuf>, start_time: DateTime<Utc>) -> Self {
+        ReportContext { start_time, scan_root: scan_root.into(), output_fields: None, summary: None, redact: true }
+    }
+
+    /// Keep only the selected finding fields in JSON reports
+    pub fn with_output_fields(mut self, fields: Option<FieldSelection>) -> Self {
+        self.output_fields = fields;
+        self
+    }
+
+    pub fn with_summary(mut self, summary: Option<ScanSummary>) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    /// `--no-redact` turns redaction off
+    pub fn with_redaction(mut self, redact: bool) -> Self {
+        self.redact = redact;
+        self
+    }
+}
+
+impl Default for ReportContext {
+    fn default() -> Self {
+        ReportContext::new(".", Utc::now())
+    }
+}
+
+/// Serialize findings in the given format
+pub fn render_report(findings: &[Finding], format: OutputFormat, context: &ReportContext) -> ScanResult<String> {
+    let report = match format {
+        OutputFormat::Json => render_json(findings, context.output_fields.as_ref())?,
+        OutputFormat::GitlabSast => {
+            serde_json::to_string_pretty(&build_gitlab_sast_report(findings, context.start_time, Utc::now()))?
+        }
+        OutputFormat::Sarif => serde_json::to_string_pretty(&build_sarif_report(findings, &context.scan_root))?,
+        OutputFormat::Csv => render_csv(findings),
+        OutputFormat::Jsonl => render_jsonl(findings, context.output_fields.as_ref())?,
+        OutputFormat::Html => render_html_report(findings, HTML_REPORT_TITLE, context.summary.as_ref(), context.redact),
+    };
+    Ok(report)
+}
+
+/// Whether `path` already holds exactly the report `findings` render to.
+/// A missing or unreadable file counts as changed.
+pub fn report_unchanged(path: &Path, findings: &[Finding], format: OutputFormat, context: &ReportContext) -> ScanResult<bool> {
+    let Ok(existing) = fs::read(path) else {
+        return Ok(false);
+    };
+    Ok(render_report(findings, format, context)?.as_bytes() == existing.as_slice())
+}
+
+/// Writes the report to a file, creating parent directories as needed
+pub struct FileSink {
+    pub path: PathBuf,
+    pub format: OutputFormat,
+    pub context: ReportContext,
+}
+
+impl ReportSink for FileSink {
+    fn write(&mut self, findings: &[Finding]) -> ScanResult<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        match self.format {
+            OutputFormat::Json => write_report_to_json_fields(findings, &self.path, self.context.output_fields.as_ref())?,
+            OutputFormat::GitlabSast => write_report_to_gitlab_sast(findings, &self.path, self.context.start_time)?,
+            OutputFormat::Sarif => write_report_to_sarif(findings, &self.path, &self.context.scan_root)?,
+            OutputFormat::Csv => write_report_to_csv(findings, &self.path)?,
+            OutputFormat::Jsonl => write_report_to_jsonl(findings, &self.path, self.context.output_fields.as_ref())?,
+            OutputFormat::Html => {
+                write_report_to_html(findings, &self.path, self.context.summary.as_ref(), self.context.redact)?
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the report to standard output
+pub struct StdoutSink {
+    pub format: OutputFormat,
+    pub context: ReportContext,
+}
+
+impl ReportSink for StdoutSink {
+    fn write(&mut self, findings: &[Finding]) -> ScanResult<()> {
+        let report = render_report(findings, self.format, &self.context)?;
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(report.as_bytes())?;
+        stdout.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+/// POSTs the report to a plain `http://` endpoint
+pub struct HttpSink {
+    pub url: String,
+    pub format: OutputFormat,
+    pub context: ReportContext,
+}
+
+/// Host, port and request path parsed from an `http://` URL
+pub(crate) struct HttpTarget {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl HttpTarget {
+    /// The host as written in a `Host` header, with IPv6 addresses bracketed again
+    fn host_header(&self) -> String {
+        if self.host.contains(':') {
+            format!("[{}]", self.host)
+        } else {
+            self.host.clone()
+        }
+    }
+}
+
+pub(crate) fn parse_http_url(url: &str) -> ScanResult<HttpTarget> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| config_error(&format!("Only http:// URLs are supported for report upload: {}", url)))?;
+
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => (&rest[..idx], &rest[idx..]),
+        None => (rest, "/"),
+    };
+
+    // IPv6 literals are bracketed, so the port separator is the first ':' after the ']'
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(bracketed) => {
+            let (host, after) = bracketed
+                .split_once(']')
+                .ok_or_else(|| config_error(&format!("Unclosed IPv6 address in URL: {}", url)))?;
+            match after {
+                "" => (host, None),
+                _ => match after.strip_prefix(':') {
+                    Some(port) => (host, Some(port)),
+                    None => return Err(config_error(&format!("Invalid host in URL: {}", url))),
+                },
+            }
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+    let port = match port {
+        Some(port) => port
+            .parse::<u16>()
+            .map_err(|_| config_error(&format!("Invalid port in URL: {}", url)))?,
+        None => 80,
+    };
+
+    if host.is_empty() {
+        return Err(config_error(&format!("Missing host in URL: {}", url)));
+    }
+
+    Ok(HttpTarget { host: host.to_string(), port, path: path.to_string() })
+}
+
+impl ReportSink for HttpSink {
+    fn write(&mut self, findings: &[Finding]) -> ScanResult<()> {
+        let target = parse_http_url(&self.url)?;
+        let body = render_report(findings, self.format, &self.context)?;
+
+        let mut stream = TcpStream::connect((target.host.as_str(), target.port))?;
+        stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: cryptoscan/{}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            target.path,
+            target.host_header(),
+            env!("CARGO_PKG_VERSION"),
+            self.format.content_type(),
+            body.len()
+        );
+        stream.write_all(request.as_bytes())?;
+        stream.write_all(body.as_bytes())?;
+        stream.flush()?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        let status = response
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|code| code.parse::<u16>().ok())
+            .ok_or_else(|| scanner_error("Malformed HTTP response from report endpoint"))?;
+
+        if !(200..300).contains(&status) {
+            return Err(scanner_error(&format!("Report upload to {} failed with HTTP status {}", self.url, status)));
+        }
+        Ok(())
+    }
+}
+
+/// Where the report is sent, as selected by `--output`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputTarget {
+    File(PathBuf),
+    Stdout,
+    Http(String),
+}
+
+impl OutputTarget {
+    /// Resolve the output target from the CLI configuration
+    pub fn from_config(config: &Config) -> Self {
+        match config.output.as_deref() {
+            None => OutputTarget::File(PathBuf::from(config.format.default_output_path())),
+            Some("-") => OutputTarget::Stdout,
+            Some(url) if url.starts_with("http://") || url.starts_with("https://") => OutputTarget::Http(url.to_string()),
+            Some(path) => OutputTarget::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Build the sink for this target
+    pub fn into_sink(self, format: OutputFormat, context: ReportContext) -> Box<dyn ReportSink> {
+        match self {
+            OutputTarget::File(path) => Box::new(FileSink { path, format, context }),
+            OutputTarget::Stdout => Box::new(StdoutSink { format, context }),
+            OutputTarget::Http(url) => Box::new(HttpSink { url, format, context }),
+        }
+    }
+}
+
+/// Where to send the report if the configured destination cannot be written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FallbackOutput {
+    /// Print the report to standard output
+    Stdout,
+    /// Write the report to a uniquely named file in the system temp directory
+    Temp,
+}
+
+impl FallbackOutput {
+    fn target(self, format: OutputFormat) -> OutputTarget {
+        match self {
+            FallbackOutput::Stdout => OutputTarget::Stdout,
+            FallbackOutput::Temp => {
+                let file_name = PathBuf::from(format.default_output_path())
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "findings.json".to_string());
+                OutputTarget::File(std::env::temp_dir().join(format!("cryptoscan-{}-{}", uuid::Uuid::new_v4(), file_name)))
+            }
+        }
+    }
+}
+
+/// Write findings to `target`, retrying at `fallback` if that fails.
+///
+/// Returns the destination the report actually went to. Failures are reported as
+/// `ScanError::ReportWrite` so callers can tell them apart from a failed scan.
+pub fn write_with_fallback(
+    findings: &[Finding],
+    target: OutputTarget,
+    fallback: Option<FallbackOutput>,
+    format: OutputFormat,
+    context: &ReportContext,
+) -> ScanResult<OutputTarget> {
+    let destination = target.to_string();
+    let err = match target.clone().into_sink(format, context.clone()).write(findings) {
+        Ok(()) => return Ok(target),
+        Err(err) => err,
+    };
+
+    let Some(fallback) = fallback else {
+        return Err(report_write_error(&destination, err));
+    };
+
+    let fallback_target = fallback.target(format);
+    warn!("Could not write report to {} ({}); falling back to {}", destination, err, fallback_target);
+    eprintln!("⚠️  Could not write report to {} ({}); falling back to {}", destination, err, fallback_target);
+
+    let fallback_destination = fallback_target.to_string();
+    fallback_target
+        .clone()
+        .into_sink(format, context.clone())
+        .write(findings)
+        .map_err(|e| report_write_error(&fallback_destination, e))?;
+    Ok(fallback_target)
+}
+
+impl fmt::Display for OutputTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputTarget::File(path) => write!(f, "{}", path.display()),
+            OutputTarget::Stdout => write!(f, "stdout"),
+            OutputTarget::Http(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn config_with_output(output: Option<&str>) -> Config {
+        Config { output: output.map(String::from), ..Default::default() }
+    }
+
+    #[test]
+    fn test_output_target_selection() {
+        assert_eq!(
+            OutputTarget::from_config(&config_with_output(None)),
+            OutputTarget::File(PathBuf::from("web/data/findings.json"))
+        );
+        assert_eq!(OutputTarget::from_config(&config_with_output(Some("-"))), OutputTarget::Stdout);
+        assert_eq!(
+            OutputTarget::from_config(&config_with_output(Some("http://ci:8080/ingest"))),
+            OutputTarget::Http("http://ci:8080/ingest".to_string())
+        );
+        assert_eq!(
+            OutputTarget::from_config(&config_with_output(Some("out/report.json"))),
+            OutputTarget::File(PathBuf::from("out/report.json"))
+        );
+    }
+
+    #[test]
+    fn test_unwritable_output_without_fallback_is_report_error() {
+        // A regular file as the parent makes the directory uncreatable, even when running as root
+        let blocker = std::env::temp_dir().join(format!("cryptoscan-blocker-{}", uuid::Uuid::new_v4()));
+        fs::write(&blocker, "").unwrap();
+        let target = OutputTarget::File(blocker.join("data").join("findings.json"));
+
+        let result = write_with_fallback(&[], target, None, OutputFormat::Json, &ReportContext::default());
+        assert!(matches!(result, Err(crate::error::ScanError::ReportWrite { .. })));
+        fs::remove_file(blocker).unwrap();
+    }
+
+    #[test]
+    fn test_unwritable_output_falls_back_to_temp() {
+        let blocker = std::env::temp_dir().join(format!("cryptoscan-blocker-{}", uuid::Uuid::new_v4()));
+        fs::write(&blocker, "").unwrap();
+        let target = OutputTarget::File(blocker.join("data").join("findings.json"));
+
+        let used = write_with_fallback(&[], target, Some(FallbackOutput::Temp), OutputFormat::Json, &ReportContext::default()).unwrap();
+        let OutputTarget::File(path) = used else {
+            panic!("expected a temp file fallback, got {:?}", used);
+        };
+        assert!(path.starts_with(std::env::temp_dir()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[]");
+
+        fs::remove_file(path).unwrap();
+        fs::remove_file(blocker).unwrap();
+    }
+
+    #[test]
+    fn test_parse_http_url() {
+        let target = parse_http_url("http://localhost:9000/api/findings").unwrap();
+        assert_eq!(target.host, "localhost");
+        assert_eq!(target.port, 9000);
+        assert_eq!(target.path, "/api/findings");
+
+        let target = parse_http_url("http://collector").unwrap();
+        assert_eq!(target.port, 80);
+        assert_eq!(target.path, "/");
+
+        let target = parse_http_url("http://[::1]:8080/ingest").unwrap();
+        assert_eq!(target.host, "::1");
+        assert_eq!(target.port, 8080);
+        assert_eq!(target.path, "/ingest");
+        assert_eq!(target.host_header(), "[::1]");
+
+        let target = parse_http_url("http://[fe80::1]").unwrap();
+        assert_eq!(target.host, "fe80::1");
+        assert_eq!(target.port, 80);
+
+        assert!(parse_http_url("http://[::1/ingest").is_err());
+        assert!(parse_http_url("http://[::1]8080/ingest").is_err());
+        assert!(parse_http_url("https://collector/ingest").is_err());
+    }
+
+    #[test]
+    fn test_file_sink_creates_parent_dirs() {
+        let dir = std::env::temp_dir().join(format!("cryptoscan-sink-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("nested").join("findings.json");
+        let mut sink = FileSink { path: path.clone(), format: OutputFormat::Json, context: ReportContext::default() };
+
+        sink.write(&[]).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[]");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_report_unchanged() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("findings.json");
+        let context = ReportContext::default();
+        assert!(!report_unchanged(&path, &[], OutputFormat::Json, &context).unwrap());
+
+        FileSink { path: path.clone(), format: OutputFormat::Json, context: context.clone() }.write(&[]).unwrap();
+        assert!(report_unchanged(&path, &[], OutputFormat::Json, &context).unwrap());
+        // Same findings in another format render differently
+        assert!(!report_unchanged(&path, &[], OutputFormat::Csv, &context).unwrap());
+    }
+
+    #[test]
+    fn test_http_sink_posts_report() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            stream.write_all(b"HTTP/1.1 202 Accepted\r\nContent-Length: 0\r\n\r\n").unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let mut sink = HttpSink {
+            url: format!("http://127.0.0.1:{}/ingest", port),
+            format: OutputFormat::Json,
+            context: ReportContext::default(),
+        };
+        sink.write(&[]).unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /ingest HTTP/1.1"));
+        assert!(request.contains("Content-Type: application/json"));
+    }
+}
@@ -0,0 +1,108 @@
+//! Files a scan walked but could not (fully) cover, with the reason for each.
+//!
+//! Printed as a summary at the end of a scan and, with `--report-skipped`, written as a
+//! JSON array so an audit can show exactly what the results don't speak for.
+
+use crate::error::ScanResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+/// Why a file's content wasn't scanned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkipReason {
+    /// The file could not be opened or read
+    IoError,
+    /// The content is binary rather than (possibly mis-encoded) text
+    NonUtf8,
+    /// Larger than `--max-file-size`
+    TooLarge,
+    /// Dropped by `--use-mime-filter`
+    MimeFiltered,
+    /// An archive whose remaining entries exceed `--archive-max-size` or `--archive-max-entries`
+    ArchiveLimit,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SkipReason::IoError => "unreadable",
+            SkipReason::NonUtf8 => "not UTF-8 text",
+            SkipReason::TooLarge => "too large",
+            SkipReason::MimeFiltered => "filtered by MIME type",
+            SkipReason::ArchiveLimit => "archive partly scanned",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// One file whose content the scan did not cover
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: SkipReason,
+    /// The underlying error, size or MIME type
+    pub detail: String,
+}
+
+impl SkippedFile {
+    pub fn new(path: &Path, reason: SkipReason, detail: impl Into<String>) -> Self {
+        SkippedFile { path: path.display().to_string(), reason, detail: detail.into() }
+    }
+}
+
+impl fmt::Display for SkippedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}: {})", self.path, self.reason, self.detail)
+    }
+}
+
+/// Number of skipped files per reason
+pub fn count_by_reason(skipped: &[SkippedFile]) -> BTreeMap<SkipReason, usize> {
+    let mut counts = BTreeMap::new();
+    for file in skipped {
+        *counts.entry(file.reason).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Write the skipped files as a JSON array sorted by path, creating parent directories as needed
+pub fn write_skipped_report(skipped: &[SkippedFile], path: &Path) -> ScanResult<()> {
+    let mut sorted = skipped.to_vec();
+    sorted.sort_by(|a, b| a.path.cmp(&b.path));
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = File::create(path)?;
+    file.write_all(serde_json::to_string_pretty(&sorted)?.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skipped_report() {
+        let skipped = vec![
+            SkippedFile::new(Path::new("src/z.py"), SkipReason::IoError, "Permission denied (os error 13)"),
+            SkippedFile::new(Path::new("dump.sql"), SkipReason::TooLarge, "52428800 bytes"),
+            SkippedFile::new(Path::new("src/a.py"), SkipReason::IoError, "No such file or directory (os error 2)"),
+        ];
+        let counts = count_by_reason(&skipped);
+        assert_eq!(counts[&SkipReason::IoError], 2);
+        assert_eq!(counts[&SkipReason::TooLarge], 1);
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("out/skipped.json");
+        write_skipped_report(&skipped, &path).unwrap();
+        let written: Vec<SkippedFile> = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let paths: Vec<&str> = written.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["dump.sql", "src/a.py", "src/z.py"]);
+        assert!(fs::read_to_string(&path).unwrap().contains("\"reason\": \"too-large\""));
+    }
+}
@@ -0,0 +1,148 @@
+//! `--emit-socket`: stream findings as JSON lines to a local listener while the scan runs.
+//!
+//! cryptoscan connects to a Unix domain socket that the consumer (editor plugin,
+//! pre-commit daemon) is already listening on, and writes one JSON object per line:
+//!
+//! ```text
+//! {"event":"finding","finding":{"file":"src/auth.py","line_number":12,...}}
+//! {"event":"complete","files_scanned":120,"findings":7}
+//! ```
+//!
+//! `finding` lines arrive as each file finishes scanning, in no particular file order;
+//! `--output-fields` applies to them. `complete` is the last line of a scan. If the
+//! listener goes away the scan carries on and nothing more is written.
+
+use crate::utils::report::{finding_to_value, FieldSelection, Finding};
+use log::warn;
+use serde_json::json;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+#[cfg(unix)]
+type Stream = std::os::unix::net::UnixStream;
+
+/// Writes findings to a connected socket listener, or nothing once it has disconnected
+pub struct SocketEmitter {
+    path: PathBuf,
+    #[cfg(unix)]
+    stream: Mutex<Option<Stream>>,
+    #[cfg(not(unix))]
+    stream: Mutex<Option<std::io::Sink>>,
+    fields: Option<FieldSelection>,
+}
+
+impl SocketEmitter {
+    /// Connect to the listener at `path`. A failed connection is logged and leaves the emitter inactive.
+    pub fn connect(path: &Path, fields: Option<FieldSelection>) -> Self {
+        #[cfg(unix)]
+        let stream = match Stream::connect(path) {
+            Ok(stream) => Some(stream),
+            Err(e) => {
+                warn!("Cannot connect to --emit-socket {}: {}; findings will not be streamed", path.display(), e);
+                None
+            }
+        };
+        #[cfg(not(unix))]
+        let stream = {
+            warn!("--emit-socket is only supported on Unix; findings will not be streamed");
+            None
+        };
+
+        SocketEmitter { path: path.to_path_buf(), stream: Mutex::new(stream), fields }
+    }
+
+    /// Whether the listener is still connected
+    pub fn is_connected(&self) -> bool {
+        self.stream.lock().map(|stream| stream.is_some()).unwrap_or(false)
+    }
+
+    /// Send the findings of one scanned file
+    pub fn emit(&self, findings: &[Finding]) {
+        let mut lines = String::new();
+        for finding in findings {
+            match finding_to_value(finding, self.fields.as_ref()) {
+                Ok(value) => {
+                    lines.push_str(&json!({ "event": "finding", "finding": value }).to_string());
+                    lines.push('\n');
+                }
+                Err(e) => warn!("Cannot serialize finding in {}: {}", finding.file, e),
+            }
+        }
+        self.send(&lines);
+    }
+
+    /// Tell the listener the scan is over
+    pub fn finish(&self, files_scanned: usize, findings: usize) {
+        let line = json!({ "event": "complete", "files_scanned": files_scanned, "findings": findings });
+        self.send(&format!("{}\n", line));
+    }
+
+    fn send(&self, lines: &str) {
+        if lines.is_empty() {
+            return;
+        }
+        let Ok(mut guard) = self.stream.lock() else {
+            return;
+        };
+        if let Some(stream) = guard.as_mut() {
+            if let Err(e) = stream.write_all(lines.as_bytes()).and_then(|_| stream.flush()) {
+                warn!("--emit-socket {} disconnected ({}); continuing without streaming", self.path.display(), e);
+                *guard = None;
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::utils::report::parse_output_fields;
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::net::UnixListener;
+
+    #[test]
+    fn test_findings_streamed_as_json_lines() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("scan.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let fields = parse_output_fields("file,line_number").unwrap();
+        let emitter = SocketEmitter::connect(&path, Some(fields));
+        let (client, _) = listener.accept().unwrap();
+        let findings = [Finding::for_test("a.py", 3, "API Key", "secret"), Finding::for_test("a.py", 9, "API Key", "secret")];
+        emitter.emit(&findings);
+        emitter.emit(&[]);
+        emitter.finish(2, 2);
+        drop(emitter);
+
+        let lines: Vec<serde_json::Value> = BufReader::new(client)
+            .lines()
+            .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], json!({ "event": "finding", "finding": { "file": "a.py", "line_number": 3 } }));
+        assert_eq!(lines[2], json!({ "event": "complete", "files_scanned": 2, "findings": 2 }));
+    }
+
+    #[test]
+    fn test_disconnected_listener_stops_writes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("scan.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let emitter = SocketEmitter::connect(&path, None);
+        drop(listener.accept().unwrap());
+        drop(listener);
+
+        // The first write may still be buffered by the kernel; a later one sees the broken pipe
+        for _ in 0..100 {
+            emitter.emit(&[Finding::for_test("a.py", 1, "API Key", "secret")]);
+        }
+        assert!(!emitter.is_connected());
+
+        let missing = SocketEmitter::connect(&dir.path().join("missing.sock"), None);
+        missing.emit(&[Finding::for_test("a.py", 1, "API Key", "secret")]);
+        assert!(!missing.is_connected());
+    }
+}
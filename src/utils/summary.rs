@@ -0,0 +1,224 @@
+//! Machine-readable scan summary written next to the findings report as `summary.json`,
+//! so automation can read the counts without parsing every finding.
+
+use crate::utils::report::{Finding, Severity};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const SUMMARY_FILE: &str = "summary.json";
+
+/// Findings counted by category and by severity
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct FindingCounts {
+    pub total: usize,
+    pub by_category: BTreeMap<String, usize>,
+    /// Keyed by severity name, with `unrated` for findings without one
+    pub by_severity: BTreeMap<String, usize>,
+}
+
+impl FindingCounts {
+    pub fn of(findings: &[Finding]) -> Self {
+        let mut counts = FindingCounts::default();
+        counts.record(findings);
+        counts
+    }
+
+    pub fn record(&mut self, findings: &[Finding]) {
+        self.total += findings.len();
+        for finding in findings {
+            *self.by_category.entry(finding.category.clone()).or_insert(0) += 1;
+            let severity = finding.severity.map(|s| s.to_string()).unwrap_or_else(|| "unrated".to_string());
+            *self.by_severity.entry(severity).or_insert(0) += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: FindingCounts) {
+        self.total += other.total;
+        for (category, count) in other.by_category {
+            *self.by_category.entry(category).or_insert(0) += count;
+        }
+        for (severity, count) in other.by_severity {
+            *self.by_severity.entry(severity).or_insert(0) += count;
+        }
+    }
+}
+
+/// `--cache` lookups of a scan: files whose findings were reused, and files scanned afresh
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+/// Where a scan's time went, in milliseconds. `walk_ms` and `scan_ms` are wall-clock time;
+/// the per-scanner times are summed over all worker threads.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ScanTimings {
+    /// Finding the files to scan
+    pub walk_ms: u64,
+    /// Scanning them, from the first file started to the last one finished
+    pub scan_ms: u64,
+    pub code_ms: u64,
+    pub secrets_ms: u64,
+    pub artefacts_ms: u64,
+}
+
+/// Totals of one scan, self-describing enough to be archived on its own
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScanSummary {
+    pub tool: String,
+    pub version: String,
+    pub scanned_path: String,
+    /// When the scan finished
+    pub timestamp: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub files_scanned: usize,
+    /// Files whose content wasn't scanned (unreadable, binary, too large, MIME-filtered)
+    pub files_skipped: usize,
+    pub total_findings: usize,
+    pub findings_by_category: BTreeMap<String, usize>,
+    pub findings_by_severity: BTreeMap<String, usize>,
+    /// Only present for scans run with `--cache`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheStats>,
+    /// Absent from summaries of scans that weren't timed (written before timings were recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<ScanTimings>,
+}
+
+impl ScanSummary {
+    pub fn new(
+        scanned_path: &str,
+        start_time: DateTime<Utc>,
+        files_scanned: usize,
+        files_skipped: usize,
+        counts: FindingCounts,
+    ) -> Self {
+        let timestamp = Utc::now();
+        ScanSummary {
+            tool: "CryptoScanner".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            scanned_path: scanned_path.to_string(),
+            timestamp,
+            duration_ms: (timestamp - start_time).num_milliseconds().max(0) as u64,
+            files_scanned,
+            files_skipped,
+            total_findings: counts.total,
+            findings_by_category: counts.by_category,
+            findings_by_severity: counts.by_severity,
+            cache: None,
+            timings: None,
+        }
+    }
+
+    /// `summary.json` in the report's directory, or `<report>.summary.json` if the report itself
+    /// is called `summary.json`
+    pub fn sibling_path(report: &Path) -> PathBuf {
+        let dir = report.parent().unwrap_or_else(|| Path::new(""));
+        if report.file_name().is_some_and(|name| name == SUMMARY_FILE) {
+            return dir.join(format!("{}.{}", report.file_stem().unwrap_or_default().to_string_lossy(), SUMMARY_FILE));
+        }
+        dir.join(SUMMARY_FILE)
+    }
+
+    /// The one-line result printed at the end of every CLI scan, e.g.
+    /// `cryptoscan: 12 findings (3 critical, 4 high, 5 unrated) across 1423 files in 2.1s`.
+    /// Severities are the `--fail-on` levels, most severe first; with `fail_on` the count at
+    /// or above it (the findings that fail the run) is appended. `scanned` names what was
+    /// scanned (`file`, `endpoint`), and `new` marks findings left by a baseline.
+    pub fn status_line(&self, scanned: &str, new: bool, fail_on: Option<Severity>) -> String {
+        let plural = |count: usize, noun: &str| format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" });
+        let finding = if new { "new finding" } else { "finding" };
+        let mut line = format!("cryptoscan: {}", plural(self.total_findings, finding));
+
+        let count = |severity: &str| self.findings_by_severity.get(severity).copied().unwrap_or(0);
+        let severities = [Severity::Critical, Severity::High, Severity::Medium, Severity::Low, Severity::Info];
+        let buckets: Vec<String> = severities
+            .iter()
+            .map(Severity::to_string)
+            .chain(["unrated".to_string()])
+            .filter(|severity| count(severity) > 0)
+            .map(|severity| format!("{} {}", count(&severity), severity))
+            .collect();
+        if !buckets.is_empty() {
+            line.push_str(&format!(" ({})", buckets.join(", ")));
+        }
+        line.push_str(&format!(" across {} in {}", plural(self.files_scanned, scanned), format_ms(self.duration_ms)));
+
+        if let Some(level) = fail_on {
+            let failing: usize = severities.iter().filter(|s| **s >= level).map(|s| count(&s.to_string())).sum();
+            line.push_str(&format!("; {} at or above {} (--fail-on)", failing, level));
+        }
+        line
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Milliseconds as `850ms` or `12.4s`
+pub(crate) fn format_ms(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_merge() {
+        let mut counts = FindingCounts::default();
+        counts.by_category.insert("library".to_string(), 2);
+        counts.by_severity.insert("unrated".to_string(), 2);
+        counts.total = 2;
+        let mut other = counts.clone();
+        other.by_category.insert("secret".to_string(), 1);
+        other.total = 3;
+
+        counts.merge(other);
+        assert_eq!(counts.total, 5);
+        assert_eq!(counts.by_category["library"], 4);
+        assert_eq!(counts.by_category["secret"], 1);
+        assert_eq!(counts.by_severity["unrated"], 4);
+    }
+
+    #[test]
+    fn test_status_line() {
+        let mut counts = FindingCounts { total: 12, ..FindingCounts::default() };
+        for (severity, count) in [("critical", 3), ("high", 4), ("low", 1), ("unrated", 4)] {
+            counts.by_severity.insert(severity.to_string(), count);
+        }
+        let mut summary = ScanSummary::new("./src", Utc::now(), 1423, 0, counts);
+        summary.duration_ms = 2140;
+
+        assert_eq!(
+            summary.status_line("file", false, None),
+            "cryptoscan: 12 findings (3 critical, 4 high, 1 low, 4 unrated) across 1423 files in 2.1s"
+        );
+        // Unrated findings never fail the run, as with --fail-on itself
+        assert!(summary.status_line("file", false, Some(Severity::Low)).ends_with("; 8 at or above low (--fail-on)"));
+
+        let mut summary = ScanSummary::new("<stdin>", Utc::now(), 1, 0, FindingCounts::default());
+        summary.duration_ms = 35;
+        assert_eq!(summary.status_line("file", true, None), "cryptoscan: 0 new findings across 1 file in 35ms");
+    }
+
+    #[test]
+    fn test_sibling_path() {
+        assert_eq!(ScanSummary::sibling_path(Path::new("web/data/findings.json")), PathBuf::from("web/data/summary.json"));
+        assert_eq!(ScanSummary::sibling_path(Path::new("findings.csv")), PathBuf::from("summary.json"));
+        assert_eq!(ScanSummary::sibling_path(Path::new("out/summary.json")), PathBuf::from("out/summary.summary.json"));
+    }
+}
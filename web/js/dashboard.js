@@ -11,6 +11,8 @@ const itemsPerPage = 25;
 let sortConfig = { field: null, direction: 'asc' };
 let scanInProgress = false;
 let scanStatusInterval = null;
+let currentScanId = null;
+let scanEventSource = null;
 
 // Color schemes for professional look
 const colorSchemes = {
@@ -368,7 +370,10 @@ console.log('Starting dashboard initialization...');
 // Wait for DOM to be ready and Chart.js to load
 function startDashboard() {
   console.log('Starting data fetch...');
-  fetch('data/findings.json')
+  // After a server scan the page is reloaded with ?scan=<id> to show that scan's results
+  const scanId = new URLSearchParams(window.location.search).get('scan');
+  const source = scanId ? `/api/scan/results/${encodeURIComponent(scanId)}` : 'data/findings.json';
+  fetch(source)
     .then(response => {
       console.log('Fetch response:', response.status, response.statusText);
       if (!response.ok) {
@@ -1015,10 +1020,33 @@ function showScanProgress(message) {
   }
 }
 
-// Start polling for scan status
+// Follow scan status: pushed over Server-Sent Events, or polled where EventSource is unavailable
 function startScanStatusPolling(scanId) {
-  console.log('Starting scan status polling for ID:', scanId);
+  console.log('Following scan status for ID:', scanId);
+  currentScanId = scanId;
   
+  if (window.EventSource) {
+    scanEventSource = new EventSource(`/api/scan/events/${encodeURIComponent(scanId)}`);
+    scanEventSource.addEventListener('status', event => {
+      handleScanStatus(JSON.parse(event.data));
+    });
+    scanEventSource.onerror = () => {
+      // Connection lost before the scan ended: fall back to polling
+      if (scanEventSource) {
+        console.warn('Scan event stream interrupted, polling instead');
+        scanEventSource.close();
+        scanEventSource = null;
+        startPolling(scanId);
+      }
+    };
+    return;
+  }
+  
+  startPolling(scanId);
+}
+
+// Poll for scan status
+function startPolling(scanId) {
   scanStatusInterval = setInterval(() => {
     checkScanStatus(scanId);
   }, 2000); // Poll every 2 seconds
@@ -1027,6 +1055,18 @@ function startScanStatusPolling(scanId) {
   checkScanStatus(scanId);
 }
 
+// Stop following scan status
+function stopScanStatusUpdates() {
+  if (scanStatusInterval) {
+    clearInterval(scanStatusInterval);
+    scanStatusInterval = null;
+  }
+  if (scanEventSource) {
+    scanEventSource.close();
+    scanEventSource = null;
+  }
+}
+
 // Check scan status
 function checkScanStatus(scanId) {
   fetch(`/api/scan/status/${scanId}`)
@@ -1036,73 +1076,82 @@ function checkScanStatus(scanId) {
       }
       return response.json();
     })
-    .then(data => {
-      console.log('Scan status:', data);
-      
-      if (data.status === 'completed') {
-        // Scan completed successfully
-        clearInterval(scanStatusInterval);
-        scanStatusInterval = null;
-        
-        showScanProgress('Scan completed! Loading results...');
-        
-        // Reload data and refresh dashboard
-        setTimeout(() => {
-          location.reload();
-        }, 1500);
-        
-      } else if (data.status === 'failed') {
-        // Scan failed
-        clearInterval(scanStatusInterval);
-        scanStatusInterval = null;
-        
-        console.error('Scan failed:', data.error);
-        alert('Scan failed: ' + (data.error || 'Unknown error'));
-        resetScanUI();
-        
-      } else if (data.status === 'running') {
-        // Scan still in progress
-        const progress = data.progress || 'Scanning in progress...';
-        showScanProgress(progress);
-        
-      } else {
-        // Unknown status
-        console.warn('Unknown scan status:', data.status);
-      }
-    })
+    .then(handleScanStatus)
     .catch(error => {
       console.error('Error checking scan status:', error);
       
       // If we get repeated errors, stop polling and reset UI
       if (scanStatusInterval) {
-        clearInterval(scanStatusInterval);
-        scanStatusInterval = null;
+        stopScanStatusUpdates();
         alert('Lost connection to scan process. Please refresh the page to check results.');
         resetScanUI();
       }
     });
 }
 
+// Update the UI for a scan status update
+function handleScanStatus(data) {
+  console.log('Scan status:', data);
+  
+  if (data.status === 'completed') {
+    // Scan completed successfully
+    stopScanStatusUpdates();
+    
+    showScanProgress('Scan completed! Loading results...');
+    
+    // Reload the dashboard with this scan's results
+    const scanId = currentScanId;
+    setTimeout(() => {
+      window.location.search = `?scan=${encodeURIComponent(scanId)}`;
+    }, 1500);
+    
+  } else if (data.status === 'failed') {
+    // Scan failed
+    stopScanStatusUpdates();
+    
+    console.error('Scan failed:', data.error);
+    alert('Scan failed: ' + (data.error || 'Unknown error'));
+    resetScanUI();
+    
+  } else if (data.status === 'cancelled') {
+    // Scan stopped at the user's request
+    stopScanStatusUpdates();
+    resetScanUI();
+    
+  } else if (data.status === 'running') {
+    // Scan still in progress
+    const progress = data.progress || 'Scanning in progress...';
+    showScanProgress(progress);
+    
+  } else {
+    // Unknown status
+    console.warn('Unknown scan status:', data.status);
+  }
+}
+
 // Cancel ongoing scan
 function cancelScan() {
-  if (scanStatusInterval) {
-    clearInterval(scanStatusInterval);
-    scanStatusInterval = null;
+  stopScanStatusUpdates();
+  
+  if (!currentScanId) {
+    resetScanUI();
+    return;
   }
   
-  // Try to cancel the scan on the server
-  fetch('/api/scan/cancel', {
+  // Ask the server to stop the scan between files
+  fetch(`/api/scan/cancel/${encodeURIComponent(currentScanId)}`, {
     method: 'POST'
   })
   .then(response => {
     if (response.ok) {
-      console.log('Scan cancelled');
+      console.log('Scan cancellation requested');
     }
   })
   .catch(error => {
     console.error('Error cancelling scan:', error);
   })
   .finally(() => {
+    currentScanId = null;
     resetScanUI();
   });
 }